@@ -0,0 +1,135 @@
+//! This module provides `CounterBuilder`, which assembles a `Counter` from
+//! whichever endpoints are known and checks that the result makes sense.
+use crate::errors::BuildError;
+use crate::times::{Time, TimeUnits};
+use crate::{Counter, Direction};
+use std::ops::Sub;
+
+/// Builds a `Counter`, checking the endpoints that `Counter::down`/`Counter::up`
+/// take as given.
+///
+/// Like there, a missing `start` is taken to be now. Unlike there, a `Down`
+/// counter needs an `end`, given directly or `lasting` a duration from
+/// `start`, rather than ending now, and an `end` before `start` is rejected.
+/// Counters count `Down` unless told otherwise, and an `Up` counter may leave
+/// out its `end` to be open-ended.
+/// # Examples
+/// ```rust
+/// # use countrs::{CounterBuilder, Direction, Time, TimeUnits};
+/// # use countrs::types::{Duration, TimeStamp};
+/// let pomodoro = CounterBuilder::<TimeStamp>::new()
+///     .starting_now()
+///     .lasting(Duration::seconds(1500))
+///     .build()
+///     .unwrap();
+/// assert_eq!(pomodoro.to_string(), "00:24:59");
+///
+/// let stopwatch = CounterBuilder::<TimeStamp>::new()
+///     .start(TimeStamp::now() - 90)
+///     .direction(Direction::Up)
+///     .build()
+///     .unwrap();
+/// assert_eq!(stopwatch.to_string(), "00:01:30");
+/// ```
+/// Nonsensical combinations are rejected:
+/// ```rust
+/// # use countrs::{BuildError, CounterBuilder, Time};
+/// # use countrs::types::TimeStamp;
+/// let now = TimeStamp::now();
+/// let built = CounterBuilder::new().start(now).end(now - 60).build();
+/// assert_eq!(built, Err(BuildError::EndBeforeStart));
+///
+/// assert_eq!(CounterBuilder::<TimeStamp>::new().build(), Err(BuildError::MissingEndpoints));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct CounterBuilder<T: Time> {
+    start: Option<T>,
+    end: Option<T>,
+    lasting: Option<T::Duration>,
+    direction: Direction,
+}
+
+impl<T, D> CounterBuilder<T>
+where
+    T: Copy + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits + PartialOrd,
+{
+    pub fn new() -> CounterBuilder<T> {
+        CounterBuilder {
+            start: None,
+            end: None,
+            lasting: None,
+            direction: Direction::Down,
+        }
+    }
+
+    pub fn start(mut self, start: T) -> CounterBuilder<T> {
+        self.start = Some(start);
+        self
+    }
+
+    /// Sets `start` to the current time.
+    pub fn starting_now(self) -> CounterBuilder<T> {
+        self.start(T::now())
+    }
+
+    pub fn end(mut self, end: T) -> CounterBuilder<T> {
+        self.end = Some(end);
+        self
+    }
+
+    /// Sets `end` to `start` plus `duration`, once `start` is known.
+    /// For `Up` counters, this is their goal, see `Counter::goal`.
+    pub fn lasting(mut self, duration: impl Into<D>) -> CounterBuilder<T> {
+        self.lasting = Some(duration.into());
+        self
+    }
+
+    pub fn direction(mut self, direction: Direction) -> CounterBuilder<T> {
+        self.direction = direction;
+        self
+    }
+
+    /// Assembles the counter, or returns which endpoint is missing or wrong.
+    /// A missing `start` is read from `T::now()` here, which also records it
+    /// as the counter's `created_at`.
+    pub fn build(self) -> Result<Counter<T>, BuildError> {
+        if self.start.is_none() && self.end.is_none() && self.lasting.is_none() {
+            return Err(BuildError::MissingEndpoints);
+        }
+        let (start, now) = match self.start {
+            Some(start) => (start, None),
+            None => {
+                let now = T::now();
+                (now, Some(now))
+            }
+        };
+        let end = match (self.end, self.lasting, self.direction) {
+            (Some(_), Some(_), _) => return Err(BuildError::ConflictingEnd),
+            (Some(end), None, _) => end,
+            (None, Some(lasting), _) => start
+                .add_seconds(lasting)
+                .map_err(|_| BuildError::Overflow)?,
+            (None, None, Direction::Up) => start,
+            (None, None, Direction::Down) => return Err(BuildError::MissingEnd),
+        };
+        if end - start < D::seconds(0) {
+            return Err(BuildError::EndBeforeStart);
+        }
+        let counter = Counter::new(start, end, self.direction);
+        Ok(match now {
+            Some(now) => counter.stamped(now),
+            None => counter,
+        })
+    }
+}
+
+impl<T, D> Default for CounterBuilder<T>
+where
+    T: Copy + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits + PartialOrd,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,213 @@
+//! This module provides `Schedule`, a set of weekly active hours, for
+//! counters that only count while a schedule is active, e.g. business hours.
+use crate::reading::{write_hms, CounterReading};
+use crate::times::Time;
+use crate::types::{Duration, TimeStamp};
+use crate::{Counter, Direction, TimeUnits};
+use chrono::{
+    self, DateTime, Datelike, FixedOffset, NaiveDateTime, NaiveTime, Offset, Utc, Weekday,
+};
+use std::fmt::{self, Display, Formatter};
+
+const WEEKDAYS: [Weekday; 5] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+];
+
+/// Weekly windows of active time, in a fixed UTC offset. A window whose end
+/// is not after its start runs past midnight into the next day. Windows
+/// should not overlap, or the overlap is counted twice.
+/// ```rust
+/// # use countrs::business::Schedule;
+/// # use countrs::TimeUnits;
+/// # use chrono::NaiveTime;
+/// let nine = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+/// let five = NaiveTime::from_hms_opt(17, 0, 0).unwrap();
+/// let office = Schedule::new().weekdays(nine, five);
+/// assert_eq!(office.weekly_total().num_hours(), 40);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Schedule {
+    offset: FixedOffset,
+    windows: Vec<(Weekday, NaiveTime, chrono::Duration)>,
+}
+
+impl Schedule {
+    /// An empty schedule in UTC
+    pub fn new() -> Schedule {
+        Schedule::in_offset(Utc.fix())
+    }
+
+    /// An empty schedule whose windows are given in `offset`
+    pub fn in_offset(offset: FixedOffset) -> Schedule {
+        Schedule {
+            offset,
+            windows: Vec::new(),
+        }
+    }
+
+    /// Adds a window on `day` from `start` until `end`.
+    pub fn window(mut self, day: Weekday, start: NaiveTime, end: NaiveTime) -> Schedule {
+        let length = match end - start {
+            length if length > chrono::Duration::zero() => length,
+            length => length + chrono::Duration::days(1),
+        };
+        self.windows.push((day, start, length));
+        self
+    }
+
+    /// Adds a window from `start` until `end` on each day from Monday to Friday.
+    pub fn weekdays(self, start: NaiveTime, end: NaiveTime) -> Schedule {
+        WEEKDAYS
+            .into_iter()
+            .fold(self, |schedule, day| schedule.window(day, start, end))
+    }
+
+    /// The active time in a whole week
+    pub fn weekly_total(&self) -> Duration {
+        self.weekly().into()
+    }
+
+    fn weekly(&self) -> chrono::Duration {
+        self.windows
+            .iter()
+            .fold(chrono::Duration::zero(), |total, (_, _, length)| {
+                total + *length
+            })
+    }
+
+    /// The active time between `from` and `to`, negative if `to` is first.
+    pub fn active_between(&self, from: TimeStamp, to: TimeStamp) -> Duration {
+        if to < from {
+            return -self.active_between(to, from);
+        }
+        let local = |time: TimeStamp| {
+            DateTime::<Utc>::from(time)
+                .with_timezone(&self.offset)
+                .naive_local()
+        };
+        let (from, to) = (local(from), local(to));
+        // Whole weeks all look the same, so only the rest is walked day by day
+        let weeks = i32::try_from((to - from).num_weeks()).unwrap_or(i32::MAX);
+        let from = from + chrono::Duration::weeks(i64::from(weeks));
+        (self.weekly() * weeks + self.active_within(from, to)).into()
+    }
+
+    /// The active time between `from` and `to`, at most a week apart
+    fn active_within(&self, from: NaiveDateTime, to: NaiveDateTime) -> chrono::Duration {
+        let zero = chrono::Duration::zero();
+        let mut total = zero;
+        // Windows from the day before may run past midnight
+        let mut date = from.date().pred_opt().unwrap_or(from.date());
+        while date <= to.date() {
+            for (_, start, length) in self
+                .windows
+                .iter()
+                .filter(|(day, ..)| *day == date.weekday())
+            {
+                let start = date.and_time(*start);
+                let overlap = (start + *length).min(to) - start.max(from);
+                total += overlap.max(zero);
+            }
+            match date.succ_opt() {
+                Some(next) => date = next,
+                None => break,
+            }
+        }
+        total
+    }
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Schedule::new()
+    }
+}
+
+impl Counter<TimeStamp> {
+    /// Reads the counter counting only the time during which `schedule` is
+    /// active, e.g. working hours until a deadline.
+    /// ```rust
+    /// # use countrs::business::Schedule;
+    /// # use countrs::Counter;
+    /// # use countrs::types::TimeStamp;
+    /// # use chrono::{NaiveTime, TimeZone, Utc};
+    /// let nine = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+    /// let five = NaiveTime::from_hms_opt(17, 0, 0).unwrap();
+    /// let office = Schedule::new().weekdays(nine, five);
+    ///
+    /// // From Thursday noon until Monday noon
+    /// let thursday = TimeStamp::from(Utc.with_ymd_and_hms(2024, 3, 7, 12, 0, 0).unwrap());
+    /// let monday = TimeStamp::from(Utc.with_ymd_and_hms(2024, 3, 11, 12, 0, 0).unwrap());
+    /// let deadline = Counter::down(Some(thursday), Some(monday));
+    /// assert_eq!(deadline.with_schedule(&office).at(thursday).to_string(), "16:00:00");
+    /// ```
+    pub fn with_schedule<'a>(&'a self, schedule: &'a Schedule) -> OnSchedule<'a> {
+        OnSchedule {
+            counter: self,
+            schedule,
+        }
+    }
+}
+
+/// A `Counter` that only counts while a `Schedule` is active, returned by
+/// `Counter::with_schedule`. Partial seconds are dropped.
+pub struct OnSchedule<'a> {
+    counter: &'a Counter<TimeStamp>,
+    schedule: &'a Schedule,
+}
+
+impl OnSchedule<'_> {
+    /// The active time from `reference` until `end`, negative once past it.
+    /// A paused counter is evaluated at the moment of pausing instead.
+    pub fn remaining_at(&self, reference: TimeStamp) -> Duration {
+        let reference = self.counter.reading_time(reference);
+        self.schedule.active_between(reference, self.counter.end)
+    }
+
+    /// The active time from `start` until `reference`, negative before it.
+    /// A paused counter is evaluated at the moment of pausing instead.
+    pub fn elapsed_at(&self, reference: TimeStamp) -> Duration {
+        let reference = self.counter.reading_time(reference);
+        self.schedule.active_between(self.counter.start, reference)
+    }
+
+    /// `remaining_at` the current time
+    pub fn remaining(&self) -> Duration {
+        self.remaining_at(TimeStamp::now())
+    }
+
+    /// `elapsed_at` the current time
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed_at(TimeStamp::now())
+    }
+
+    /// Like `Counter::at`
+    pub fn at(&self, reference: TimeStamp) -> CounterReading {
+        let shown = match self.counter.direction {
+            Direction::Down => self.remaining_at(reference),
+            Direction::Up => self.elapsed_at(reference),
+        };
+        CounterReading::new(shown.num_seconds())
+    }
+
+    /// Like `Counter::breakdown`
+    pub fn breakdown(&self) -> CounterReading {
+        self.at(TimeStamp::now())
+    }
+
+    /// Like `Counter::counter`
+    pub fn counter(&self) -> (i64, i64, i64) {
+        self.breakdown().clamped_hms()
+    }
+}
+
+/// Shows the time left (or elapsed) on the schedule, as "HH:MM:SS"
+impl Display for OnSchedule<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_hms(f, self.counter())
+    }
+}
@@ -2,13 +2,13 @@
 //! for `chrono`'s `DateTime<Tz>`, as long as `From<DateTime<Utc>>`
 //! is implemented for `DateTime<Tz>`.
 use crate::errors::TimeOverflow;
-use crate::{Time, TimeUnits};
-use chrono::{DateTime, Duration, TimeZone, Utc};
-use std::ops::Add;
+use crate::times::humanize;
+use crate::{Counter, Time, TimeUnits};
+use chrono::{DateTime, Duration, Local, TimeZone, Utc};
+use std::fmt::Display;
 
 impl<Tz: TimeZone + 'static> Time for DateTime<Tz>
 where
-    &'static DateTime<Tz>: Add<Duration, Output = DateTime<Tz>>,
     DateTime<Tz>: From<DateTime<Utc>>,
 {
     type Duration = Duration;
@@ -21,16 +21,93 @@ where
     where
         Self: Sized,
     {
-        Ok(self + duration)
+        self.checked_add_signed(duration).ok_or(TimeOverflow)
+    }
+
+    fn max_value() -> Option<Self> {
+        Some(DateTime::<Utc>::MAX_UTC.into())
+    }
+
+    fn min_value() -> Option<Self> {
+        Some(DateTime::<Utc>::MIN_UTC.into())
     }
 }
 
+/// `Duration::seconds` panics outside of roughly ±`i64::MAX` milliseconds.
+pub(crate) fn checked_seconds(seconds: i64) -> Option<Duration> {
+    let range = Duration::MIN.num_seconds()..=Duration::MAX.num_seconds();
+    range.contains(&seconds).then(|| Duration::seconds(seconds))
+}
+
 impl TimeUnits for Duration {
     fn seconds(seconds: i64) -> Self {
         Duration::seconds(seconds)
     }
 
+    fn try_seconds(seconds: i64) -> Result<Self, TimeOverflow> {
+        checked_seconds(seconds).ok_or(TimeOverflow)
+    }
+
     fn num_seconds(&self) -> i64 {
         self.num_seconds()
     }
+
+    fn subsec_nanos(&self) -> i64 {
+        let rest = *self - Duration::seconds(self.num_seconds());
+        rest.num_nanoseconds().unwrap_or_default()
+    }
+
+    fn humanize(&self) -> String {
+        humanize(self.num_seconds(), TimeUnits::subsec_nanos(self))
+    }
+}
+
+impl_duration_cmp!(
+    [Tz: TimeZone + 'static] DateTime<Tz> => Duration
+    where
+        Tz::Offset: Copy,
+        DateTime<Tz>: From<DateTime<Utc>>,
+);
+
+impl<Tz: TimeZone> Counter<DateTime<Tz>> {
+    /// `end` as a wall clock time in the local time zone, see `deadline_string_at`.
+    pub fn deadline_string(&self) -> String {
+        self.deadline_string_at(&Local::now())
+    }
+
+    /// `end` as a wall clock time in the time zone of `now`: "%H:%M" on the
+    /// same day as `now`, "%a %H:%M" on the six days after it, and the date
+    /// ("%Y-%m-%d") further out or on any day before it.
+    /// ```rust
+    /// # use countrs::Counter;
+    /// # use chrono::{FixedOffset, TimeZone};
+    /// let berlin = FixedOffset::east_opt(2 * 3600).unwrap();
+    /// let now = berlin.with_ymd_and_hms(2024, 3, 4, 15, 17, 0).unwrap();
+    /// let end = berlin.with_ymd_and_hms(2024, 3, 4, 17, 30, 0).unwrap();
+    /// let meeting = Counter::down(Some(now), Some(end));
+    /// assert_eq!(meeting.deadline_string_at(&now), "17:30");
+    /// assert_eq!(meeting.deadline_string_at(&(now - chrono::Duration::days(1))), "Mon 17:30");
+    /// ```
+    pub fn deadline_string_at<Z: TimeZone>(&self, now: &DateTime<Z>) -> String
+    where
+        Z::Offset: Display,
+    {
+        deadline_string(&self.end, now)
+    }
+}
+
+/// Formats `end` for `Counter::deadline_string_at`.
+pub(crate) fn deadline_string<Tz, Z>(end: &DateTime<Tz>, now: &DateTime<Z>) -> String
+where
+    Tz: TimeZone,
+    Z: TimeZone,
+    Z::Offset: Display,
+{
+    let end = end.with_timezone(&now.timezone());
+    let fmt = match (end.date_naive() - now.date_naive()).num_days() {
+        0 => "%H:%M",
+        1..=6 => "%a %H:%M",
+        _ => "%Y-%m-%d",
+    };
+    end.format(fmt).to_string()
 }
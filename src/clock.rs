@@ -0,0 +1,67 @@
+//! This module provides the `Clock` trait, through which a `Counter` can be
+//! read against a time source other than `Time::now`, along with
+//! `SystemClock`, `FixedClock` and the manually advanced `TestClock`.
+use crate::errors::TimeOverflow;
+use crate::times::Time;
+use std::cell::Cell;
+
+/// A source of the current time
+pub trait Clock<T> {
+    fn now(&self) -> T;
+}
+
+impl<T, C: Clock<T> + ?Sized> Clock<T> for &C {
+    fn now(&self) -> T {
+        (**self).now()
+    }
+}
+
+/// Reads `Time::now`, as `Counter` does by default
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl<T: Time> Clock<T> for SystemClock {
+    fn now(&self) -> T {
+        T::now()
+    }
+}
+
+/// Always reads the same time
+#[derive(Debug, PartialEq, Eq, Default, Clone, Copy)]
+pub struct FixedClock<T>(pub T);
+
+impl<T: Copy> Clock<T> for FixedClock<T> {
+    fn now(&self) -> T {
+        self.0
+    }
+}
+
+/// Reads a time that only changes through `set` and `advance`
+#[derive(Default)]
+pub struct TestClock<T> {
+    time: Cell<T>,
+}
+
+impl<T: Time + Copy> TestClock<T> {
+    pub fn new(time: T) -> TestClock<T> {
+        TestClock {
+            time: Cell::new(time),
+        }
+    }
+
+    pub fn set(&self, time: T) {
+        self.time.set(time);
+    }
+
+    /// Moves the clock by `duration`, leaving it unchanged on overflow.
+    pub fn advance(&self, duration: impl Into<T::Duration>) -> Result<(), TimeOverflow> {
+        self.time.set(self.time.get().add_seconds(duration.into())?);
+        Ok(())
+    }
+}
+
+impl<T: Copy> Clock<T> for TestClock<T> {
+    fn now(&self) -> T {
+        self.time.get()
+    }
+}
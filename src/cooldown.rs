@@ -0,0 +1,129 @@
+//! This module provides `Cooldown`, which tracks when something may be used
+//! again, e.g. an ability in a game or a rate-limited action.
+use crate::errors::NotReady;
+use crate::times::{Time, TimeUnits};
+use crate::{is_positive, saturating_add};
+use std::ops::Sub;
+
+/// Allows `charges` uses at once, each of which takes `duration` to recharge.
+/// Charges recharge one after the other, like most abilities with stacks.
+/// ```rust
+/// # use countrs::{Cooldown, NotReady};
+/// # use countrs::unix::{Seconds, UnixSeconds};
+/// let mut dash = Cooldown::new(Seconds(10));
+/// assert_eq!(dash.trigger_at(UnixSeconds(0)), Ok(()));
+/// assert_eq!(dash.trigger_at(UnixSeconds(4)), Err(NotReady));
+/// assert_eq!(dash.remaining_at(UnixSeconds(4)), Seconds(6));
+/// assert_eq!(dash.trigger_at(UnixSeconds(10)), Ok(()));
+///
+/// let mut blink = Cooldown::with_charges(Seconds(10), 2);
+/// assert_eq!(blink.trigger_at(UnixSeconds(0)), Ok(()));
+/// assert_eq!(blink.trigger_at(UnixSeconds(1)), Ok(()));
+/// assert_eq!(blink.charges_at(UnixSeconds(1)), 0);
+/// // The first charge is back after 10 seconds, the second after 20
+/// assert_eq!(blink.charges_at(UnixSeconds(10)), 1);
+/// assert_eq!(blink.remaining_at(UnixSeconds(10)), Seconds(0));
+/// assert_eq!(blink.charges_at(UnixSeconds(20)), 2);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Cooldown<T: Time> {
+    duration: T::Duration,
+    charges: u32,
+    /// When each spent charge is back, in order
+    recharged_at: Vec<T>,
+}
+
+impl<T, D> Cooldown<T>
+where
+    T: Copy + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits + Copy,
+{
+    /// A cooldown with a single charge
+    pub fn new(duration: impl Into<D>) -> Cooldown<T> {
+        Cooldown::with_charges(duration, 1)
+    }
+
+    /// A cooldown with up to `charges` uses at once, but at least one.
+    pub fn with_charges(duration: impl Into<D>, charges: u32) -> Cooldown<T> {
+        Cooldown {
+            duration: duration.into(),
+            charges: charges.max(1),
+            recharged_at: Vec::new(),
+        }
+    }
+
+    pub fn duration(&self) -> D {
+        self.duration
+    }
+
+    /// The number of charges when all are ready
+    pub fn max_charges(&self) -> u32 {
+        self.charges
+    }
+
+    /// Uses up a charge, failing if none is ready. A failed trigger changes
+    /// nothing. Recharge times saturate at the backend's limits.
+    pub fn trigger(&mut self) -> Result<(), NotReady> {
+        self.trigger_at(T::now())
+    }
+
+    /// `trigger` if the clock showed `reference`
+    pub fn trigger_at(&mut self, reference: T) -> Result<(), NotReady> {
+        self.recharged_at
+            .retain(|ready| is_positive(&(*ready - reference)));
+        if self.recharged_at.len() >= self.charges as usize {
+            return Err(NotReady);
+        }
+        let from = self.recharged_at.last().copied().unwrap_or(reference);
+        self.recharged_at.push(saturating_add(from, self.duration));
+        Ok(())
+    }
+
+    /// `true` while at least one charge is ready
+    pub fn ready(&self) -> bool {
+        self.ready_at(T::now())
+    }
+
+    /// `ready` if the clock showed `reference`
+    pub fn ready_at(&self, reference: T) -> bool {
+        self.charges_at(reference) > 0
+    }
+
+    /// The number of charges ready to use
+    pub fn charges(&self) -> u32 {
+        self.charges_at(T::now())
+    }
+
+    /// `charges` if the clock showed `reference`
+    pub fn charges_at(&self, reference: T) -> u32 {
+        let spent = self
+            .recharged_at
+            .iter()
+            .filter(|ready| is_positive(&(**ready - reference)))
+            .count();
+        self.charges.saturating_sub(spent as u32)
+    }
+
+    /// The time until the next charge is ready, or zero while one is.
+    pub fn remaining(&self) -> D {
+        self.remaining_at(T::now())
+    }
+
+    /// `remaining` if the clock showed `reference`
+    pub fn remaining_at(&self, reference: T) -> D {
+        match self.ready_at(reference) {
+            true => D::seconds(0),
+            false => self
+                .recharged_at
+                .iter()
+                .map(|ready| *ready - reference)
+                .find(is_positive)
+                .unwrap_or_else(|| D::seconds(0)),
+        }
+    }
+
+    /// Makes all charges ready again.
+    pub fn reset(&mut self) {
+        self.recharged_at.clear();
+    }
+}
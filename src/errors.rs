@@ -1,4 +1,5 @@
 use std::fmt::{Display, Formatter, Result};
+use std::io;
 
 #[derive(Debug)]
 pub struct InvalidDirection;
@@ -11,14 +12,31 @@ impl Display for InvalidDirection {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct TimeParserError;
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct TimeParserError {
+    tried: &'static [&'static str],
+}
+
+impl TimeParserError {
+    pub(crate) fn new(tried: &'static [&'static str]) -> Self {
+        TimeParserError { tried }
+    }
+
+    /// The formats the input was checked against, in order
+    pub fn tried(&self) -> &'static [&'static str] {
+        self.tried
+    }
+}
 
 impl std::error::Error for TimeParserError {}
 
 impl Display for TimeParserError {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        write!(f, "Tried to parse invalid time string")
+        write!(f, "Tried to parse invalid time string")?;
+        if !self.tried.is_empty() {
+            write!(f, " (tried {})", self.tried.join(", "))?;
+        }
+        Ok(())
     }
 }
 
@@ -32,3 +50,325 @@ impl Display for TimeOverflow {
         write!(f, "Time could not be added due to an overflow")
     }
 }
+
+/// Returned when a counter would end before it starts: by
+/// `Counter::try_down`/`Counter::try_up`, and by
+/// `Counter::set_start`/`Counter::set_end` while counting down.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct InvalidWindow {
+    start: String,
+    end: String,
+}
+
+/// The name `Counter::try_down`/`Counter::try_up` used to return
+#[deprecated(note = "use InvalidWindow")]
+pub type InvalidCounter = InvalidWindow;
+
+impl InvalidWindow {
+    pub(crate) fn new(start: String, end: String) -> Self {
+        InvalidWindow { start, end }
+    }
+
+    /// The rejected `start`, rendered with `Display`
+    pub fn start(&self) -> &str {
+        &self.start
+    }
+
+    /// The rejected `end`, rendered with `Display`
+    pub fn end(&self) -> &str {
+        &self.end
+    }
+}
+
+impl std::error::Error for InvalidWindow {}
+
+impl Display for InvalidWindow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "Counter ends ({}) before it starts ({})",
+            self.end, self.start
+        )
+    }
+}
+
+/// Returned by `Counter::try_move_start_strict`/`Counter::try_move_end_strict`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum WindowViolation {
+    /// The move would have put `start` after `end`.
+    Crossed,
+    /// The moved endpoint does not fit into the time backend.
+    Overflow,
+}
+
+impl std::error::Error for WindowViolation {}
+
+impl Display for WindowViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            WindowViolation::Crossed => {
+                write!(f, "Move would make the counter end before it starts")
+            }
+            WindowViolation::Overflow => write!(f, "Time could not be added due to an overflow"),
+        }
+    }
+}
+
+/// Returned by `CounterSet::try_move_all_ends`, naming the counter that
+/// could not be moved.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct InvalidMove {
+    name: String,
+    violation: WindowViolation,
+}
+
+impl InvalidMove {
+    pub(crate) fn new(name: String, violation: WindowViolation) -> Self {
+        InvalidMove { name, violation }
+    }
+
+    /// The name of the counter in the set
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Why that counter could not be moved
+    pub fn violation(&self) -> WindowViolation {
+        self.violation
+    }
+}
+
+impl std::error::Error for InvalidMove {}
+
+impl Display for InvalidMove {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}: {}", self.name, self.violation)
+    }
+}
+
+/// Returned by `CounterBuilder::build` for combinations that do not make a counter.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BuildError {
+    /// None of `start`, `end` and `lasting` were given.
+    MissingEndpoints,
+    /// A `Down` counter was given neither an `end` nor a duration.
+    MissingEnd,
+    /// Both an `end` and a duration were given.
+    ConflictingEnd,
+    /// The `end` would be before `start`.
+    EndBeforeStart,
+    /// `start` plus the duration does not fit into the time backend.
+    Overflow,
+}
+
+impl std::error::Error for BuildError {}
+
+impl Display for BuildError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            BuildError::MissingEndpoints => write!(f, "Counter has neither start nor end"),
+            BuildError::MissingEnd => write!(f, "Counting down requires an end"),
+            BuildError::ConflictingEnd => write!(f, "Counter was given both an end and a duration"),
+            BuildError::EndBeforeStart => write!(f, "Counter ends before it starts"),
+            BuildError::Overflow => write!(f, "Time could not be added due to an overflow"),
+        }
+    }
+}
+
+/// Returned by the `Counter::*_hms` constructors.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum InvalidHms {
+    /// A component was negative, or minutes/seconds were not below 60,
+    /// or hours were not below 24 alongside days.
+    OutOfRange,
+    /// The total does not fit into the time backend.
+    Overflow,
+}
+
+impl std::error::Error for InvalidHms {}
+
+impl Display for InvalidHms {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            InvalidHms::OutOfRange => write!(f, "Time component is out of range"),
+            InvalidHms::Overflow => write!(f, "Time could not be added due to an overflow"),
+        }
+    }
+}
+
+/// Describes where and why a duration string could not be parsed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DurationParserError {
+    position: usize,
+    reason: &'static str,
+}
+
+impl DurationParserError {
+    pub(crate) fn new(position: usize, reason: &'static str) -> Self {
+        DurationParserError { position, reason }
+    }
+
+    /// Byte offset into the input at which parsing failed
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn reason(&self) -> &'static str {
+        self.reason
+    }
+}
+
+impl std::error::Error for DurationParserError {}
+
+impl Display for DurationParserError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "Invalid duration at position {}: {}",
+            self.position, self.reason
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct InvalidFormat;
+
+impl std::error::Error for InvalidFormat {}
+
+impl Display for InvalidFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "Format string contains an invalid specifier")
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct InvalidSnapshot;
+
+impl std::error::Error for InvalidSnapshot {}
+
+impl Display for InvalidSnapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "String is not a valid counter snapshot")
+    }
+}
+
+/// A timer spec that `Counter::parse_spec` could not read, with the part of
+/// the input that failed.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct InvalidSpec {
+    token: String,
+    reason: &'static str,
+}
+
+impl InvalidSpec {
+    #[cfg_attr(not(feature = "types"), allow(dead_code))]
+    pub(crate) fn new(token: &str, reason: &'static str) -> Self {
+        InvalidSpec {
+            token: token.to_string(),
+            reason,
+        }
+    }
+
+    /// The part of the spec that could not be read, empty if it ended early
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    pub fn reason(&self) -> &'static str {
+        self.reason
+    }
+}
+
+impl std::error::Error for InvalidSpec {}
+
+impl Display for InvalidSpec {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.token.is_empty() {
+            true => write!(f, "Invalid timer spec: {}", self.reason),
+            false => write!(
+                f,
+                "Invalid timer spec at \"{}\": {}",
+                self.token, self.reason
+            ),
+        }
+    }
+}
+
+/// Returned by `TrackedCounter::undo`/`redo` when there is nothing to revert
+/// or reapply.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct EmptyHistory;
+
+impl std::error::Error for EmptyHistory {}
+
+impl Display for EmptyHistory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "No adjustments to undo or redo")
+    }
+}
+
+/// Returned by `RepeatingCounter::new` for periods shorter than one second.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct InvalidPeriod;
+
+impl std::error::Error for InvalidPeriod {}
+
+impl Display for InvalidPeriod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "Period must be at least one second")
+    }
+}
+
+/// Returned by `Sequence::phase` for phases shorter than zero seconds.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct InvalidPhase;
+
+impl std::error::Error for InvalidPhase {}
+
+impl Display for InvalidPhase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "Phase must not be shorter than zero seconds")
+    }
+}
+
+/// Returned by `Cooldown::trigger` while no charge is ready.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct NotReady;
+
+impl std::error::Error for NotReady {}
+
+impl Display for NotReady {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "Cooldown is not ready yet")
+    }
+}
+
+/// Why `CounterSet::load_dir` skipped a file
+#[derive(Debug)]
+pub enum PersistError {
+    /// The file could not be read
+    Io(io::Error),
+    /// The file name is not one `CounterSet::save_dir` would write
+    InvalidName,
+    /// The file does not hold a valid counter
+    Invalid(io::Error),
+}
+
+impl std::error::Error for PersistError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PersistError::Io(error) | PersistError::Invalid(error) => Some(error),
+            PersistError::InvalidName => None,
+        }
+    }
+}
+
+impl Display for PersistError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            PersistError::Io(error) => write!(f, "Could not read counter file: {}", error),
+            PersistError::InvalidName => write!(f, "File name is not a valid counter name"),
+            PersistError::Invalid(error) => write!(f, "Invalid counter file: {}", error),
+        }
+    }
+}
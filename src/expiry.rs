@@ -0,0 +1,149 @@
+//! An async stream of the counters in a `CounterSet` reaching their `end`,
+//! behind the `tokio` feature.
+use crate::as_nanos;
+use crate::set::{CounterSet, SortKey};
+use crate::times::{Time, TimeUnits};
+use futures_util::stream::{self, Stream};
+use std::hash::{Hash, Hasher};
+use std::ops::Sub;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{watch, RwLock};
+
+/// A counter of a `CounterSet` reaching its `end`, see
+/// `CounterSet::expiry_stream`
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct ExpiryEvent<T> {
+    /// The name of the counter in the set
+    pub name: String,
+    /// The `end` the counter reached
+    pub end: T,
+}
+
+/// Tells the `expiry_stream`s of a set that it changed. Always compares
+/// equal, and a clone starts out without streams, since it is a separate set.
+pub(crate) struct Changes {
+    sender: watch::Sender<()>,
+}
+
+impl Changes {
+    pub(crate) fn notify(&self) {
+        self.sender.send_replace(());
+    }
+}
+
+impl Default for Changes {
+    fn default() -> Self {
+        Changes {
+            sender: watch::channel(()).0,
+        }
+    }
+}
+
+impl Clone for Changes {
+    fn clone(&self) -> Self {
+        Changes::default()
+    }
+}
+
+impl PartialEq for Changes {
+    fn eq(&self, _: &Changes) -> bool {
+        true
+    }
+}
+
+impl Eq for Changes {}
+
+impl Hash for Changes {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+impl<T, D> CounterSet<T>
+where
+    T: Copy + Ord + Send + Sync + Time<Duration = D> + Sub<T, Output = D> + 'static,
+    D: TimeUnits + Ord,
+{
+    /// Yields an `ExpiryEvent` each time a counter in `set` finishes, sleeping
+    /// until the soonest running counter's `end` in between. Counters that
+    /// are added, removed or adjusted while the stream waits are taken into
+    /// account right away, since every change through `&mut CounterSet` wakes
+    /// the stream to recompute. Counters that are already finished when the
+    /// stream is created do not fire, and a counter fires again once moved
+    /// to a new `end` that it reaches. Counters reaching their `end` at the
+    /// same time fire in the order of `SortKey::Remaining`.
+    ///
+    /// This takes the set behind a `tokio::sync::RwLock`, which the stream
+    /// only holds while it looks for the next event, never while it sleeps.
+    pub fn expiry_stream(set: Arc<RwLock<Self>>) -> impl Stream<Item = ExpiryEvent<T>> {
+        stream::unfold(None, move |state| {
+            let set = Arc::clone(&set);
+            async move {
+                let (mut changes, mut fired) = match state {
+                    Some(state) => state,
+                    None => {
+                        let set = set.read().await;
+                        let now = T::now();
+                        let fired = set.finished_ends(now);
+                        (set.changes.sender.subscribe(), fired)
+                    }
+                };
+                loop {
+                    let wait = {
+                        let set = set.read().await;
+                        let now = T::now();
+                        let finished = set.finished_ends(now);
+                        fired.retain(|fired| finished.contains(fired));
+                        let due = set.iter_sorted(SortKey::Remaining).find(|(name, counter)| {
+                            counter.completion_time().is_some()
+                                && counter.is_finished_at(now)
+                                && !fired.contains(&(name.to_string(), counter.end))
+                        });
+                        if let Some((name, counter)) = due {
+                            let event = ExpiryEvent {
+                                name: name.to_string(),
+                                end: counter.end,
+                            };
+                            fired.push((event.name.clone(), event.end));
+                            return Some((event, Some((changes, fired))));
+                        }
+                        set.iter()
+                            .filter(|(_, counter)| {
+                                counter.paused_at.is_none()
+                                    && counter.completion_time().is_some()
+                                    && !counter.is_finished_at(now)
+                            })
+                            .map(|(_, counter)| counter.remaining_at(now))
+                            .min()
+                            .map(|remaining| to_std(&remaining))
+                    };
+                    let sleep = async {
+                        match wait {
+                            Some(wait) => tokio::time::sleep(wait).await,
+                            None => std::future::pending().await,
+                        }
+                    };
+                    tokio::select! {
+                        _ = sleep => {}
+                        _ = changes.changed() => {}
+                    }
+                }
+            }
+        })
+    }
+
+    /// The names and `end`s of the counters finished at `now`
+    fn finished_ends(&self, now: T) -> Vec<(String, T)> {
+        self.iter()
+            .filter(|(_, counter)| {
+                counter.completion_time().is_some() && counter.is_finished_at(now)
+            })
+            .map(|(name, counter)| (name.to_string(), counter.end))
+            .collect()
+    }
+}
+
+/// `duration` as a `std::time::Duration`, or zero if it is negative
+fn to_std<D: TimeUnits>(duration: &D) -> Duration {
+    let nanos = as_nanos(duration).max(0);
+    Duration::from_nanos(u64::try_from(nanos).unwrap_or(u64::MAX))
+}
@@ -1,19 +1,116 @@
+use std::cmp::Ordering;
 use std::fmt::{self, Display, Formatter};
 use std::fs::{self, read_to_string};
+use std::hash::{Hash, Hasher};
 use std::io;
-use std::ops::Sub;
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
 use std::path::Path;
 use std::str::FromStr;
 
+/// Emits a `tracing::debug!` event if the `tracing` feature is enabled,
+/// and expands to nothing otherwise.
+macro_rules! debug_event {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($arg)*);
+    };
+}
+
+/// Implements `PartialEq`/`PartialOrd` between `Counter<$time>` and
+/// `$duration`, comparing the remaining (`Down`) or elapsed (`Up`) time.
+/// Generic parameters go in leading brackets, e.g. `[const TPS: u32]`.
+macro_rules! impl_duration_cmp {
+    ([$($generics:tt)*] $time:ty => $duration:ty $(where $($bounds:tt)*)?) => {
+        /// Compares the remaining (`Down`) or elapsed (`Up`) time against a duration,
+        /// reading the clock once. Since the result changes as time passes, this is
+        /// not a consistent order across calls.
+        impl<$($generics)*> PartialEq<$duration> for $crate::Counter<$time>
+        $(where $($bounds)*)?
+        {
+            fn eq(&self, other: &$duration) -> bool {
+                self.duration() == *other
+            }
+        }
+
+        /// Compares the remaining (`Down`) or elapsed (`Up`) time against a duration,
+        /// reading the clock once. Since the result changes as time passes, this is
+        /// not a consistent order across calls.
+        impl<$($generics)*> PartialOrd<$duration> for $crate::Counter<$time>
+        $(where $($bounds)*)?
+        {
+            fn partial_cmp(&self, other: &$duration) -> Option<::core::cmp::Ordering> {
+                self.duration().partial_cmp(other)
+            }
+        }
+    };
+    ($time:ty => $duration:ty) => {
+        impl_duration_cmp!([] $time => $duration);
+    };
+}
+
 #[cfg(feature = "types")]
 pub mod types;
 
+#[cfg(feature = "serde")]
+mod serialize;
+
 #[cfg(feature = "chrono")]
 pub mod chrono;
 
+#[cfg(feature = "natural")]
+mod natural;
+
+#[cfg(feature = "types")]
+pub mod business;
+
+#[cfg(feature = "types")]
+pub mod recurring;
+
+pub mod ticks;
+
+pub mod unix;
+
+pub mod clock;
+use crate::clock::Clock;
+
+mod builder;
+pub use crate::builder::CounterBuilder;
+
+mod reading;
+pub use crate::reading::{CounterReading, FrozenCounter};
+
+mod tracked;
+pub use crate::tracked::{Adjustment, AdjustmentKind, TrackedCounter};
+
+mod scaled;
+pub use crate::scaled::ScaledCounter;
+
+mod repeating;
+pub use crate::repeating::RepeatingCounter;
+
+mod sequence;
+pub use crate::sequence::Sequence;
+
+#[cfg(feature = "tokio")]
+mod expiry;
+mod set;
+#[cfg(feature = "tokio")]
+pub use crate::expiry::ExpiryEvent;
+pub use crate::set::{CounterSet, LoadErrors, Metadata, SetEntries, SetIntoIter, SetIter, SortKey};
+
+mod cooldown;
+pub use crate::cooldown::Cooldown;
+
+mod relative;
+pub use crate::relative::RelativeCounter;
+
 mod times;
 pub use crate::times::*;
 
+mod parse;
+use crate::parse::parse_clock;
+use crate::reading::write_hms;
+
 mod errors;
 pub use crate::errors::*;
 
@@ -21,10 +118,20 @@ pub use crate::errors::*;
 /// to either show the time passed since `start`, or until `end`,
 /// formatted as `HH(+):MM:SS`.  
 /// The timer will not go down past 00:00:00.
+///
+/// `CounterBuilder` is the safest way to create a counter. `down` and `up`
+/// fill in missing endpoints with `T::now()` and accept an `end` before
+/// `start`, while the builder rejects a countdown without an `end` and an
+/// inverted window.
+///
+/// Only `start`, `end` and `direction` are public fields. The rest of the
+/// state, like the pause, laps and reset targets, is changed through methods
+/// such as `pause` and `lap`, and read through getters such as `paused_at`
+/// and `laps`.
 /// # Examples
 /// Basic functionality is very simple:
 /// ```rust
-/// # use countrs::{Counter, Time, TimeUnits};
+/// # use countrs::{Adjust, Counter, Time, TimeUnits};
 /// # use countrs::types::{Duration, TimeStamp};
 /// let now = TimeStamp::now();
 /// let mut counter = Counter::down(
@@ -34,6 +141,8 @@ pub use crate::errors::*;
 ///
 /// // A small amount of time will have passed since `now` was assigned
 /// assert_eq!(counter.to_string(), "00:09:59");
+/// counter.move_end_by(Adjust::Minutes(5)).unwrap();
+/// assert_eq!(counter.to_string(), "00:14:59");
 /// counter.flip();
 /// // It now counts up from `start`
 /// assert_eq!(counter.to_string(), "00:10:00")
@@ -42,95 +151,550 @@ pub use crate::errors::*;
 /// ```rust
 /// # use countrs::{Counter, Time, TimeUnits};
 /// # use countrs::types::{Duration, TimeStamp};
-/// let mut counter = Counter::up(Some(TimeStamp::now()), None);
+/// let mut counter = Counter::<TimeStamp>::up_from_now();
 /// counter.try_move_start(-30).unwrap();
 ///
 /// assert_eq!(counter.to_string(), "00:00:30")
 /// ```
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+/// Partial seconds are dropped by default, so a fresh countdown shows one
+/// second less than its length. `Rounding::Ceil` counts them instead:
+/// ```rust
+/// # use countrs::{Counter, Rounding};
+/// # use countrs::types::TimeStamp;
+/// let mut counter = Counter::<TimeStamp>::down_for_hms(0, 10, 0).unwrap();
+/// assert_eq!(counter.to_string(), "00:09:59");
+///
+/// counter.set_rounding(Rounding::Ceil);
+/// assert_eq!(counter.to_string(), "00:10:00")
+/// ```
+///
+/// Counters are `Clone` but not `Copy`, even for `Copy` backends: `laps`
+/// holds any number of recorded moments in a `Vec`, and a `Copy` type cannot
+/// own heap memory. Cloning a counter without laps does not allocate.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
 pub struct Counter<T> {
     pub start: T,
     pub end: T,
     pub direction: Direction,
+    /// The moment `pause` was called, while the counter is paused
+    pub(crate) paused_at: Option<T>,
+    /// The `start` restored by `reset`
+    pub(crate) initial_start: T,
+    /// The `end` restored by `reset`
+    pub(crate) initial_end: T,
+    /// The `direction` restored by `reset`
+    pub(crate) initial_direction: Direction,
+    /// The moments recorded by `lap`, in order
+    pub(crate) laps: Vec<T>,
+    /// How often `snooze` was called since creation or `reset`
+    pub(crate) snoozes: u32,
+    /// Whether counting up stops at `end`, see `clamp_to_end`
+    pub(crate) stop_at_end: bool,
+    /// Whether counting down turns into counting up at `end`, see `set_auto_flip`
+    pub(crate) auto_flip: bool,
+    /// How partial seconds are shown, see `set_rounding`
+    pub(crate) rounding: Rounding,
+    /// When the counter was created and last adjusted, if known
+    audit: Audit<T>,
+}
+
+/// When a counter was created and last adjusted, see `Counter::created_at`.
+/// Always compares equal, so that counters over the same window are equal
+/// however and whenever they were made.
+#[derive(Clone)]
+struct Audit<T> {
+    created_at: Option<T>,
+    modified_at: Option<T>,
+}
+
+impl<T> Audit<T> {
+    fn unknown() -> Audit<T> {
+        Audit {
+            created_at: None,
+            modified_at: None,
+        }
+    }
+}
+
+impl<T> PartialEq for Audit<T> {
+    fn eq(&self, _: &Audit<T>) -> bool {
+        true
+    }
+}
+
+impl<T> Eq for Audit<T> {}
+
+impl<T> PartialOrd for Audit<T> {
+    fn partial_cmp(&self, other: &Audit<T>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Audit<T> {
+    fn cmp(&self, _: &Audit<T>) -> Ordering {
+        Ordering::Equal
+    }
+}
+
+impl<T> Hash for Audit<T> {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
 }
 
 /// Specifies whether to count `Up` from a starting time,
 /// or `Down` from a target end time.
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
     Up,
     Down,
 }
 
+/// Where a counter is in its window, see `Counter::state`.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum CounterState {
+    /// `start` is still in the future.
+    Pending,
+    /// Past `start`, but not finished.
+    Running,
+    /// The clock has reached `end`. Open-ended `Up` counters never get here.
+    Finished,
+}
+
+impl<T> Counter<T> {
+    /// When the counter is due, i.e. `end`, e.g. for showing next to its value.
+    pub fn deadline(&self) -> &T {
+        &self.end
+    }
+
+    /// Converts `start` and `end` to another time type, keeping the direction.
+    /// `f` should represent the same instants, since the clock is not consulted.
+    pub fn map_times<U, F: Fn(T) -> U>(self, f: F) -> Counter<U> {
+        Counter {
+            start: f(self.start),
+            end: f(self.end),
+            direction: self.direction,
+            paused_at: self.paused_at.map(&f),
+            initial_start: f(self.initial_start),
+            initial_end: f(self.initial_end),
+            initial_direction: self.initial_direction,
+            laps: self.laps.into_iter().map(&f).collect(),
+            snoozes: self.snoozes,
+            stop_at_end: self.stop_at_end,
+            auto_flip: self.auto_flip,
+            rounding: self.rounding,
+            audit: Audit {
+                created_at: self.audit.created_at.map(&f),
+                modified_at: self.audit.modified_at.map(&f),
+            },
+        }
+    }
+}
+
 impl<T, D> Counter<T>
 where
-    T: Copy + Default + Display + Time<Duration = D> + FromStr + Sub<T, Output = D>,
+    T: Copy + Time<Duration = D> + Sub<T, Output = D>,
     D: TimeUnits,
 {
-    /// If given `None`, the default value for `T` will be assigned.
+    /// A missing `start` or `end` is set to the current time.
+    /// No validation is done, see `try_down` or `CounterBuilder` for that.
     pub fn down(start: Option<T>, end: Option<T>) -> Counter<T> {
-        Counter {
-            start: start.unwrap_or_default(),
-            end: end.unwrap_or_default(),
-            direction: Direction::Down,
+        match (start, end) {
+            (Some(start), Some(end)) => Self::new(start, end, Direction::Down),
+            (start, end) => {
+                // Read once, so that `down(None, None)` is empty
+                let now = T::now();
+                Self::new(start.unwrap_or(now), end.unwrap_or(now), Direction::Down).stamped(now)
+            }
         }
     }
 
-    /// If given `None`, the default value for `T` will be assigned.
+    /// A missing `start` is set to the current time. A missing `end` is set
+    /// to `start`, leaving the counter open-ended, and showing "00:00:00"
+    /// when flipped.
+    /// No validation is done, see `try_up` or `CounterBuilder` for that.
     pub fn up(start: Option<T>, end: Option<T>) -> Counter<T> {
+        match start {
+            Some(start) => Self::new(start, end.unwrap_or(start), Direction::Up),
+            None => {
+                let now = T::now();
+                Self::new(now, end.unwrap_or(now), Direction::Up).stamped(now)
+            }
+        }
+    }
+
+    /// Never reads the clock, so `created_at` and `modified_at` are unknown.
+    pub(crate) fn new(start: T, end: T, direction: Direction) -> Counter<T> {
         Counter {
-            start: start.unwrap_or_default(),
-            end: end.unwrap_or_default(),
-            direction: Direction::Up,
+            start,
+            end,
+            direction,
+            paused_at: None,
+            initial_start: start,
+            initial_end: end,
+            initial_direction: direction,
+            laps: Vec::new(),
+            snoozes: 0,
+            stop_at_end: false,
+            auto_flip: false,
+            rounding: Rounding::Floor,
+            audit: Audit::unknown(),
         }
     }
 
-    /// Calls `to_string` on `start`, `end`, and `direction`, and `std::fs::write`s each
-    /// to one line in a file, in that order.
-    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
-        fs::write(
-            path,
-            format!("{}\n{}\n{}", self.start, self.end, self.direction),
-        )?;
+    /// Records `now` as the moment the counter was created, for constructors
+    /// that read the clock anyway.
+    fn stamped(mut self, now: T) -> Counter<T> {
+        self.audit = Audit {
+            created_at: Some(now),
+            modified_at: Some(now),
+        };
+        self
+    }
+
+    /// Like `down`, but rejects an `end` before `start`, which would
+    /// otherwise always show "00:00:00".
+    /// ```rust
+    /// # use countrs::{Counter, Time};
+    /// # use countrs::types::TimeStamp;
+    /// let start = TimeStamp::now();
+    /// assert!(Counter::try_down(start, start + 600).is_ok());
+    ///
+    /// let error = Counter::try_down(start + 600, start).unwrap_err();
+    /// assert_eq!(error.start(), (start + 600).to_string());
+    /// ```
+    pub fn try_down(start: T, end: T) -> Result<Counter<T>, InvalidWindow>
+    where
+        T: Display,
+        D: PartialOrd,
+    {
+        Self::validate(start, end)?;
+        Ok(Counter::new(start, end, Direction::Down))
+    }
+
+    /// Like `up`, but rejects an `end` before `start`.
+    pub fn try_up(start: T, end: T) -> Result<Counter<T>, InvalidWindow>
+    where
+        T: Display,
+        D: PartialOrd,
+    {
+        Self::validate(start, end)?;
+        Ok(Counter::new(start, end, Direction::Up))
+    }
+
+    fn validate(start: T, end: T) -> Result<(), InvalidWindow>
+    where
+        T: Display,
+        D: PartialOrd,
+    {
+        match end - start < D::seconds(0) {
+            true => Err(InvalidWindow::new(start.to_string(), end.to_string())),
+            false => Ok(()),
+        }
+    }
+
+    /// An empty countdown with `start` and `end` both at now, which always
+    /// shows "00:00:00" unless flipped. Useful as a placeholder before the
+    /// real counter is known.
+    pub fn zero() -> Counter<T> {
+        let now = T::now();
+        Counter::new(now, now, Direction::Down).stamped(now)
+    }
+
+    /// `true` if `start` and `end` are the same instant.
+    pub fn is_zero_length(&self) -> bool
+    where
+        T: PartialEq,
+    {
+        self.start == self.end
+    }
+
+    /// Counts up from now, with `end` also set to now.
+    pub fn up_from_now() -> Counter<T> {
+        let now = T::now();
+        Counter::new(now, now, Direction::Up).stamped(now)
+    }
+
+    /// Counts down from now to `end`. An `end` in the past gives a counter
+    /// that is already finished.
+    pub fn until(end: T) -> Counter<T> {
+        let now = T::now();
+        Counter::new(now, end, Direction::Down).stamped(now)
+    }
+
+    /// Counts down for `duration`, starting now.
+    /// ```rust
+    /// # use countrs::{Counter, TimeUnits};
+    /// # use countrs::types::{Duration, TimeStamp};
+    /// let counter = Counter::<TimeStamp>::down_for(Duration::seconds(1500)).unwrap();
+    /// // A small amount of time will have passed since the counter was created
+    /// assert_eq!(counter.to_string(), "00:24:59");
+    /// ```
+    pub fn down_for(duration: impl Into<D>) -> Result<Counter<T>, TimeOverflow> {
+        let start = T::now();
+        let end = start.add_seconds(duration.into())?;
+        Ok(Counter::new(start, end, Direction::Down).stamped(start))
+    }
+
+    /// Starts a countdown from now, showing the given `"HH(+):MM:SS"`.
+    /// ```rust
+    /// # use countrs::Counter;
+    /// # use countrs::types::TimeStamp;
+    /// let egg_timer = Counter::<TimeStamp>::down_showing("00:04:30").unwrap();
+    /// assert_eq!(egg_timer.to_string(), "00:04:29");
+    ///
+    /// assert!(Counter::<TimeStamp>::down_showing("00:60:00").is_err());
+    /// ```
+    pub fn down_showing(hms: &str) -> Result<Counter<T>, DurationParserError> {
+        let now = T::now();
+        let end = now
+            .add_seconds(D::from_hms_str(hms)?)
+            .map_err(|_| DurationParserError::new(0, "duration is too large"))?;
+        Ok(Counter::new(now, end, Direction::Down).stamped(now))
+    }
+
+    /// Builds a counter anchored at now that shows the given `"HH:MM:SS"`,
+    /// or `"MM:SS"`. Hours may have any number of digits, and leading zeros
+    /// may be left out, so typed input like `"1:5:00"` is accepted.
+    /// ```rust
+    /// # use countrs::{Counter, Direction};
+    /// # use countrs::types::TimeStamp;
+    /// let counter = Counter::<TimeStamp>::from_hms("1:30:00", Direction::Up).unwrap();
+    /// assert_eq!(counter.to_string(), "01:30:00");
+    ///
+    /// let error = Counter::<TimeStamp>::from_hms("1:2:x", Direction::Down).unwrap_err();
+    /// assert_eq!(error.position(), 4);
+    /// ```
+    pub fn from_hms(hms: &str, direction: Direction) -> Result<Counter<T>, DurationParserError> {
+        let seconds = parse_clock(hms)?;
+        let too_large = |_| DurationParserError::new(0, "duration is too large");
+        let now = T::now();
+        let counter = match direction {
+            Direction::Down => {
+                let end = now.add_seconds(D::try_seconds(seconds).map_err(too_large)?);
+                Counter::new(now, end.map_err(too_large)?, direction)
+            }
+            Direction::Up => {
+                let start = now.add_seconds(D::try_seconds(-seconds).map_err(too_large)?);
+                Counter::new(start.map_err(too_large)?, now, direction)
+            }
+        };
+        Ok(counter.stamped(now))
+    }
+
+    /// Starts a countdown from now that runs for the given time.
+    /// Minutes and seconds must be below 60.
+    pub fn down_for_hms(hours: i64, minutes: i64, seconds: i64) -> Result<Counter<T>, InvalidHms> {
+        Self::down_for_dhms(0, hours, minutes, seconds)
+    }
+
+    /// Like `down_for_hms`, with hours also required to be below 24.
+    pub fn down_for_dhms(
+        days: i64,
+        hours: i64,
+        minutes: i64,
+        seconds: i64,
+    ) -> Result<Counter<T>, InvalidHms> {
+        let duration = D::try_seconds(dhms_seconds(days, hours, minutes, seconds)?)
+            .map_err(|_| InvalidHms::Overflow)?;
+        let now = T::now();
+        let end = now
+            .add_seconds(duration)
+            .map_err(|_| InvalidHms::Overflow)?;
+        Ok(Counter::new(now, end, Direction::Down).stamped(now))
+    }
+
+    /// Starts counting up from the given time ago.
+    /// Minutes and seconds must be below 60.
+    pub fn up_since_hms(hours: i64, minutes: i64, seconds: i64) -> Result<Counter<T>, InvalidHms> {
+        let duration = D::try_seconds(-dhms_seconds(0, hours, minutes, seconds)?)
+            .map_err(|_| InvalidHms::Overflow)?;
+        let now = T::now();
+        let start = now
+            .add_seconds(duration)
+            .map_err(|_| InvalidHms::Overflow)?;
+        Ok(Counter::up(Some(start), None).stamped(now))
+    }
+
+    /// Restores `start`, `end` and `direction` to the values the counter was
+    /// created with, leaves any pause, and clears the laps and snoozes.
+    pub fn reset(&mut self) {
+        self.start = self.initial_start;
+        self.end = self.initial_end;
+        self.direction = self.initial_direction;
+        self.paused_at = None;
+        self.laps.clear();
+        self.snoozes = 0;
+        self.touch();
+        debug_event!(
+            length = (self.end - self.start).num_seconds(),
+            "reset counter"
+        );
+    }
+
+    /// The `start` restored by `reset`
+    pub fn initial_start(&self) -> T {
+        self.initial_start
+    }
+
+    /// The `end` restored by `reset`
+    pub fn initial_end(&self) -> T {
+        self.initial_end
+    }
+
+    /// The `direction` restored by `reset`
+    pub fn initial_direction(&self) -> Direction {
+        self.initial_direction
+    }
+
+    /// Moves the whole window so it starts now, keeping its length, leaves
+    /// any pause, and clears the laps. Nothing changes if the new `end` would
+    /// overflow.
+    /// ```rust
+    /// # use countrs::{Counter, Time};
+    /// # use countrs::types::TimeStamp;
+    /// let now = TimeStamp::now();
+    /// let mut break_timer = Counter::down(Some(now - 300), Some(now + 300));
+    /// break_timer.restart().unwrap();
+    /// // A small amount of time will have passed since `restart`
+    /// assert_eq!(break_timer.to_string(), "00:09:59");
+    /// ```
+    pub fn restart(&mut self) -> Result<(), TimeOverflow> {
+        let now = T::now();
+        let end = now.add_seconds(self.end - self.start)?;
+        self.start = now;
+        self.end = end;
+        self.paused_at = None;
+        self.laps.clear();
+        self.touch();
+        debug_event!(
+            length = (self.end - self.start).num_seconds(),
+            "restarted counter"
+        );
         Ok(())
     }
 
-    /// Tries converting the first three lines of a file (read by `std::fs::read_to_string`)
-    /// into a `Counter` by attempting to parse them into `start`, `end`, and `direction`
-    /// respectively, calling `from_str`.
-    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Counter<T>> {
-        let lines = read_to_string(path)?;
-        let mut lines = lines.split('\n');
-        if let (Some(s), Some(e), Some(d)) = (lines.next(), lines.next(), lines.next()) {
-            let start = T::from_str(s).map_err(|_| {
-                io::Error::new(
-                    io::ErrorKind::InvalidData,
-                    "File does not contain valid start data",
-                )
-            })?;
-            let end = T::from_str(e).map_err(|_| {
-                io::Error::new(
-                    io::ErrorKind::InvalidData,
-                    "File does not contain valid end data",
-                )
-            })?;
-            let Ok(direction) = d.parse() else {
-                return Err(io::Error::new(
-                    io::ErrorKind::InvalidData,
-                    "File doesn ot contain complete direction data",
-                ))
-            };
+    /// Pushes `end` back by `duration`, like an alarm's snooze button. Once the
+    /// counter `is_finished`, the new `end` is `duration` from now, since the
+    /// old one may be long past. Before that, `end` is extended instead, so
+    /// snoozing early never shortens the counter.
+    /// ```rust
+    /// # use countrs::{Counter, Time, TimeUnits};
+    /// # use countrs::types::TimeStamp;
+    /// let now = TimeStamp::now();
+    /// let mut alarm = Counter::down(Some(now - 3600), Some(now - 600));
+    /// alarm.snooze(300).unwrap();
+    /// assert_eq!(alarm.to_string(), "00:04:59");
+    /// assert_eq!(alarm.snooze_count(), 1);
+    /// ```
+    pub fn snooze(&mut self, duration: impl Into<D>) -> Result<(), TimeOverflow>
+    where
+        D: PartialOrd,
+    {
+        let from = match self.is_finished() {
+            true => self.now(),
+            false => self.end,
+        };
+        self.end = from.add_seconds(duration.into())?;
+        self.snoozes = self.snoozes.saturating_add(1);
+        self.touch();
+        debug_event!(snoozes = self.snoozes, "snoozed counter");
+        Ok(())
+    }
+
+    pub fn snooze_count(&self) -> u32 {
+        self.snoozes
+    }
+
+    /// When the counter was created, if it was made by a constructor that
+    /// reads the clock, like `down_for`, `down` with an endpoint missing, or
+    /// `CounterBuilder::build` without a `start`.
+    /// Counters built from given endpoints, and those loaded from a file that
+    /// did not record it, return `None`.
+    pub fn created_at(&self) -> Option<T> {
+        self.audit.created_at
+    }
+
+    /// When `start`, `end`, `direction` or a setting was last changed through
+    /// the counter's methods, including `resume`, which moves the window by the
+    /// time spent paused. Pausing and laps do not count.
+    /// `None` where `created_at` is, since those counters don't track
+    /// changes, and adjusting them never reads the clock.
+    pub fn modified_at(&self) -> Option<T> {
+        self.audit.modified_at
+    }
 
-            return Ok(Counter {
-                start,
-                end,
-                direction,
-            });
+    fn touch(&mut self) {
+        if self.audit.modified_at.is_some() {
+            self.audit.modified_at = Some(T::now());
         }
-        Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            "File does not contain valid counter data",
-        ))
+    }
+
+    /// Splits the window at now into what has passed, counting up from
+    /// `start`, and what is left, counting down to `end`. The split point
+    /// stays within the window, so the halves' `total`s always add up to the
+    /// original one, and a finished counter leaves an empty remaining half.
+    pub fn split(&self) -> (Counter<T>, Counter<T>)
+    where
+        D: PartialOrd,
+    {
+        let zero = D::seconds(0);
+        let now = self.now();
+        let at = if self.until_end_at(now) < zero {
+            self.end
+        } else if now - self.start < zero {
+            self.start
+        } else {
+            now
+        };
+        (
+            Counter::new(self.start, at, Direction::Up),
+            Counter::new(at, self.end, Direction::Down),
+        )
+    }
+
+    /// Records a lap for a counter counting up, returning the time since the
+    /// previous lap, or since `start` for the first one. While paused, the
+    /// lap is taken at the moment of pausing.
+    pub fn lap(&mut self) -> Result<D, InvalidDirection> {
+        if self.direction == Direction::Down {
+            return Err(InvalidDirection);
+        }
+        let now = self.now();
+        let previous = self.laps.last().copied().unwrap_or(self.start);
+        self.laps.push(now);
+        Ok(now - previous)
+    }
+
+    /// The moments recorded by `lap`, in order
+    pub fn laps(&self) -> &[T] {
+        &self.laps
+    }
+
+    /// The time between consecutive laps, starting from `start`.
+    pub fn lap_durations(&self) -> Vec<D> {
+        let previous = std::iter::once(self.start).chain(self.laps.iter().copied());
+        self.laps
+            .iter()
+            .zip(previous)
+            .map(|(lap, previous)| *lap - previous)
+            .collect()
+    }
+
+    /// Exchanges `start` and `end`, e.g. to fix a file written the wrong way round.
+    ///
+    /// On a window symmetric around now, `flip` alone keeps the displayed value,
+    /// as the time until `end` equals the time since `start`. Following `swap`
+    /// with `flip` instead negates the reading, so a running countdown shows
+    /// "00:00:00" while a finished one shows how far past its `end` it is.
+    pub fn swap(&mut self) {
+        std::mem::swap(&mut self.start, &mut self.end);
+        self.touch();
+    }
+
+    /// Like `swap`, by value.
+    pub fn swapped(mut self) -> Counter<T> {
+        self.swap();
+        self
     }
 
     /// Changes the direction of the Counter between Up/Down.
@@ -139,28 +703,421 @@ where
             Direction::Up => Direction::Down,
             Direction::Down => Direction::Up,
         };
+        self.touch();
+        debug_event!(direction = %self.direction, "flipped counter");
+    }
+
+    /// Rebuilds the counter as a countdown from the value it shows now, unlike
+    /// `flip`, which changes the value shown. A counter showing "00:00:00"
+    /// becomes a finished countdown.
+    /// ```rust
+    /// # use countrs::{Counter, Direction, Time};
+    /// # use countrs::types::TimeStamp;
+    /// let stopwatch = Counter::up(Some(TimeStamp::now() - 754), None);
+    /// let countdown = stopwatch.into_down_preserving().unwrap();
+    /// assert_eq!(countdown.direction, Direction::Down);
+    /// assert_eq!(countdown.to_string(), "00:12:34");
+    /// ```
+    pub fn into_down_preserving(self) -> Result<Counter<T>, TimeOverflow>
+    where
+        D: PartialOrd,
+    {
+        let shown = self.shown();
+        let now = T::now();
+        Ok(Counter::new(now, now.add_seconds(shown)?, Direction::Down).stamped(now))
+    }
+
+    /// Like `into_down_preserving`, but counting up from the value shown now.
+    pub fn into_up_preserving(self) -> Result<Counter<T>, TimeOverflow>
+    where
+        D: PartialOrd,
+    {
+        let shown = self.shown();
+        let now = T::now();
+        let start = now.add_seconds(D::try_seconds(-shown.num_seconds())?)?;
+        Ok(Counter::new(start, now, Direction::Up).stamped(now))
+    }
+
+    /// The duration behind the displayed value, which is never negative.
+    fn shown(&self) -> D
+    where
+        D: PartialOrd,
+    {
+        let zero = D::seconds(0);
+        match self.duration() {
+            duration if duration < zero => zero,
+            duration => duration,
+        }
+    }
+
+    /// Chooses how partial seconds are shown. With the default, `Floor`, they
+    /// are dropped, so a new countdown of ten minutes shows "00:09:59". With
+    /// `Ceil` they count as a full second, so it shows "00:10:00" and its
+    /// last value before finishing is "00:00:01". Partial seconds past `end`
+    /// are rounded the same way, away from zero.
+    pub fn set_rounding(&mut self, rounding: Rounding) {
+        self.rounding = rounding;
+        self.touch();
+    }
+
+    /// The rounding chosen with `set_rounding`
+    pub fn rounding(&self) -> Rounding {
+        self.rounding
+    }
+
+    /// Makes a counter counting up stop at `end` (if it is after `start`),
+    /// so it never shows more than the length of the window, e.g. for
+    /// progress through a session of fixed length. `elapsed` is unaffected.
+    /// ```rust
+    /// # use countrs::{Counter, Time};
+    /// # use countrs::types::TimeStamp;
+    /// let now = TimeStamp::now();
+    /// let mut session = Counter::up(Some(now - 4000), Some(now - 400));
+    /// session.clamp_to_end(true);
+    /// assert_eq!(session.to_string(), "01:00:00");
+    /// ```
+    pub fn clamp_to_end(&mut self, clamp: bool) {
+        self.stop_at_end = clamp;
+        self.touch();
+    }
+
+    /// `true` while the counter was last set to `clamp_to_end(true)`
+    pub fn clamps_to_end(&self) -> bool {
+        self.stop_at_end
+    }
+
+    /// Makes a counter counting down show how far it is past `end` once it
+    /// gets there, like a kitchen timer, instead of staying at "00:00:00".
+    /// `direction` stays `Down`; see `is_overdue`. Partial seconds past `end`
+    /// are rounded the other way around, so "00:00:00" is shown for one
+    /// second, not two.
+    /// ```rust
+    /// # use countrs::{Counter, Time};
+    /// # use countrs::types::TimeStamp;
+    /// let now = TimeStamp::now();
+    /// let mut tea = Counter::down(Some(now - 240), Some(now - 90));
+    /// tea.set_auto_flip(true);
+    /// assert!(tea.is_overdue());
+    /// assert_eq!(tea.to_string(), "00:01:31");
+    /// ```
+    pub fn set_auto_flip(&mut self, auto_flip: bool) {
+        self.auto_flip = auto_flip;
+        self.touch();
+    }
+
+    /// Whether the counter was last set to `set_auto_flip(true)`
+    pub fn auto_flips(&self) -> bool {
+        self.auto_flip
+    }
+
+    /// `true` once a counter counting down with `set_auto_flip` has reached
+    /// `end`, and is now showing the time past it.
+    pub fn is_overdue(&self) -> bool {
+        self.is_overdue_at(T::now())
+    }
+
+    fn is_overdue_at(&self, reference: T) -> bool {
+        self.auto_flip
+            && self.direction == Direction::Down
+            && !is_positive(&self.until_end_at(reference))
+    }
+
+    /// Freezes the displayed value until `resume` is called.
+    /// Pausing an already paused counter does nothing.
+    pub fn pause(&mut self) {
+        if self.paused_at.is_none() {
+            self.paused_at = Some(T::now());
+            debug_event!("paused counter");
+        }
+    }
+
+    /// Continues from the value shown while paused, by moving `start` (`Up`)
+    /// or `end` (`Down`) later by the time spent paused. Resuming a counter
+    /// that is not paused does nothing. On overflow, the counter stays paused.
+    pub fn resume(&mut self) -> Result<(), TimeOverflow> {
+        let Some(paused_at) = self.paused_at else {
+            return Ok(());
+        };
+        let paused_for = T::now() - paused_at;
+        match self.direction {
+            Direction::Down => self.end = self.end.add_seconds(paused_for)?,
+            Direction::Up => self.start = self.start.add_seconds(paused_for)?,
+        }
+        self.paused_at = None;
+        self.touch();
+        debug_event!(
+            length = (self.end - self.start).num_seconds(),
+            "resumed counter"
+        );
+        Ok(())
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused_at.is_some()
+    }
+
+    /// The moment `pause` was called, while the counter is paused
+    pub fn paused_at(&self) -> Option<T> {
+        self.paused_at
+    }
+
+    /// The current time, or the moment the counter was paused.
+    fn now(&self) -> T {
+        self.paused_at.unwrap_or_else(T::now)
+    }
+
+    /// `reference`, or the moment the counter was paused.
+    fn reading_time(&self, reference: T) -> T {
+        self.paused_at.unwrap_or(reference)
     }
 
     fn duration(&self) -> D {
+        self.duration_at(self.now())
+    }
+
+    fn duration_at(&self, reference: T) -> D {
+        let overdue = self.is_overdue_at(reference);
+        let reference = self.reading_time(reference);
         match self.direction {
-            Direction::Down => self.end - T::now(),
-            Direction::Up => T::now() - self.start,
+            Direction::Down if overdue => reference - self.end,
+            Direction::Down => reference.until_end(self.start, self.end),
+            Direction::Up if self.stops_at(reference) => self.end - self.start,
+            Direction::Up => reference - self.start,
+        }
+    }
+
+    /// Whether `clamp_to_end` holds the value at `end` at the time `reference`.
+    fn stops_at(&self, reference: T) -> bool {
+        self.stop_at_end
+            && is_positive(&(self.end - self.start))
+            && as_nanos(&reference.until_end(self.start, self.end)) < 0
+    }
+
+    /// The reading the counter would have if the clock showed `reference`.
+    /// A paused counter keeps showing its value from the moment of pausing.
+    pub fn at(&self, reference: T) -> CounterReading {
+        CounterReading::new(self.rounded_at(reference))
+    }
+
+    /// The time from `reference` until `end`, negative once past it.
+    /// A paused counter is evaluated at the moment of pausing instead.
+    pub fn remaining_at(&self, reference: T) -> D {
+        self.until_end_at(reference)
+    }
+
+    /// `Time::until_end` from the reading at `reference` to `end`
+    fn until_end_at(&self, reference: T) -> D {
+        self.reading_time(reference).until_end(self.start, self.end)
+    }
+
+    /// The time from `start` until `reference`, negative before it.
+    /// A paused counter is evaluated at the moment of pausing instead.
+    pub fn elapsed_at(&self, reference: T) -> D {
+        self.reading_time(reference) - self.start
+    }
+
+    /// The length of the window from `start` to `end`, or zero if `end` is
+    /// before `start`. While the window is active, `elapsed` and `remaining`
+    /// add up to it.
+    /// ```rust
+    /// # use countrs::Counter;
+    /// # use countrs::unix::{Seconds, UnixSeconds};
+    /// let counter = Counter::down(Some(UnixSeconds(0)), Some(UnixSeconds(1500)));
+    /// let reference = UnixSeconds(600);
+    /// assert_eq!(counter.total(), Seconds(1500));
+    /// assert_eq!(counter.elapsed_at(reference).0 + counter.remaining_at(reference).0, 1500);
+    /// ```
+    pub fn total(&self) -> D
+    where
+        D: PartialOrd,
+    {
+        let zero = D::seconds(0);
+        match self.end - self.start {
+            total if total < zero => zero,
+            total => total,
         }
     }
 
+    /// `remaining_at` the current time
+    pub fn remaining(&self) -> D {
+        self.remaining_at(self.now())
+    }
+
+    /// `elapsed_at` the current time
+    pub fn elapsed(&self) -> D {
+        self.elapsed_at(self.now())
+    }
+
+    /// Moves `start` back by the time `other` has elapsed, so that this
+    /// counter's `elapsed` grows by the same amount, e.g. to fold a session
+    /// into a running total. Both are read at the same instant. Counters
+    /// counting up without a `goal` have their `end` moved along, so they
+    /// stay open-ended. On error the counter is left unchanged.
+    pub fn accumulate(&mut self, other: &Counter<T>) -> Result<(), TimeOverflow>
+    where
+        D: Copy + PartialOrd + Neg<Output = D>,
+    {
+        let elapsed = -other.elapsed_at(T::now());
+        let start = self.start.add_seconds(elapsed)?;
+        if self.goal().is_none() {
+            self.end = self.end.add_seconds(elapsed)?;
+        }
+        self.start = start;
+        self.touch();
+        debug_event!(
+            length = (self.end - self.start).num_seconds(),
+            "accumulated counter"
+        );
+        Ok(())
+    }
+
+    /// The `elapsed` times of all `counters` added up, all read at the same
+    /// instant. Fails if the total does not fit into the time backend.
+    pub fn sum_elapsed(counters: &[Counter<T>]) -> Result<D, TimeOverflow> {
+        let now = T::now();
+        checked_sum(now, counters.iter().map(|counter| counter.elapsed_at(now)))
+    }
+
     /// Returns the tuple of (hours, minutes, seconds) shown on the countdown(/up)
     pub fn counter(&self) -> (i64, i64, i64) {
-        let duration = self.duration();
-        match duration.num_seconds() {
-            num if num >= 0 => (num / 3600, num / 60 % 60, num % 60),
-            _ => (0, 0, 0),
+        self.breakdown().clamped_hms()
+    }
+
+    /// Reads the counter against `clock` instead of `Time::now`, e.g. for
+    /// reproducible output in tests.
+    /// ```rust
+    /// # use countrs::Counter;
+    /// # use countrs::clock::FixedClock;
+    /// # use countrs::unix::UnixSeconds;
+    /// let counter = Counter::down(Some(UnixSeconds(0)), Some(UnixSeconds(600)));
+    /// let clock = FixedClock(UnixSeconds(30));
+    /// assert_eq!(counter.with_clock(&clock).to_string(), "00:09:30");
+    /// ```
+    pub fn with_clock<C: Clock<T>>(&self, clock: C) -> WithClock<'_, T, C> {
+        WithClock {
+            counter: self,
+            clock,
+        }
+    }
+
+    /// Splits the value of `signed_seconds` into days, hours, minutes and
+    /// seconds, without clamping.
+    pub fn breakdown(&self) -> CounterReading {
+        self.at(self.now())
+    }
+
+    /// The time elapsed and the length of the window, read from a single
+    /// sample of the clock, like a media player's position "01:23 / 45:00".
+    /// The elapsed time is rounded like the counter's value and not clamped,
+    /// so it is negative before `start` and exceeds the length once past
+    /// `end`. Open-ended counters have a length of zero.
+    pub fn position(&self) -> (CounterReading, CounterReading)
+    where
+        D: PartialOrd,
+    {
+        self.position_at(self.now())
+    }
+
+    /// `position` if the clock showed `reference`
+    pub fn position_at(&self, reference: T) -> (CounterReading, CounterReading)
+    where
+        D: PartialOrd,
+    {
+        let elapsed = round_seconds(self.elapsed_at(reference), self.rounding);
+        let total = round_seconds(self.total(), self.rounding);
+        (CounterReading::new(elapsed), CounterReading::new(total))
+    }
+
+    /// Shows `position` as "MM:SS / MM:SS", or "HH:MM:SS / HH:MM:SS" for
+    /// windows of an hour or more, with each part padded like `Display`.
+    /// The elapsed part is clamped to the window, so it stays at
+    /// "00:00" before `start` and at the length once past `end`. A
+    /// zero-length window, including an open-ended one, shows "00:00 / 00:00".
+    /// ```rust
+    /// # use countrs::Counter;
+    /// # use countrs::unix::UnixSeconds;
+    /// let episode = Counter::down(Some(UnixSeconds(0)), Some(UnixSeconds(2700)));
+    /// assert_eq!(episode.position_string_at(UnixSeconds(83)), "01:23 / 45:00");
+    /// assert_eq!(episode.position_string_at(UnixSeconds(3000)), "45:00 / 45:00");
+    /// let film = Counter::down(Some(UnixSeconds(0)), Some(UnixSeconds(7200)));
+    /// assert_eq!(film.position_string_at(UnixSeconds(83)), "00:01:23 / 02:00:00");
+    /// ```
+    pub fn position_string(&self) -> String
+    where
+        D: PartialOrd,
+    {
+        self.position_string_at(self.now())
+    }
+
+    /// `position_string` if the clock showed `reference`
+    pub fn position_string_at(&self, reference: T) -> String
+    where
+        D: PartialOrd,
+    {
+        let (elapsed, total) = self.position_at(reference);
+        let total_seconds = total.total_seconds();
+        let elapsed = CounterReading::new(elapsed.total_seconds().clamp(0, total_seconds));
+        let (elapsed, total) = (elapsed.clamped_hms(), total.clamped_hms());
+        match total.0 {
+            0 => format!(
+                "{:0>2}:{:0>2} / {:0>2}:{:0>2}",
+                elapsed.1, elapsed.2, total.1, total.2
+            ),
+            _ => format!(
+                "{:0>2}:{:0>2}:{:0>2} / {:0>2}:{:0>2}:{:0>2}",
+                elapsed.0, elapsed.1, elapsed.2, total.0, total.1, total.2
+            ),
+        }
+    }
+
+    /// Captures what the counter shows right now. The counter itself keeps
+    /// running; see `pause` to stop it instead.
+    pub fn snapshot(&self) -> FrozenCounter {
+        FrozenCounter {
+            direction: self.direction,
+            reading: self.breakdown(),
+        }
+    }
+
+    /// Yields the reading each time the displayed value changes, sleeping in
+    /// between. The first reading is yielded right away, and the sleeps follow
+    /// the counter's own second boundaries, so no value is skipped. Counting
+    /// down ends at "00:00:00" (unless `set_auto_flip` is set), counting up
+    /// runs until the iterator is dropped (see `Iterator::take`), and a
+    /// paused counter yields once.
+    /// ```rust,no_run
+    /// # use countrs::Counter;
+    /// # use countrs::types::TimeStamp;
+    /// let counter = Counter::<TimeStamp>::down_showing("00:05:00").unwrap();
+    /// // Instead of `loop { println!("{counter}"); sleep(Duration::from_secs(1)) }`
+    /// for reading in counter.ticks() {
+    ///     println!("{reading}");
+    /// }
+    /// ```
+    pub fn ticks(&self) -> Ticks<'_, T> {
+        Ticks {
+            counter: self,
+            last: None,
+            finished: false,
+        }
+    }
+
+    /// Returns the total number of full weeks on the countdown(/up)
+    pub fn weeks(&self) -> i64 {
+        self.days() / 7
+    }
+
+    /// Returns the total number of full days on the countdown(/up)
+    pub fn days(&self) -> i64 {
+        match self.signed_seconds() {
+            num if num >= 0 => num / 86400,
+            _ => 0,
         }
     }
 
     /// Returns the total number of full hours on the countdown(/up)
     pub fn hours(&self) -> i64 {
-        let duration = self.duration();
-        match duration.num_seconds() {
+        match self.signed_seconds() {
             num if num >= 0 => num / 3600,
             _ => 0,
         }
@@ -168,8 +1125,7 @@ where
 
     /// Returns the total number of full minutes on the countdown(/up)
     pub fn minutes(&self) -> i64 {
-        let duration = self.duration();
-        match duration.num_seconds() {
+        match self.signed_seconds() {
             num if num >= 0 => num / 60,
             _ => 0,
         }
@@ -177,32 +1133,944 @@ where
 
     /// Returns the total number of seconds on the countdown(/up)
     pub fn seconds(&self) -> i64 {
-        let duration = self.duration();
-        match duration.num_seconds() {
+        match self.signed_seconds() {
             num if num >= 0 => num,
             _ => 0,
         }
     }
 
-    pub fn try_move_start(&mut self, seconds: impl Into<D>) -> Result<(), TimeOverflow> {
-        self.start = self.start.add_seconds(seconds.into())?;
-        Ok(())
+    /// Yields `start`, `start + every`, `start + 2 * every`, and so on, up to
+    /// and including `end`. Yields nothing if `end` is before `start` or
+    /// `every` is not positive, and stops early if an addition overflows.
+    pub fn intervals(&self, every: D) -> impl Iterator<Item = T>
+    where
+        T: PartialOrd,
+        D: Copy + PartialOrd,
+    {
+        let end = self.end;
+        let first = (self.start <= end && every > D::seconds(0)).then_some(self.start);
+        std::iter::successors(first, move |time| {
+            time.add_seconds(every).ok().filter(|next| *next <= end)
+        })
     }
 
-    pub fn try_move_end(&mut self, seconds: impl Into<D>) -> Result<(), TimeOverflow> {
-        self.end = self.end.add_seconds(seconds.into())?;
-        Ok(())
+    /// The number of times yielded by `intervals`, found by walking them.
+    pub fn interval_count(&self, every: D) -> usize
+    where
+        T: PartialOrd,
+        D: Copy + PartialOrd,
+    {
+        self.intervals(every).count()
     }
-}
 
-/// "Up" -> `Up`, "Down" -> `Down`
-impl FromStr for Direction {
-    type Err = InvalidDirection;
+    /// How much later `self` ends than `other`, negative if it ends first.
+    /// Since both `end`s are fixed, this needs no reading of the clock, and
+    /// unlike the derived `Ord`, which compares `start` first, it tells which
+    /// counter fires first.
+    /// ```rust
+    /// # use countrs::{Counter, Time, TimeUnits};
+    /// # use countrs::types::TimeStamp;
+    /// let now = TimeStamp::now();
+    /// let tea = Counter::down(Some(now), Some(now + 240));
+    /// let pasta = Counter::down(Some(now - 300), Some(now + 600));
+    /// assert_eq!(pasta.diff(&tea).num_seconds(), 360);
+    /// assert_eq!(tea.diff(&pasta).num_seconds(), -360);
+    /// ```
+    pub fn diff(&self, other: &Counter<T>) -> D {
+        self.end - other.end
+    }
 
-    fn from_str(string: &str) -> Result<Self, InvalidDirection> {
-        match string {
-            "Up" => Ok(Direction::Up),
-            "Down" => Ok(Direction::Down),
+    /// Orders two counters by the time left until their `end`, both measured
+    /// from the same reading of the clock, or from when they were paused.
+    /// Counters counting up with an `end` that is not after `start` are
+    /// open-ended, and order after all others. Ties are broken by `end`, then
+    /// by `start`. See `diff` for the size of the gap.
+    pub fn cmp_remaining(&self, other: &Counter<T>) -> Ordering
+    where
+        T: Ord,
+        D: Ord,
+    {
+        self.cmp_remaining_at(other, T::now())
+    }
+
+    fn cmp_remaining_at(&self, other: &Counter<T>, now: T) -> Ordering
+    where
+        T: Ord,
+        D: Ord,
+    {
+        let remaining = |counter: &Counter<T>| match counter.direction {
+            Direction::Up if counter.end <= counter.start => None,
+            _ => Some(counter.until_end_at(now)),
+        };
+        match (remaining(self), remaining(other)) {
+            (Some(left), Some(right)) => left.cmp(&right),
+            (None, None) => Ordering::Equal,
+            (left, right) => right.is_some().cmp(&left.is_some()),
+        }
+        .then(self.end.cmp(&other.end))
+        .then(self.start.cmp(&other.start))
+    }
+
+    /// `true` from the moment the clock reaches `end`, regardless of the
+    /// clamping in `counter`. Counters counting up only finish if their `end`
+    /// is after `start`, otherwise they are open-ended.
+    pub fn is_finished(&self) -> bool
+    where
+        D: PartialOrd,
+    {
+        self.is_finished_at(T::now())
+    }
+
+    /// `is_finished` if the clock showed `reference`.
+    pub(crate) fn is_finished_at(&self, reference: T) -> bool
+    where
+        D: PartialOrd,
+    {
+        let zero = D::seconds(0);
+        match self.direction {
+            Direction::Up if self.end - self.start <= zero => false,
+            _ => self.until_end_at(reference) <= zero,
+        }
+    }
+
+    /// How far past `end` the counter has run, once it `is_finished`.
+    /// ```rust
+    /// # use countrs::{Counter, Time, TimeUnits};
+    /// # use countrs::types::TimeStamp;
+    /// let now = TimeStamp::now();
+    /// let meeting = Counter::down(Some(now - 3600), Some(now - 90));
+    /// assert_eq!(meeting.overtime().unwrap().num_seconds(), 90);
+    /// ```
+    pub fn overtime(&self) -> Option<D>
+    where
+        D: PartialOrd,
+    {
+        let now = self.now();
+        self.is_finished_at(now).then(|| now - self.end)
+    }
+
+    /// `true` while at most `seconds` are left until `completion_time`, e.g.
+    /// for a warning during the last minute. Always `false` once finished, and
+    /// for counters that never finish. A threshold too long for the backend
+    /// covers any time left, and one too far below zero none.
+    pub fn in_final_seconds(&self, seconds: i64) -> bool
+    where
+        D: PartialOrd,
+    {
+        self.eta()
+            .is_some_and(|left| match D::try_seconds(seconds) {
+                Ok(threshold) => left <= threshold,
+                Err(_) => seconds > 0,
+            })
+    }
+
+    /// Like `in_final_seconds`, with the threshold given as a fraction of the
+    /// window from `start` to `end`, e.g. `0.1` for the last tenth.
+    pub fn in_final_fraction(&self, fraction: f64) -> bool
+    where
+        D: PartialOrd,
+    {
+        let total = as_secs_f64(&(self.end - self.start));
+        self.eta()
+            .is_some_and(|left| as_secs_f64(&left) <= total * fraction)
+    }
+
+    /// `false` while `start` is still in the future, e.g. for a scheduled
+    /// counter, which `Display` would show as "00:00:00" either way.
+    pub fn has_started(&self) -> bool
+    where
+        D: PartialOrd,
+    {
+        self.now() - self.start >= D::seconds(0)
+    }
+
+    /// `true` once the counter `has_started`, until it `is_finished`.
+    pub fn is_running(&self) -> bool
+    where
+        D: PartialOrd,
+    {
+        self.state() == CounterState::Running
+    }
+
+    /// Classifies the counter against a single reading of the clock.
+    /// ```rust
+    /// # use countrs::{Counter, CounterState, Time};
+    /// # use countrs::types::TimeStamp;
+    /// let now = TimeStamp::now();
+    /// let talk = Counter::down(Some(now + 60), Some(now + 1860));
+    /// assert_eq!(talk.state(), CounterState::Pending);
+    /// assert_eq!(talk.state().to_string(), "pending");
+    /// ```
+    pub fn state(&self) -> CounterState
+    where
+        D: PartialOrd,
+    {
+        self.state_at(self.now())
+    }
+
+    /// `state` if the clock showed `reference`.
+    pub(crate) fn state_at(&self, reference: T) -> CounterState
+    where
+        D: PartialOrd,
+    {
+        let now = self.reading_time(reference);
+        if now - self.start < D::seconds(0) {
+            CounterState::Pending
+        } else if self.is_finished_at(now) {
+            CounterState::Finished
+        } else {
+            CounterState::Running
+        }
+    }
+
+    /// The time until `start`, or `None` once the counter `has_started`.
+    pub fn starts_in(&self) -> Option<D>
+    where
+        D: PartialOrd,
+    {
+        (!self.has_started()).then(|| self.start - self.now())
+    }
+
+    /// Displays like the counter itself, except that before `start` it shows
+    /// the time until `start`, prefixed with "-".
+    /// ```rust
+    /// # use countrs::{Counter, Time};
+    /// # use countrs::types::TimeStamp;
+    /// let now = TimeStamp::now();
+    /// let stream = Counter::up(Some(now + 91), None);
+    /// assert_eq!(stream.to_string(), "00:00:00");
+    /// assert_eq!(stream.display_prestart().to_string(), "-00:01:30");
+    /// ```
+    pub fn display_prestart(&self) -> PreStart<'_, T> {
+        PreStart { counter: self }
+    }
+
+    /// When the counter finishes, see `is_finished`: `end` for counters
+    /// counting down, and for counters counting up only if they have a goal,
+    /// i.e. `end` after `start`. A paused countdown's `end` moves on `resume`.
+    /// ```rust
+    /// # use countrs::{Counter, Time};
+    /// # use countrs::types::TimeStamp;
+    /// let now = TimeStamp::now();
+    /// let countdown = Counter::down(Some(now), Some(now + 600));
+    /// assert_eq!(countdown.completion_time(), Some(now + 600));
+    ///
+    /// let workout = Counter::up(Some(now - 60), Some(now + 1800));
+    /// assert_eq!(workout.completion_time(), Some(now + 1800));
+    /// assert_eq!(Counter::up(Some(now), None).completion_time(), None);
+    /// ```
+    pub fn completion_time(&self) -> Option<T>
+    where
+        D: PartialOrd,
+    {
+        match self.direction {
+            Direction::Up if self.end - self.start <= D::seconds(0) => None,
+            _ => Some(self.end),
+        }
+    }
+
+    /// The time left until `completion_time`, or `None` once finished (or if
+    /// the counter never finishes). Unlike `remaining`, this tells a counter
+    /// that has just finished apart from one that is still running.
+    /// ```rust
+    /// # use countrs::{Counter, Time, TimeUnits};
+    /// # use countrs::types::TimeStamp;
+    /// let now = TimeStamp::now();
+    /// let countdown = Counter::down(Some(now - 60), Some(now + 600));
+    /// assert!(countdown.eta().unwrap().num_seconds() >= 599);
+    /// assert!(Counter::down(Some(now - 60), Some(now)).eta().is_none());
+    ///
+    /// let workout = Counter::up(Some(now - 60), Some(now + 1800));
+    /// assert!(workout.eta().unwrap().num_seconds() >= 1799);
+    /// ```
+    pub fn eta(&self) -> Option<D>
+    where
+        D: PartialOrd,
+    {
+        self.completion_time()?;
+        let now = self.now();
+        (!self.is_finished_at(now)).then(|| self.remaining_at(now))
+    }
+
+    /// The target length of the counter, i.e. the window from `start` to its
+    /// `completion_time`. For counters counting up, the goal is just `end`,
+    /// which they keep counting past unless they `clamp_to_end`, and which is
+    /// saved along with the rest of the counter by `to_file`.
+    /// ```rust
+    /// # use countrs::{Counter, Time, TimeUnits};
+    /// # use countrs::types::{Duration, TimeStamp};
+    /// let mut writing = Counter::up(Some(TimeStamp::now() - 3600), None);
+    /// assert!(writing.goal().is_none());
+    /// writing.set_goal(Duration::seconds(2 * 3600)).unwrap();
+    /// assert!(!writing.reached_goal());
+    /// assert_eq!(writing.remaining_to_goal().num_seconds(), 3599);
+    /// ```
+    pub fn goal(&self) -> Option<D>
+    where
+        D: PartialOrd,
+    {
+        self.completion_time().map(|end| end - self.start)
+    }
+
+    /// Sets `end` to `goal` after `start`.
+    pub fn set_goal(&mut self, goal: impl Into<D>) -> Result<(), TimeOverflow> {
+        let end = self.start.add_seconds(goal.into())?;
+        self.set_end_unchecked(end);
+        Ok(())
+    }
+
+    /// `true` once the counter has run for its `goal`, see `is_finished`.
+    /// Always `false` without a goal.
+    pub fn reached_goal(&self) -> bool
+    where
+        D: PartialOrd,
+    {
+        self.is_finished()
+    }
+
+    /// The time left until the `goal` is reached, or zero once it has been,
+    /// and for counters without a goal.
+    pub fn remaining_to_goal(&self) -> D
+    where
+        D: PartialOrd,
+    {
+        self.eta().unwrap_or_else(|| D::seconds(0))
+    }
+
+    /// The fraction of the window from `start` to `end` that has passed,
+    /// clamped to `0.0..=1.0`, in either direction. A window that is empty or
+    /// ends before it starts is at `0.0` before `end` and `1.0` from then on.
+    pub fn progress(&self) -> f64 {
+        let now = self.now();
+        let span = (self.end - self.start).num_seconds();
+        if span <= 0 {
+            return match self.until_end_at(now).num_seconds() <= 0 {
+                true => 1.0,
+                false => 0.0,
+            };
+        }
+        let elapsed = (now - self.start).num_seconds();
+        (elapsed as f64 / span as f64).clamp(0.0, 1.0)
+    }
+
+    /// Like `seconds`, but negative instead of clamped when past `end`
+    /// (counting down) or before `start` (counting up)
+    pub fn signed_seconds(&self) -> i64 {
+        self.rounded_at(self.now())
+    }
+
+    /// The whole seconds of `duration_at(reference)`, rounded by `rounding`
+    /// away from the sub-second part. `Floor` keeps only the whole seconds.
+    /// Overdue counters swap `Floor` and `Ceil`, see `set_auto_flip`.
+    fn rounded_at(&self, reference: T) -> i64 {
+        let rounding = match (self.is_overdue_at(reference), self.rounding) {
+            (true, Rounding::Floor) => Rounding::Ceil,
+            (true, Rounding::Ceil) => Rounding::Floor,
+            (_, rounding) => rounding,
+        };
+        round_seconds(self.duration_at(reference), rounding)
+    }
+
+    /// Like `counter`, but each part carries the sign of `signed_seconds`
+    pub fn signed_counter(&self) -> (i64, i64, i64) {
+        let num = self.signed_seconds();
+        (num / 3600, num / 60 % 60, num % 60)
+    }
+
+    /// Returns how far `start` can move down and `end` can move up before
+    /// `try_move_start`/`try_move_end` overflow, or `None` if the backend does
+    /// not report its limits.
+    pub fn headroom(&self) -> Option<(D, D)> {
+        Some((self.start - T::min_value()?, T::max_value()? - self.end))
+    }
+
+    /// Like `try_move_start`, taking the adjustment in minutes, hours or days.
+    pub fn move_start_by(&mut self, adjust: Adjust) -> Result<(), TimeOverflow> {
+        let seconds = adjust.seconds().ok_or(TimeOverflow)?;
+        self.try_move_start(D::try_seconds(seconds)?)
+    }
+
+    /// Like `try_move_end`, taking the adjustment in minutes, hours or days.
+    pub fn move_end_by(&mut self, adjust: Adjust) -> Result<(), TimeOverflow> {
+        let seconds = adjust.seconds().ok_or(TimeOverflow)?;
+        self.try_move_end(D::try_seconds(seconds)?)
+    }
+
+    /// Moves both `start` and `end` by `amount`. If either would overflow,
+    /// neither is changed.
+    pub fn shift(&mut self, amount: impl Into<D>) -> Result<(), TimeOverflow>
+    where
+        D: Copy,
+    {
+        let amount = amount.into();
+        let start = self.start.add_seconds(amount)?;
+        let end = self.end.add_seconds(amount)?;
+        self.start = start;
+        self.end = end;
+        self.touch();
+        debug_event!(delta = amount.num_seconds(), "shifted counter");
+        Ok(())
+    }
+
+    /// Sets `start`, refusing to move it after `end` while counting down.
+    /// Counting up, an `end` before `start` just means the counter is open-ended.
+    pub fn set_start(&mut self, start: T) -> Result<(), InvalidWindow>
+    where
+        T: Display,
+        D: PartialOrd,
+    {
+        self.check_window(start, self.end)?;
+        self.set_start_unchecked(start);
+        Ok(())
+    }
+
+    /// Sets `end`, refusing to move it before `start` while counting down.
+    pub fn set_end(&mut self, end: T) -> Result<(), InvalidWindow>
+    where
+        T: Display,
+        D: PartialOrd,
+    {
+        self.check_window(self.start, end)?;
+        self.set_end_unchecked(end);
+        Ok(())
+    }
+
+    /// Like `set_start`, but allows any window.
+    pub fn set_start_unchecked(&mut self, start: T) {
+        self.start = start;
+        self.touch();
+        debug_event!(length = (self.end - self.start).num_seconds(), "set start");
+    }
+
+    /// Like `set_end`, but allows any window.
+    pub fn set_end_unchecked(&mut self, end: T) {
+        self.end = end;
+        self.touch();
+        debug_event!(length = (self.end - self.start).num_seconds(), "set end");
+    }
+
+    fn check_window(&self, start: T, end: T) -> Result<(), InvalidWindow>
+    where
+        T: Display,
+        D: PartialOrd,
+    {
+        match self.direction {
+            Direction::Down => Self::validate(start, end),
+            Direction::Up => Ok(()),
+        }
+    }
+
+    pub fn try_move_start(&mut self, seconds: impl Into<D>) -> Result<(), TimeOverflow> {
+        let seconds = seconds.into();
+        #[cfg(feature = "tracing")]
+        let delta = seconds.num_seconds();
+        self.start = self.start.add_seconds(seconds)?;
+        self.touch();
+        debug_event!(
+            delta,
+            length = (self.end - self.start).num_seconds(),
+            "moved start"
+        );
+        Ok(())
+    }
+
+    pub fn try_move_end(&mut self, seconds: impl Into<D>) -> Result<(), TimeOverflow> {
+        let seconds = seconds.into();
+        #[cfg(feature = "tracing")]
+        let delta = seconds.num_seconds();
+        self.end = self.end.add_seconds(seconds)?;
+        self.touch();
+        debug_event!(
+            delta,
+            length = (self.end - self.start).num_seconds(),
+            "moved end"
+        );
+        Ok(())
+    }
+
+    /// Like `try_move_start`, but refuses to move `start` past `end`, in
+    /// either direction. On error the counter is left unchanged.
+    ///
+    /// Open-ended `Up` counters already end before they start, so every move
+    /// is rejected; use `try_move_start` for those.
+    pub fn try_move_start_strict(&mut self, seconds: impl Into<D>) -> Result<(), WindowViolation>
+    where
+        D: PartialOrd,
+    {
+        let start = self
+            .start
+            .add_seconds(seconds.into())
+            .map_err(|_| WindowViolation::Overflow)?;
+        if self.end - start < D::seconds(0) {
+            return Err(WindowViolation::Crossed);
+        }
+        self.set_start_unchecked(start);
+        Ok(())
+    }
+
+    /// Like `try_move_end`, but refuses to move `end` before `start`, in
+    /// either direction. On error the counter is left unchanged.
+    pub fn try_move_end_strict(&mut self, seconds: impl Into<D>) -> Result<(), WindowViolation>
+    where
+        D: PartialOrd,
+    {
+        let end = self
+            .end
+            .add_seconds(seconds.into())
+            .map_err(|_| WindowViolation::Overflow)?;
+        if end - self.start < D::seconds(0) {
+            return Err(WindowViolation::Crossed);
+        }
+        self.set_end_unchecked(end);
+        Ok(())
+    }
+}
+
+impl<T, D> Counter<T>
+where
+    T: Copy + Display + FromStr + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits,
+{
+    /// Calls `to_string` on `start`, `end`, and `direction`, and `std::fs::write`s each
+    /// to one line in a file, in that order. Further state follows as one
+    /// `key=value` line each: `paused_at` while paused, the `initial_*`
+    /// reset targets if they differ from the current values, one `lap`
+    /// line per lap, `snoozes` once snoozed, `stop_at_end` and `auto_flip`
+    /// if set, `rounding` as `ceil` or `nearest` unless it is `Floor`, and
+    /// `created_at` and `modified_at` if known.
+    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_contents())?;
+        debug_event!(path = %path.display(), "saved counter");
+        Ok(())
+    }
+
+    /// The contents `to_file` writes
+    pub(crate) fn to_contents(&self) -> String {
+        let mut contents = format!("{}\n{}\n{}", self.start, self.end, self.direction);
+        if let Some(paused_at) = self.paused_at {
+            contents.push_str(&format!("\npaused_at={}", paused_at));
+        }
+        let initial = [
+            (
+                "initial_start",
+                self.start.to_string(),
+                self.initial_start.to_string(),
+            ),
+            (
+                "initial_end",
+                self.end.to_string(),
+                self.initial_end.to_string(),
+            ),
+            (
+                "initial_direction",
+                self.direction.to_string(),
+                self.initial_direction.to_string(),
+            ),
+        ];
+        for (key, current, initial) in initial {
+            if current != initial {
+                contents.push_str(&format!("\n{}={}", key, initial));
+            }
+        }
+        for lap in &self.laps {
+            contents.push_str(&format!("\nlap={}", lap));
+        }
+        if self.snoozes > 0 {
+            contents.push_str(&format!("\nsnoozes={}", self.snoozes));
+        }
+        if self.stop_at_end {
+            contents.push_str("\nstop_at_end=true");
+        }
+        if self.auto_flip {
+            contents.push_str("\nauto_flip=true");
+        }
+        match self.rounding {
+            Rounding::Floor => {}
+            Rounding::Ceil => contents.push_str("\nrounding=ceil"),
+            Rounding::Nearest => contents.push_str("\nrounding=nearest"),
+        }
+        if let Some(created_at) = self.audit.created_at {
+            contents.push_str(&format!("\ncreated_at={}", created_at));
+        }
+        if let Some(modified_at) = self.audit.modified_at {
+            contents.push_str(&format!("\nmodified_at={}", modified_at));
+        }
+        contents
+    }
+
+    /// Tries converting the first three lines of a file (read by `std::fs::read_to_string`)
+    /// into a `Counter` by attempting to parse them into `start`, `end`, and `direction`
+    /// respectively, calling `from_str`. Any further lines must be `key=value`
+    /// pairs as written by `to_file`.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Counter<T>> {
+        Self::from_file_with(path, |s| T::from_str(s).ok())
+    }
+
+    /// Reads a file written by `to_file`, parsing `start` and `end` with `parse`.
+    pub(crate) fn from_file_with<P, F>(path: P, parse: F) -> io::Result<Counter<T>>
+    where
+        P: AsRef<Path>,
+        F: Fn(&str) -> Option<T>,
+    {
+        let path = path.as_ref();
+        let counter = Self::from_contents_with(&read_to_string(path)?, parse)?;
+        debug_event!(
+            path = %path.display(),
+            start = %counter.start,
+            end = %counter.end,
+            direction = %counter.direction,
+            "loaded counter"
+        );
+        Ok(counter)
+    }
+
+    /// Parses the contents of a file written by `to_file`.
+    pub(crate) fn from_contents_with<F>(contents: &str, parse: F) -> io::Result<Counter<T>>
+    where
+        F: Fn(&str) -> Option<T>,
+    {
+        let mut lines = contents.split('\n');
+        if let (Some(s), Some(e), Some(d)) = (lines.next(), lines.next(), lines.next()) {
+            let start = parse(s).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "File does not contain valid start data",
+                )
+            })?;
+            let end = parse(e).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "File does not contain valid end data",
+                )
+            })?;
+            let Ok(direction) = d.parse() else {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "File doesn ot contain complete direction data",
+                ));
+            };
+
+            let mut counter = Counter::new(start, end, direction);
+            for line in lines.filter(|line| !line.is_empty()) {
+                let Some((key, value)) = line.split_once('=') else {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "File contains unknown counter data",
+                    ));
+                };
+                let invalid = || {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("File does not contain valid {} data", key),
+                    )
+                };
+                match key {
+                    "paused_at" => counter.paused_at = Some(parse(value).ok_or_else(invalid)?),
+                    "initial_start" => counter.initial_start = parse(value).ok_or_else(invalid)?,
+                    "initial_end" => counter.initial_end = parse(value).ok_or_else(invalid)?,
+                    "initial_direction" => {
+                        counter.initial_direction = value.parse().map_err(|_| invalid())?
+                    }
+                    "lap" => counter.laps.push(parse(value).ok_or_else(invalid)?),
+                    "snoozes" => counter.snoozes = value.parse().map_err(|_| invalid())?,
+                    "stop_at_end" => counter.stop_at_end = value.parse().map_err(|_| invalid())?,
+                    "auto_flip" => counter.auto_flip = value.parse().map_err(|_| invalid())?,
+                    "rounding" => {
+                        counter.rounding = match value {
+                            "floor" => Rounding::Floor,
+                            "ceil" => Rounding::Ceil,
+                            "nearest" => Rounding::Nearest,
+                            _ => return Err(invalid()),
+                        }
+                    }
+                    "created_at" => {
+                        counter.audit.created_at = Some(parse(value).ok_or_else(invalid)?)
+                    }
+                    "modified_at" => {
+                        counter.audit.modified_at = Some(parse(value).ok_or_else(invalid)?)
+                    }
+                    _ => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "File contains unknown counter data",
+                        ))
+                    }
+                }
+            }
+
+            return Ok(counter);
+        }
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "File does not contain valid counter data",
+        ))
+    }
+}
+
+/// The readings of a `Counter` as they change, returned by `Counter::ticks`
+pub struct Ticks<'a, T> {
+    counter: &'a Counter<T>,
+    last: Option<CounterReading>,
+    finished: bool,
+}
+
+impl<T, D> Iterator for Ticks<'_, T>
+where
+    T: Copy + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits,
+{
+    type Item = CounterReading;
+
+    fn next(&mut self) -> Option<CounterReading> {
+        if self.finished {
+            return None;
+        }
+        loop {
+            let now = self.counter.now();
+            let reading = self.counter.at(now);
+            if self.last != Some(reading) {
+                self.last = Some(reading);
+                self.finished = self.counter.is_paused()
+                    || (self.counter.direction == Direction::Down
+                        && !self.counter.auto_flip
+                        && reading.clamped_hms() == (0, 0, 0));
+                return Some(reading);
+            }
+            let direction = match self.counter.is_overdue_at(now) {
+                true => Direction::Up,
+                false => self.counter.direction,
+            };
+            std::thread::sleep(until_next_second(self.counter.duration_at(now), direction));
+        }
+    }
+}
+
+fn as_secs_f64<D: TimeUnits>(duration: &D) -> f64 {
+    duration.num_seconds() as f64 + duration.subsec_nanos() as f64 / 1e9
+}
+
+/// Adds up `durations` by stepping through the backend from `now`, so the
+/// total fails instead of wrapping once it leaves the backend's range.
+fn checked_sum<T, D>(now: T, durations: impl IntoIterator<Item = D>) -> Result<D, TimeOverflow>
+where
+    T: Copy + Time<Duration = D> + Sub<T, Output = D>,
+{
+    let total = durations
+        .into_iter()
+        .try_fold(now, |total, duration| total.add_seconds(duration))?;
+    Ok(total - now)
+}
+
+fn as_nanos<D: TimeUnits>(duration: &D) -> i128 {
+    i128::from(duration.num_seconds()) * 1_000_000_000 + i128::from(duration.subsec_nanos())
+}
+
+/// The whole seconds of `duration`, with partial ones rounded by `rounding`
+fn round_seconds<D: TimeUnits>(duration: D, rounding: Rounding) -> i64 {
+    let (seconds, nanos) = (duration.num_seconds(), duration.subsec_nanos());
+    let round_up = match rounding {
+        Rounding::Floor => false,
+        Rounding::Ceil => nanos != 0,
+        Rounding::Nearest => nanos.abs() >= 500_000_000,
+    };
+    match round_up {
+        true => seconds.saturating_add(nanos.signum()),
+        false => seconds,
+    }
+}
+
+/// `true` for durations above zero, including sub-second ones
+fn is_positive<D: TimeUnits>(duration: &D) -> bool {
+    duration.num_seconds() > 0 || duration.subsec_nanos() > 0
+}
+
+/// `time + delta`, or the backend's limit in that direction if it overflows.
+/// Backends without limits keep `time` instead.
+fn saturating_add<T, D>(time: T, delta: D) -> T
+where
+    T: Copy + Time<Duration = D>,
+    D: TimeUnits,
+{
+    let forward = is_positive(&delta);
+    time.add_seconds(delta).unwrap_or_else(|_| {
+        match forward {
+            true => T::max_value(),
+            false => T::min_value(),
+        }
+        .unwrap_or(time)
+    })
+}
+
+/// How long until the whole seconds of `duration` change, with a small margin
+/// so the sleep never wakes up just before the boundary. Durations without a
+/// sub-second part wait a full second.
+fn until_next_second<D: TimeUnits>(duration: D, direction: Direction) -> std::time::Duration {
+    const SECOND: u64 = 1_000_000_000;
+    let subsec = duration.subsec_nanos().unsigned_abs();
+    let positive = is_positive(&duration);
+    let towards_zero = positive == (direction == Direction::Down);
+    let nanos = match subsec {
+        0 => SECOND,
+        subsec if towards_zero => subsec,
+        subsec => SECOND - subsec,
+    };
+    std::time::Duration::from_nanos(nanos) + std::time::Duration::from_millis(1)
+}
+
+/// A `Counter` showing the time until its `start`, returned by
+/// `Counter::display_prestart`
+pub struct PreStart<'a, T> {
+    counter: &'a Counter<T>,
+}
+
+impl<T, D> Display for PreStart<'_, T>
+where
+    T: Copy + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits + PartialOrd,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.counter.starts_in() {
+            Some(until) => {
+                write!(f, "-")?;
+                write_hms(f, CounterReading::new(until.num_seconds()).clamped_hms())
+            }
+            None => write!(f, "{}", self.counter),
+        }
+    }
+}
+
+/// A `Counter` read against a `Clock`, returned by `Counter::with_clock`
+pub struct WithClock<'a, T, C> {
+    counter: &'a Counter<T>,
+    clock: C,
+}
+
+impl<T, D, C> WithClock<'_, T, C>
+where
+    T: Copy + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits,
+    C: Clock<T>,
+{
+    /// Like `Counter::counter`
+    pub fn counter(&self) -> (i64, i64, i64) {
+        self.breakdown().clamped_hms()
+    }
+
+    /// Like `Counter::breakdown`
+    pub fn breakdown(&self) -> CounterReading {
+        self.counter.at(self.clock.now())
+    }
+}
+
+/// Shows the value at the attached clock's time, as "HH:MM:SS"
+impl<T, D, C> Display for WithClock<'_, T, C>
+where
+    T: Copy + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits,
+    C: Clock<T>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_hms(f, self.counter())
+    }
+}
+
+/// Sorts counters by `Counter::cmp_remaining`, most urgent first, reading the
+/// clock only once for the whole sort.
+pub fn sort_by_urgency<T, D>(counters: &mut [Counter<T>])
+where
+    T: Copy + Time<Duration = D> + Sub<T, Output = D> + Ord,
+    D: TimeUnits + Ord,
+{
+    let now = T::now();
+    counters.sort_by(|left, right| left.cmp_remaining_at(right, now));
+}
+
+/// Validates and totals the components passed to the `*_hms` constructors.
+/// Hours are only limited when days are given.
+fn dhms_seconds(days: i64, hours: i64, minutes: i64, seconds: i64) -> Result<i64, InvalidHms> {
+    let hours_in_range = hours >= 0 && (days == 0 || hours < 24);
+    if days < 0 || !hours_in_range || !(0..60).contains(&minutes) || !(0..60).contains(&seconds) {
+        return Err(InvalidHms::OutOfRange);
+    }
+    days.checked_mul(86400)
+        .and_then(|total| total.checked_add(hours.checked_mul(3600)?))
+        .and_then(|total| total.checked_add(minutes * 60 + seconds))
+        .ok_or(InvalidHms::Overflow)
+}
+
+/// Puts more time on the clock: moves `end` later when counting down, and
+/// `start` earlier when counting up. Unlike `try_move_end`/`try_move_start`,
+/// the moved endpoint saturates at the backend's limits instead of failing,
+/// and stays put on backends without limits.
+/// ```rust
+/// # use countrs::{Counter, Time, TimeUnits};
+/// # use countrs::types::{Duration, TimeStamp};
+/// let mut break_timer = Counter::down(None, Some(TimeStamp::now() + 300));
+/// break_timer += Duration::seconds(60);
+/// assert_eq!(break_timer.to_string(), "00:05:59");
+/// ```
+impl<T, D> AddAssign<D> for Counter<T>
+where
+    T: Copy + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits + Copy + Neg<Output = D>,
+{
+    fn add_assign(&mut self, duration: D) {
+        match self.direction {
+            Direction::Down => self.end = saturating_add(self.end, duration),
+            Direction::Up => self.start = saturating_add(self.start, -duration),
+        }
+        self.touch();
+    }
+}
+
+/// Takes time off the clock, the opposite of `+=`, with the same saturation.
+impl<T, D> SubAssign<D> for Counter<T>
+where
+    T: Copy + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits + Copy + Neg<Output = D>,
+{
+    fn sub_assign(&mut self, duration: D) {
+        *self += -duration;
+    }
+}
+
+/// See `AddAssign`
+impl<T, D> Add<D> for Counter<T>
+where
+    T: Copy + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits + Copy + Neg<Output = D>,
+{
+    type Output = Counter<T>;
+
+    fn add(mut self, duration: D) -> Counter<T> {
+        self += duration;
+        self
+    }
+}
+
+/// See `SubAssign`
+impl<T, D> Sub<D> for Counter<T>
+where
+    T: Copy + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits + Copy + Neg<Output = D>,
+{
+    type Output = Counter<T>;
+
+    fn sub(mut self, duration: D) -> Counter<T> {
+        self -= duration;
+        self
+    }
+}
+
+/// "Up" -> `Up`, "Down" -> `Down`
+impl FromStr for Direction {
+    type Err = InvalidDirection;
+
+    fn from_str(string: &str) -> Result<Self, InvalidDirection> {
+        match string {
+            "Up" => Ok(Direction::Up),
+            "Down" => Ok(Direction::Down),
             _ => Err(InvalidDirection),
         }
     }
@@ -211,12 +2079,52 @@ impl FromStr for Direction {
 /// Displayed as "HH(+):MM:SS", not below "00:00:00"
 impl<T, D> Display for Counter<T>
 where
-    T: Copy + Default + Display + Time<Duration = D> + FromStr + Sub<T, Output = D>,
+    T: Copy + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_hms(f, self.counter())
+    }
+}
+
+/// Shows the raw fields along with the value currently shown by `Display`, e.g.
+/// `Counter { direction: Down, start: 0, end: 599, paused_at: None, showing: "00:09:59" }`
+impl<T, D> fmt::Debug for Counter<T>
+where
+    T: fmt::Debug + Copy + Time<Duration = D> + Sub<T, Output = D>,
     D: TimeUnits,
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Counter")
+            .field("direction", &self.direction)
+            .field("start", &self.start)
+            .field("end", &self.end)
+            .field("paused_at", &self.paused_at)
+            .field("showing", &format_args!("\"{}\"", self))
+            .finish()
+    }
+}
+
+/// Same as `Display`, without allocating
+#[cfg(feature = "defmt")]
+impl<T, D> defmt::Format for Counter<T>
+where
+    T: Copy + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits,
+{
+    fn format(&self, f: defmt::Formatter<'_>) {
         let (hours, minutes, seconds) = self.counter();
-        write!(f, "{:0>2}:{:0>2}:{:0>2}", hours, minutes, seconds)
+        defmt::write!(f, "{=i64:02}:{=i64:02}:{=i64:02}", hours, minutes, seconds)
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Direction {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        match self {
+            Direction::Up => defmt::write!(f, "Up"),
+            Direction::Down => defmt::write!(f, "Down"),
+        }
     }
 }
 
@@ -230,5 +2138,27 @@ impl Display for Direction {
     }
 }
 
+#[cfg(feature = "defmt")]
+impl defmt::Format for CounterState {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        match self {
+            CounterState::Pending => defmt::write!(f, "pending"),
+            CounterState::Running => defmt::write!(f, "running"),
+            CounterState::Finished => defmt::write!(f, "finished"),
+        }
+    }
+}
+
+/// `Pending` -> "pending", `Running` -> "running", `Finished` -> "finished"
+impl Display for CounterState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CounterState::Pending => write!(f, "pending"),
+            CounterState::Running => write!(f, "running"),
+            CounterState::Finished => write!(f, "finished"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests;
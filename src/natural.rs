@@ -0,0 +1,163 @@
+//! This module implements `TimeStamp::parse_natural`, a small, deterministic
+//! grammar for phrases like "tomorrow 9am" or "next friday 17:00".
+use crate::errors::TimeParserError;
+use crate::types::TimeStamp;
+use chrono::{DateTime, Datelike, Duration, LocalResult, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono::{NaiveDateTime, Weekday};
+
+const NATURAL: &[&str] = &["natural language"];
+
+const WEEKDAYS: [(&str, &str, Weekday); 7] = [
+    ("monday", "mon", Weekday::Mon),
+    ("tuesday", "tue", Weekday::Tue),
+    ("wednesday", "wed", Weekday::Wed),
+    ("thursday", "thu", Weekday::Thu),
+    ("friday", "fri", Weekday::Fri),
+    ("saturday", "sat", Weekday::Sat),
+    ("sunday", "sun", Weekday::Sun),
+];
+
+enum Day {
+    Today,
+    Tomorrow,
+    Weekday(Weekday),
+    NextWeekday(Weekday),
+}
+
+impl TimeStamp {
+    /// Parses a phrase of the form `[day] [at] [time]`, case-insensitively,
+    /// relative to `reference_now` as seen in the time zone `tz`.
+    ///
+    /// `day` is `today`, `tomorrow`, a weekday (`friday` or `fri`), or `next`
+    /// followed by a weekday. `time` is `9am`, `9:30 pm`, `17:00`, `noon` or
+    /// `midnight`. At least one of the two must be present.
+    ///
+    /// Resolution rules:
+    /// * A missing time means the start of the day (00:00).
+    /// * A time without a day is the next occurrence of that time, so "9am"
+    ///   at 10:00 means tomorrow.
+    /// * A bare weekday is the next occurrence of that weekday and time, which
+    ///   is today if that is still in the future: "friday 17:00" on a Friday
+    ///   morning is today, but "friday" alone on a Friday is a week away.
+    /// * `next <weekday>` is the first such day strictly after today.
+    /// * `today <time>` is always today, even if that time has passed.
+    /// * Local times made ambiguous by a DST change resolve to the earlier
+    ///   instant; local times skipped by a DST change are rejected.
+    pub fn parse_natural<Tz: TimeZone>(
+        string: &str,
+        reference_now: TimeStamp,
+        tz: &Tz,
+    ) -> Result<TimeStamp, TimeParserError> {
+        let error = TimeParserError::new(NATURAL);
+        let lowercase = string.to_lowercase();
+        let mut tokens = lowercase.split_whitespace().peekable();
+
+        let day = match tokens.peek().copied() {
+            Some("today") => Some(Day::Today),
+            Some("tomorrow") => Some(Day::Tomorrow),
+            Some("next") => {
+                tokens.next();
+                Some(Day::NextWeekday(
+                    tokens.peek().and_then(|t| weekday(t)).ok_or(error)?,
+                ))
+            }
+            Some(token) => weekday(token).map(Day::Weekday),
+            None => return Err(error),
+        };
+        if day.is_some() {
+            tokens.next();
+            if tokens.peek() == Some(&"at") {
+                tokens.next();
+            }
+        }
+        let rest: Vec<&str> = tokens.collect();
+        let time = match rest.as_slice() {
+            [] if day.is_some() => None,
+            [time] => Some(time_of_day(time, None).ok_or(error)?),
+            [time, meridiem @ ("am" | "pm")] => {
+                Some(time_of_day(time, Some(meridiem)).ok_or(error)?)
+            }
+            _ => return Err(error),
+        };
+
+        let now = DateTime::<Utc>::from(reference_now).with_timezone(tz);
+        let now_naive = now.naive_local();
+        let today = now_naive.date();
+        let at = |date: NaiveDate| date.and_time(time.unwrap_or(NaiveTime::MIN));
+        let is_future = |date: NaiveDate| at(date) > now_naive;
+        let ahead = match day {
+            Some(Day::Today) => 0,
+            Some(Day::Tomorrow) => 1,
+            None if is_future(today) => 0,
+            None => 1,
+            Some(Day::Weekday(weekday)) => match days_until(today.weekday(), weekday) {
+                0 if is_future(today) => 0,
+                0 => 7,
+                ahead => ahead,
+            },
+            Some(Day::NextWeekday(weekday)) => match days_until(today.weekday(), weekday) {
+                0 => 7,
+                ahead => ahead,
+            },
+        };
+        let date = today
+            .checked_add_signed(Duration::days(ahead))
+            .ok_or(error)?;
+        resolve(tz, at(date))
+            .map(|time| TimeStamp::from(time.with_timezone(&Utc)))
+            .ok_or(error)
+    }
+}
+
+fn weekday(token: &str) -> Option<Weekday> {
+    WEEKDAYS
+        .iter()
+        .find(|(long, short, _)| token == *long || token == *short)
+        .map(|(_, _, weekday)| *weekday)
+}
+
+/// Days from `from` until the next `to`, 0 if they are the same weekday.
+fn days_until(from: Weekday, to: Weekday) -> i64 {
+    i64::from((7 + to.num_days_from_monday() - from.num_days_from_monday()) % 7)
+}
+
+/// Parses `noon`, `midnight`, `17:00`, or with a meridiem `9`/`9:30`,
+/// which may also be attached directly as in `9am`.
+fn time_of_day(token: &str, meridiem: Option<&str>) -> Option<NaiveTime> {
+    match (token, meridiem) {
+        ("noon", None) => return NaiveTime::from_hms_opt(12, 0, 0),
+        ("midnight", None) => return Some(NaiveTime::MIN),
+        _ => {}
+    }
+    let (clock, meridiem) = match meridiem {
+        Some(meridiem) => (token, Some(meridiem)),
+        None => match token
+            .strip_suffix("am")
+            .or_else(|| token.strip_suffix("pm"))
+        {
+            Some(clock) => (clock, Some(&token[clock.len()..])),
+            None => (token, None),
+        },
+    };
+    let (hour, minute) = match clock.split_once(':') {
+        Some((hour, minute)) if minute.len() == 2 => (hour.parse().ok()?, minute.parse().ok()?),
+        Some(_) => return None,
+        None if meridiem.is_some() => (clock.parse().ok()?, 0),
+        None => return None,
+    };
+    let hour = match meridiem {
+        Some(_) if !(1..=12).contains(&hour) => return None,
+        Some("am") => hour % 12,
+        Some(_) => hour % 12 + 12,
+        None => hour,
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+fn resolve<Tz: TimeZone>(tz: &Tz, local: NaiveDateTime) -> Option<DateTime<Tz>> {
+    match tz.from_local_datetime(&local) {
+        LocalResult::Single(time) => Some(time),
+        LocalResult::Ambiguous(earliest, _) => Some(earliest),
+        LocalResult::None => None,
+    }
+}
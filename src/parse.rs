@@ -0,0 +1,223 @@
+//! Backend-independent parsing of duration strings into whole seconds.
+use crate::errors::DurationParserError;
+
+const COMPACT_UNITS: [(u8, i64); 5] = [
+    (b'w', 604800),
+    (b'd', 86400),
+    (b'h', 3600),
+    (b'm', 60),
+    (b's', 1),
+];
+
+/// Parses a duration in one of three forms, each with an optional leading sign:
+/// bare seconds (`"5400"`), compact units in descending order (`"1h30m"`,
+/// `"1w 2d"`), or ISO 8601 (`"PT1H30M"`, `"P2DT3H"`).
+pub(crate) fn parse_duration(string: &str) -> Result<i64, DurationParserError> {
+    let (negative, offset) = match string.as_bytes().first() {
+        Some(b'-') => (true, 1),
+        Some(b'+') => (false, 1),
+        _ => (false, 0),
+    };
+    let body = &string[offset..];
+    let seconds = if body.starts_with('P') {
+        parse_iso8601(body, offset)?
+    } else if !body.is_empty() && body.bytes().all(|b| b.is_ascii_digit()) {
+        number(body.as_bytes(), 0, offset)?.0
+    } else {
+        parse_compact(body, offset)?
+    };
+    Ok(if negative { -seconds } else { seconds })
+}
+
+/// Parses the `Counter` display format `"H+:MM:SS"` into seconds, rejecting
+/// minutes or seconds of 60 and above.
+pub(crate) fn parse_hms(string: &str) -> Result<i64, DurationParserError> {
+    let bytes = string.as_bytes();
+    let (hours, mut pos) = number(bytes, 0, 0)?;
+    let mut total = accumulate(0, hours, 3600, 0)?;
+    for size in [60, 1] {
+        if bytes.get(pos) != Some(&b':') {
+            return Err(DurationParserError::new(pos, "expected ':'"));
+        }
+        pos += 1;
+        let digits = match bytes.get(pos..pos + 2) {
+            Some(digits) if digits.iter().all(u8::is_ascii_digit) => digits,
+            _ => return Err(DurationParserError::new(pos, "expected two digits")),
+        };
+        let value = i64::from((digits[0] - b'0') * 10 + digits[1] - b'0');
+        if value >= 60 {
+            return Err(DurationParserError::new(pos, "must be below 60"));
+        }
+        total = accumulate(total, value, size, pos)?;
+        pos += 2;
+    }
+    if pos != bytes.len() {
+        return Err(DurationParserError::new(pos, "unexpected trailing input"));
+    }
+    Ok(total)
+}
+
+/// Like `parse_hms`, but also accepts `"MM:SS"` and single-digit minutes
+/// and seconds, as typed by hand (`"1:2:3"`).
+pub(crate) fn parse_clock(string: &str) -> Result<i64, DurationParserError> {
+    let bytes = string.as_bytes();
+    let (first, mut pos) = number(bytes, 0, 0)?;
+    let mut fields = vec![(first, 0)];
+    while fields.len() < 3 && bytes.get(pos) == Some(&b':') {
+        let start = pos + 1;
+        let (value, end) = number(bytes, start, 0)?;
+        if end - start > 2 {
+            return Err(DurationParserError::new(
+                start,
+                "expected at most two digits",
+            ));
+        }
+        if value >= 60 {
+            return Err(DurationParserError::new(start, "must be below 60"));
+        }
+        fields.push((value, start));
+        pos = end;
+    }
+    if fields.len() < 2 {
+        return Err(DurationParserError::new(pos, "expected ':'"));
+    }
+    if pos != bytes.len() {
+        return Err(DurationParserError::new(pos, "unexpected trailing input"));
+    }
+    if fields.len() == 2 && first >= 60 {
+        return Err(DurationParserError::new(0, "must be below 60"));
+    }
+    let sizes = [3600, 60, 1];
+    fields
+        .iter()
+        .zip(&sizes[3 - fields.len()..])
+        .try_fold(0, |total, (&(value, position), &size)| {
+            accumulate(total, value, size, position)
+        })
+}
+
+/// Reads the digits starting at `pos`, returning the value and the position
+/// after them. `offset` is only used for error positions.
+fn number(bytes: &[u8], pos: usize, offset: usize) -> Result<(i64, usize), DurationParserError> {
+    let end = pos
+        + bytes[pos..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+    if end == pos {
+        return Err(DurationParserError::new(offset + pos, "expected a number"));
+    }
+    let mut value: i64 = 0;
+    for digit in &bytes[pos..end] {
+        value = value
+            .checked_mul(10)
+            .and_then(|v| v.checked_add(i64::from(digit - b'0')))
+            .ok_or(DurationParserError::new(
+                offset + pos,
+                "number is too large",
+            ))?;
+    }
+    Ok((value, end))
+}
+
+fn accumulate(
+    total: i64,
+    value: i64,
+    size: i64,
+    position: usize,
+) -> Result<i64, DurationParserError> {
+    value
+        .checked_mul(size)
+        .and_then(|v| v.checked_add(total))
+        .ok_or(DurationParserError::new(position, "duration is too large"))
+}
+
+fn check_order(
+    last: Option<usize>,
+    rank: usize,
+    position: usize,
+    what: &'static str,
+) -> Result<(), DurationParserError> {
+    match last {
+        Some(last) if last == rank => Err(DurationParserError::new(position, what)),
+        Some(last) if last > rank => Err(DurationParserError::new(position, "out of order")),
+        _ => Ok(()),
+    }
+}
+
+fn parse_compact(body: &str, offset: usize) -> Result<i64, DurationParserError> {
+    let bytes = body.as_bytes();
+    if bytes.is_empty() {
+        return Err(DurationParserError::new(offset, "empty duration"));
+    }
+    let (mut pos, mut total, mut last) = (0, 0, None);
+    while pos < bytes.len() {
+        if last.is_some() {
+            while bytes.get(pos) == Some(&b' ') {
+                pos += 1;
+            }
+            if pos == bytes.len() {
+                break;
+            }
+        }
+        let (value, unit_pos) = number(bytes, pos, offset)?;
+        let Some(&unit) = bytes.get(unit_pos) else {
+            return Err(DurationParserError::new(offset + unit_pos, "missing unit"));
+        };
+        let Some(rank) = COMPACT_UNITS.iter().position(|(u, _)| *u == unit) else {
+            return Err(DurationParserError::new(offset + unit_pos, "unknown unit"));
+        };
+        check_order(last, rank, offset + unit_pos, "duplicate unit")?;
+        total = accumulate(total, value, COMPACT_UNITS[rank].1, offset + pos)?;
+        last = Some(rank);
+        pos = unit_pos + 1;
+    }
+    Ok(total)
+}
+
+fn parse_iso8601(body: &str, offset: usize) -> Result<i64, DurationParserError> {
+    let bytes = body.as_bytes();
+    let (mut pos, mut total, mut last) = (1, 0, None);
+    let mut time = None;
+    while pos < bytes.len() {
+        if bytes[pos] == b'T' {
+            if time.is_some() {
+                return Err(DurationParserError::new(offset + pos, "duplicate 'T'"));
+            }
+            time = Some(pos);
+            pos += 1;
+            continue;
+        }
+        let (value, designator_pos) = number(bytes, pos, offset)?;
+        let error = |reason| DurationParserError::new(offset + designator_pos, reason);
+        let (rank, size) = match (time.is_some(), bytes.get(designator_pos)) {
+            (_, None) => return Err(error("missing designator")),
+            (false, Some(b'W')) => (0, 604800),
+            (false, Some(b'D')) => (1, 86400),
+            (true, Some(b'H')) => (2, 3600),
+            (true, Some(b'M')) => (3, 60),
+            (true, Some(b'S')) => (4, 1),
+            (false, Some(b'Y' | b'M')) => {
+                return Err(error("calendar years and months are not supported"))
+            }
+            (false, Some(b'H' | b'S')) => return Err(error("time designator before 'T'")),
+            (true, Some(b'W' | b'D')) => return Err(error("date designator after 'T'")),
+            _ => return Err(error("unknown designator")),
+        };
+        check_order(last, rank, offset + designator_pos, "duplicate designator")?;
+        total = accumulate(total, value, size, offset + pos)?;
+        last = Some(rank);
+        pos = designator_pos + 1;
+    }
+    match (last, time) {
+        (None, _) => Err(DurationParserError::new(
+            offset + pos,
+            "expected at least one component",
+        )),
+        (Some(rank), Some(_)) if rank < 2 => Err(DurationParserError::new(
+            offset + pos,
+            "expected a time component after 'T'",
+        )),
+        _ => Ok(total),
+    }
+}
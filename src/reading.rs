@@ -0,0 +1,133 @@
+//! This module provides `CounterReading`, a counter's value split into
+//! days, hours, minutes and seconds, and `FrozenCounter`, a reading kept
+//! together with the direction of the counter it was taken from.
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use crate::parse::parse_hms;
+use crate::{Direction, InvalidSnapshot};
+
+/// The magnitude of a reading, split into units, with its sign kept apart.
+/// While `days` is used, `hours` stays within `0..24`.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Default, Clone, Copy, Hash)]
+pub struct CounterReading {
+    pub days: i64,
+    pub hours: i64,
+    pub minutes: i64,
+    pub seconds: i64,
+    pub negative: bool,
+}
+
+impl CounterReading {
+    pub(crate) fn new(total_seconds: i64) -> CounterReading {
+        let rest = total_seconds.unsigned_abs();
+        CounterReading {
+            days: (rest / 86400) as i64,
+            hours: (rest / 3600 % 24) as i64,
+            minutes: (rest / 60 % 60) as i64,
+            seconds: (rest % 60) as i64,
+            negative: total_seconds < 0,
+        }
+    }
+
+    /// (hours, minutes, seconds) as shown by `Counter`, or zeros if negative
+    pub(crate) fn clamped_hms(&self) -> (i64, i64, i64) {
+        match self.negative {
+            true => (0, 0, 0),
+            false => (self.days * 24 + self.hours, self.minutes, self.seconds),
+        }
+    }
+
+    /// The reading in seconds, negative if `negative` is set
+    pub fn total_seconds(&self) -> i64 {
+        let magnitude = i128::from(self.days) * 86400
+            + i128::from(self.hours) * 3600
+            + i128::from(self.minutes) * 60
+            + i128::from(self.seconds);
+        match self.negative {
+            true => -magnitude as i64,
+            false => magnitude as i64,
+        }
+    }
+}
+
+/// Writes (hours, minutes, seconds) as "HH:MM:SS", the way every counter in
+/// this crate is shown. Hours grow past two digits instead of wrapping.
+pub(crate) fn write_hms(
+    f: &mut impl fmt::Write,
+    (hours, minutes, seconds): (i64, i64, i64),
+) -> fmt::Result {
+    write!(f, "{:0>2}:{:0>2}:{:0>2}", hours, minutes, seconds)
+}
+
+/// Displayed as "HH:MM:SS", prefixed with the number of days if there are
+/// any (e.g. "3d 04:05:06") and with "-" if negative
+impl Display for CounterReading {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.negative {
+            write!(f, "-")?;
+        }
+        if self.days > 0 {
+            write!(f, "{}d ", self.days)?;
+        }
+        write_hms(f, (self.hours, self.minutes, self.seconds))
+    }
+}
+
+/// What a counter showed at the moment `Counter::snapshot` was called. Unlike
+/// a paused counter, this never changes, and the counter it was taken from
+/// keeps running.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct FrozenCounter {
+    pub direction: Direction,
+    pub reading: CounterReading,
+}
+
+impl FrozenCounter {
+    /// Returns the tuple of (hours, minutes, seconds) shown, like `Counter::counter`
+    pub fn counter(&self) -> (i64, i64, i64) {
+        self.reading.clamped_hms()
+    }
+
+    /// Writes the snapshot as a single line that `FromStr` reads back, e.g.
+    /// "Down 00:09:59" or "Up -3d 04:05:06"
+    pub fn to_line(&self) -> String {
+        format!("{} {}", self.direction, self.reading)
+    }
+}
+
+/// Shows the frozen value as the counter showed it, e.g. "00:09:59"
+impl Display for FrozenCounter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_hms(f, self.counter())
+    }
+}
+
+/// Parses the format written by `FrozenCounter::to_line`
+impl FromStr for FrozenCounter {
+    type Err = InvalidSnapshot;
+
+    fn from_str(string: &str) -> Result<Self, InvalidSnapshot> {
+        let (direction, rest) = string.split_once(' ').ok_or(InvalidSnapshot)?;
+        let direction = direction.parse().map_err(|_| InvalidSnapshot)?;
+        let (negative, rest) = match rest.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, rest),
+        };
+        let (days, hms) = match rest.split_once("d ") {
+            Some((days, hms)) if days.bytes().all(|b| b.is_ascii_digit()) => {
+                (days.parse::<i64>().map_err(|_| InvalidSnapshot)?, hms)
+            }
+            Some(_) => return Err(InvalidSnapshot),
+            None => (0, rest),
+        };
+        let magnitude = days
+            .checked_mul(86400)
+            .and_then(|days| days.checked_add(parse_hms(hms).ok()?))
+            .ok_or(InvalidSnapshot)?;
+        Ok(FrozenCounter {
+            direction,
+            reading: CounterReading::new(if negative { -magnitude } else { magnitude }),
+        })
+    }
+}
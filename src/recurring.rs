@@ -0,0 +1,217 @@
+//! This module provides `Recurring`, a weekly or every-few-days event, and
+//! `RecurringCounter`, which counts down to its next occurrence.
+use crate::reading::{write_hms, CounterReading};
+use crate::times::Time;
+use crate::types::{Duration, TimeStamp};
+use crate::TimeUnits;
+use chrono::{self, DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Offset};
+use chrono::{TimeZone, Utc, Weekday};
+use std::fmt::{self, Display, Formatter};
+use std::fs::{self, read_to_string};
+use std::io;
+use std::path::Path;
+
+/// How `RecurringCounter::to_file` writes the first occurrence
+const FIRST_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f";
+
+/// An event at the same time of day every `days` days, in a fixed UTC offset.
+/// ```rust
+/// # use countrs::recurring::Recurring;
+/// # use countrs::types::TimeStamp;
+/// # use chrono::{NaiveTime, TimeZone, Utc, Weekday};
+/// let nine = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+/// let standup = Recurring::weekly(Weekday::Mon, nine);
+/// // From a Wednesday
+/// let wednesday = TimeStamp::from(Utc.with_ymd_and_hms(2024, 3, 6, 12, 0, 0).unwrap());
+/// let monday = TimeStamp::from(Utc.with_ymd_and_hms(2024, 3, 11, 9, 0, 0).unwrap());
+/// assert_eq!(standup.next_occurrence(wednesday), monday);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Recurring {
+    offset: FixedOffset,
+    first: NaiveDateTime,
+    days: u32,
+}
+
+impl Recurring {
+    /// Every week on `day` at `time`, in UTC, starting in the first week of
+    /// 1970
+    pub fn weekly(day: Weekday, time: NaiveTime) -> Recurring {
+        // 1970-01-05 is a Monday
+        let monday = NaiveDate::from_ymd_opt(1970, 1, 5).unwrap_or_default();
+        let date = monday + chrono::Duration::days(i64::from(day.num_days_from_monday()));
+        Recurring::every_days(7, date, time)
+    }
+
+    /// Every day at `time`, in UTC, starting on 1970-01-01
+    pub fn daily(time: NaiveTime) -> Recurring {
+        Recurring::every_days(1, NaiveDate::default(), time)
+    }
+
+    /// Every `days` days at `time`, starting on `first`, in UTC. A `days`
+    /// of zero is treated as one. There are no occurrences before `first`.
+    pub fn every_days(days: u32, first: NaiveDate, time: NaiveTime) -> Recurring {
+        Recurring {
+            offset: Utc.fix(),
+            first: first.and_time(time),
+            days: days.max(1),
+        }
+    }
+
+    /// The same event with its dates and times given in `offset`
+    pub fn in_offset(self, offset: FixedOffset) -> Recurring {
+        Recurring { offset, ..self }
+    }
+
+    /// The number of days between occurrences
+    pub fn days(&self) -> u32 {
+        self.days
+    }
+
+    pub fn weekday(&self) -> Weekday {
+        self.first.weekday()
+    }
+
+    pub fn time(&self) -> NaiveTime {
+        self.first.time()
+    }
+
+    /// The first occurrence not before `after`, so at the exact moment of an
+    /// occurrence, that occurrence is returned. Before the first occurrence,
+    /// that is the first one. At the end of chrono's range, where there is no
+    /// next occurrence, `after` is returned.
+    pub fn next_occurrence(&self, after: TimeStamp) -> TimeStamp {
+        let local = DateTime::<Utc>::from(after)
+            .with_timezone(&self.offset)
+            .naive_local();
+        let period = i64::from(self.days) * 86400;
+        let periods = (local - self.first).num_seconds().div_euclid(period).max(0);
+        let at = |periods: i64| {
+            let since = chrono::Duration::seconds(periods.checked_mul(period)?);
+            self.first.checked_add_signed(since)
+        };
+        let next = match at(periods) {
+            Some(occurrence) if occurrence < local => at(periods + 1),
+            occurrence => occurrence,
+        };
+        next.and_then(|next| self.offset.from_local_datetime(&next).single())
+            .map(|next| next.with_timezone(&Utc).into())
+            .unwrap_or(after)
+    }
+}
+
+/// Counts down to the next occurrence of a `Recurring` event, moving on to
+/// the one after as soon as it has passed. Partial seconds are dropped.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct RecurringCounter {
+    target: Recurring,
+}
+
+impl RecurringCounter {
+    /// Counts down to the next occurrence of `target`.
+    /// ```rust
+    /// # use countrs::recurring::{Recurring, RecurringCounter};
+    /// # use countrs::types::TimeStamp;
+    /// # use chrono::{NaiveTime, TimeZone, Utc, Weekday};
+    /// let nine = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+    /// let standup = RecurringCounter::new(Recurring::weekly(Weekday::Mon, nine));
+    /// // Monday morning, and just after the standup started
+    /// let before = TimeStamp::from(Utc.with_ymd_and_hms(2024, 3, 4, 8, 30, 0).unwrap());
+    /// let after = TimeStamp::from(Utc.with_ymd_and_hms(2024, 3, 4, 9, 0, 1).unwrap());
+    /// assert_eq!(standup.at(before).to_string(), "00:30:00");
+    /// assert_eq!(standup.at(after).to_string(), "6d 23:59:59");
+    /// ```
+    pub fn new(target: Recurring) -> RecurringCounter {
+        RecurringCounter { target }
+    }
+
+    pub fn target(&self) -> Recurring {
+        self.target
+    }
+
+    /// The `end` the counter shows when the clock shows `reference`
+    pub fn end_at(&self, reference: TimeStamp) -> TimeStamp {
+        self.target.next_occurrence(reference)
+    }
+
+    /// The time from `reference` until the next occurrence
+    pub fn remaining_at(&self, reference: TimeStamp) -> Duration {
+        self.end_at(reference) - reference
+    }
+
+    /// `end_at` the current time
+    pub fn end(&self) -> TimeStamp {
+        self.end_at(TimeStamp::now())
+    }
+
+    /// `remaining_at` the current time
+    pub fn remaining(&self) -> Duration {
+        self.remaining_at(TimeStamp::now())
+    }
+
+    /// Like `Counter::at`
+    pub fn at(&self, reference: TimeStamp) -> CounterReading {
+        CounterReading::new(self.remaining_at(reference).num_seconds())
+    }
+
+    /// Like `Counter::breakdown`
+    pub fn breakdown(&self) -> CounterReading {
+        self.at(TimeStamp::now())
+    }
+
+    /// Like `Counter::counter`
+    pub fn counter(&self) -> (i64, i64, i64) {
+        self.breakdown().clamped_hms()
+    }
+
+    /// Writes the first occurrence in local time, the days between
+    /// occurrences, and the offset from UTC in seconds to one line each,
+    /// e.g. "2024-03-04T09:00:00\n7\n3600".
+    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let Recurring {
+            offset,
+            first,
+            days,
+        } = self.target;
+        let first = first.format(FIRST_FORMAT);
+        let offset = offset.local_minus_utc();
+        fs::write(path, format!("{}\n{}\n{}", first, days, offset))
+    }
+
+    /// Reads a file written by `to_file`.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<RecurringCounter> {
+        let invalid = |what| io::Error::new(io::ErrorKind::InvalidData, what);
+        let contents = read_to_string(path)?;
+        let mut lines = contents.split('\n');
+        let first = lines
+            .next()
+            .and_then(|line| NaiveDateTime::parse_from_str(line, FIRST_FORMAT).ok())
+            .ok_or_else(|| invalid("File does not contain valid first occurrence data"))?;
+        let days = lines
+            .next()
+            .and_then(|line| line.parse().ok())
+            .filter(|&days: &u32| days > 0)
+            .ok_or_else(|| invalid("File does not contain valid days data"))?;
+        let offset = lines
+            .next()
+            .and_then(|line| line.parse().ok())
+            .and_then(FixedOffset::east_opt)
+            .ok_or_else(|| invalid("File does not contain valid offset data"))?;
+        if lines.next().is_some() {
+            return Err(invalid("File contains unknown counter data"));
+        }
+        let target = Recurring {
+            offset,
+            first,
+            days,
+        };
+        Ok(RecurringCounter { target })
+    }
+}
+
+/// Shows the time until the next occurrence, e.g. "06:30:00"
+impl Display for RecurringCounter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_hms(f, self.counter())
+    }
+}
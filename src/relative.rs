@@ -0,0 +1,170 @@
+//! This module provides `RelativeCounter`, a `Counter` that is driven by
+//! manual ticks instead of the clock, e.g. from a game loop.
+use crate::reading::{write_hms, CounterReading};
+use crate::times::{Time, TimeUnits};
+use crate::{Counter, Direction, TimeOverflow};
+use std::fmt::{self, Display, Formatter};
+use std::fs::{self, read_to_string};
+use std::io;
+use std::ops::Sub;
+use std::path::Path;
+use std::str::FromStr;
+
+/// A `Counter` read at a position that only moves when `tick` is called, so
+/// `Time::now` is never consulted. The position starts at the counter's
+/// `start`, and `elapsed` is the time accumulated by ticking since.
+/// ```rust
+/// # use countrs::RelativeCounter;
+/// # use countrs::unix::{Seconds, UnixSeconds};
+/// let mut round = RelativeCounter::<UnixSeconds>::down(Seconds(90)).unwrap();
+/// for _ in 0..30 {
+///     round.tick(Seconds(1)).unwrap();
+/// }
+/// assert_eq!(round.elapsed(), Seconds(30));
+/// assert_eq!(round.to_string(), "00:01:00");
+/// ```
+#[derive(PartialEq, Eq, Clone, Hash)]
+pub struct RelativeCounter<T> {
+    pub counter: Counter<T>,
+    position: T,
+}
+
+impl<T, D> RelativeCounter<T>
+where
+    T: Copy + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits,
+{
+    /// Drives `counter` by ticks, starting at its `start`.
+    pub fn new(counter: Counter<T>) -> RelativeCounter<T> {
+        RelativeCounter {
+            position: counter.start,
+            counter,
+        }
+    }
+
+    /// Counts down `duration` of ticks, on a timeline starting at `T::default()`.
+    pub fn down(duration: impl Into<D>) -> Result<RelativeCounter<T>, TimeOverflow>
+    where
+        T: Default,
+    {
+        let start = T::default();
+        let end = start.add_seconds(duration.into())?;
+        let counter = Counter::new(start, end, Direction::Down);
+        Ok(RelativeCounter::new(counter))
+    }
+
+    /// Counts ticks up from zero, on a timeline starting at `T::default()`.
+    pub fn up() -> RelativeCounter<T>
+    where
+        T: Default,
+    {
+        let start = T::default();
+        RelativeCounter::new(Counter::new(start, start, Direction::Up))
+    }
+
+    /// Advances the position by `delta`, which may be negative.
+    pub fn tick(&mut self, delta: impl Into<D>) -> Result<(), TimeOverflow> {
+        self.position = self.position.add_seconds(delta.into())?;
+        Ok(())
+    }
+
+    /// The point on the counter's timeline that readings are taken at
+    pub fn position(&self) -> T {
+        self.position
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.counter.direction
+    }
+
+    /// Like `Counter::counter`
+    pub fn counter(&self) -> (i64, i64, i64) {
+        self.breakdown().clamped_hms()
+    }
+
+    /// Like `Counter::breakdown`
+    pub fn breakdown(&self) -> CounterReading {
+        self.counter.at(self.position)
+    }
+
+    /// Like `Counter::remaining`
+    pub fn remaining(&self) -> D {
+        self.counter.remaining_at(self.position)
+    }
+
+    /// The time accumulated by `tick`, measured from the counter's `start`
+    pub fn elapsed(&self) -> D {
+        self.counter.elapsed_at(self.position)
+    }
+
+    /// Like `Counter::is_finished`
+    pub fn is_finished(&self) -> bool
+    where
+        D: PartialOrd,
+    {
+        self.counter.is_finished_at(self.position)
+    }
+
+    /// Moves the position back to the counter's `start`.
+    pub fn rewind(&mut self) {
+        self.position = self.counter.start;
+    }
+}
+
+impl<T, D> RelativeCounter<T>
+where
+    T: Copy + Display + FromStr + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits,
+{
+    /// Writes the position to the first line of a file, followed by the
+    /// counter as written by `Counter::to_file`.
+    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(
+            path,
+            format!("{}\n{}", self.position, self.counter.to_contents()),
+        )
+    }
+
+    /// Reads a file written by `to_file`.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<RelativeCounter<T>> {
+        let contents = read_to_string(path)?;
+        let (position, counter) = contents.split_once('\n').unwrap_or((&contents, ""));
+        let position = T::from_str(position).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "File does not contain valid position data",
+            )
+        })?;
+        let counter = Counter::from_contents_with(counter, |s| T::from_str(s).ok())?;
+        Ok(RelativeCounter { counter, position })
+    }
+}
+
+/// Shows the value at the current position, as "HH:MM:SS"
+impl<T, D> Display for RelativeCounter<T>
+where
+    T: Copy + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_hms(f, self.counter())
+    }
+}
+
+/// Like `Debug` for `Counter`, showing the value at the position rather
+/// than at the current time
+impl<T, D> fmt::Debug for RelativeCounter<T>
+where
+    T: fmt::Debug + Copy + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RelativeCounter")
+            .field("direction", &self.counter.direction)
+            .field("start", &self.counter.start)
+            .field("end", &self.counter.end)
+            .field("position", &self.position)
+            .field("showing", &self.to_string())
+            .finish()
+    }
+}
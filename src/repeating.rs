@@ -0,0 +1,134 @@
+//! This module provides `RepeatingCounter`, which counts down the same
+//! period over and over, e.g. for interval training or reminders.
+use crate::as_nanos;
+use crate::errors::InvalidPeriod;
+use crate::reading::{write_hms, CounterReading};
+use crate::times::{Time, TimeUnits};
+use std::fmt::{self, Display, Formatter};
+use std::fs::{self, read_to_string};
+use std::io;
+use std::ops::Sub;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Counts down `period`, restarting right away each time it reaches zero.
+/// The cycles are computed from `anchor` rather than from the last restart,
+/// so the counter stays in phase however rarely it is read. Before `anchor`,
+/// it counts down to the end of the first cycle.
+///
+/// Periods are whole seconds; sub-second parts are dropped.
+/// ```rust
+/// # use countrs::{RepeatingCounter, Time, TimeUnits};
+/// # use countrs::types::{Duration, TimeStamp};
+/// let anchor = TimeStamp::now() - 100;
+/// let intervals = RepeatingCounter::new(anchor, Duration::seconds(30)).unwrap();
+/// assert_eq!(intervals.cycle(), 3);
+/// assert_eq!(intervals.to_string(), "00:00:19");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct RepeatingCounter<T> {
+    anchor: T,
+    period: i64,
+}
+
+impl<T, D> RepeatingCounter<T>
+where
+    T: Copy + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits,
+{
+    pub fn new(anchor: T, period: impl Into<D>) -> Result<RepeatingCounter<T>, InvalidPeriod> {
+        match period.into().num_seconds() {
+            period if period > 0 => Ok(RepeatingCounter { anchor, period }),
+            _ => Err(InvalidPeriod),
+        }
+    }
+
+    /// The start of the first cycle
+    pub fn anchor(&self) -> T {
+        self.anchor
+    }
+
+    pub fn period(&self) -> D {
+        D::seconds(self.period)
+    }
+
+    /// The number of completed cycles, starting at 0
+    pub fn cycle(&self) -> u64 {
+        self.cycle_at(T::now())
+    }
+
+    /// `cycle` if the clock showed `reference`
+    pub fn cycle_at(&self, reference: T) -> u64 {
+        let since = as_nanos(&(reference - self.anchor));
+        let cycle = since.div_euclid(i128::from(self.period) * 1_000_000_000);
+        u64::try_from(cycle).unwrap_or(0)
+    }
+
+    /// The time until the current cycle ends and the next one starts
+    pub fn until_next(&self) -> D {
+        self.until_next_at(T::now())
+    }
+
+    /// `until_next` if the clock showed `reference`. Saturates at zero if the
+    /// next cycle would start past the backend's limits.
+    pub fn until_next_at(&self, reference: T) -> D {
+        let next = i128::from(self.cycle_at(reference) + 1) * i128::from(self.period);
+        i64::try_from(next)
+            .ok()
+            .and_then(|seconds| D::try_seconds(seconds).ok())
+            .and_then(|seconds| self.anchor.add_seconds(seconds).ok())
+            .map_or_else(|| D::seconds(0), |next| next - reference)
+    }
+
+    /// The value shown, like `Counter::breakdown`
+    pub fn breakdown(&self) -> CounterReading {
+        CounterReading::new(self.until_next().num_seconds())
+    }
+
+    /// Like `Counter::counter`
+    pub fn counter(&self) -> (i64, i64, i64) {
+        self.breakdown().clamped_hms()
+    }
+}
+
+impl<T, D> RepeatingCounter<T>
+where
+    T: Copy + Display + FromStr + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits,
+{
+    /// Writes `anchor` and the period in seconds to one line each.
+    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, format!("{}\n{}", self.anchor, self.period))
+    }
+
+    /// Reads a file written by `to_file`.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<RepeatingCounter<T>> {
+        let invalid = |what| io::Error::new(io::ErrorKind::InvalidData, what);
+        let contents = read_to_string(path)?;
+        let mut lines = contents.split('\n');
+        let anchor = lines
+            .next()
+            .and_then(|line| T::from_str(line).ok())
+            .ok_or_else(|| invalid("File does not contain valid anchor data"))?;
+        let period = lines
+            .next()
+            .and_then(|line| line.parse().ok())
+            .filter(|&period: &i64| period > 0)
+            .ok_or_else(|| invalid("File does not contain valid period data"))?;
+        if lines.next().is_some() {
+            return Err(invalid("File contains unknown counter data"));
+        }
+        Ok(RepeatingCounter { anchor, period })
+    }
+}
+
+/// Shows the time left in the current cycle, as "HH:MM:SS"
+impl<T, D> Display for RepeatingCounter<T>
+where
+    T: Copy + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_hms(f, self.counter())
+    }
+}
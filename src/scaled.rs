@@ -0,0 +1,158 @@
+//! This module provides `ScaledCounter`, which runs a `Counter` faster or
+//! slower than real time.
+use crate::clock::{Clock, SystemClock};
+use crate::errors::TimeOverflow;
+use crate::reading::{write_hms, CounterReading};
+use crate::times::{Time, TimeUnits};
+use crate::{as_secs_f64, Counter};
+use std::fmt::{self, Display, Formatter};
+use std::ops::Sub;
+
+/// A `Counter` on a timeline that moves `speed` times as fast as `clock`,
+/// e.g. for in-game time. The counter's times are on that scaled timeline,
+/// which matches the real one when the `ScaledCounter` is created.
+///
+/// Away from a speed of 1.0, the scaled time advances in whole seconds, and
+/// it saturates at the backend's limits, if it has any.
+/// ```rust
+/// # use countrs::{Counter, ScaledCounter, Time};
+/// # use countrs::clock::TestClock;
+/// # use countrs::types::TimeStamp;
+/// let now = TimeStamp::now();
+/// let clock = TestClock::new(now);
+/// let countdown = Counter::down(Some(now), Some(now + 600));
+/// let mut game = ScaledCounter::with_clock(countdown, 3.0, &clock);
+/// clock.advance(60).unwrap();
+/// assert_eq!(game.to_string(), "00:07:00");
+///
+/// game.set_speed(1.0);
+/// clock.advance(60).unwrap();
+/// assert_eq!(game.to_string(), "00:06:00");
+/// ```
+pub struct ScaledCounter<T, C = SystemClock> {
+    pub counter: Counter<T>,
+    clock: C,
+    speed: f64,
+    real_anchor: T,
+    scaled_anchor: T,
+}
+
+impl<T, D> ScaledCounter<T>
+where
+    T: Copy + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits,
+{
+    pub fn new(counter: Counter<T>, speed: f64) -> ScaledCounter<T> {
+        ScaledCounter::with_clock(counter, speed, SystemClock)
+    }
+}
+
+impl<T, D, C> ScaledCounter<T, C>
+where
+    T: Copy + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits,
+    C: Clock<T>,
+{
+    /// Like `new`, reading real time from `clock` instead of `Time::now`.
+    pub fn with_clock(counter: Counter<T>, speed: f64, clock: C) -> ScaledCounter<T, C> {
+        let now = clock.now();
+        ScaledCounter {
+            counter,
+            clock,
+            speed,
+            real_anchor: now,
+            scaled_anchor: now,
+        }
+    }
+
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    /// Changes the speed from now on, without changing the value shown.
+    pub fn set_speed(&mut self, speed: f64) {
+        let real = self.clock.now();
+        self.scaled_anchor = self.scaled_at(real);
+        self.real_anchor = real;
+        self.speed = speed;
+    }
+
+    /// The current time on the scaled timeline, saturating at the backend's
+    /// limits, see `try_now`
+    pub fn now(&self) -> T {
+        self.scaled_at(self.clock.now())
+    }
+
+    /// Like `now`, but fails with `TimeOverflow` instead of saturating once
+    /// the scaled time leaves the range of the backend, or when `speed` is not
+    /// a finite number.
+    pub fn try_now(&self) -> Result<T, TimeOverflow> {
+        self.try_scaled_at(self.clock.now())
+    }
+
+    /// Like `Counter::counter`
+    pub fn counter(&self) -> (i64, i64, i64) {
+        self.breakdown().clamped_hms()
+    }
+
+    /// Like `Counter::breakdown`
+    pub fn breakdown(&self) -> CounterReading {
+        self.counter.at(self.now())
+    }
+
+    /// Like `Counter::remaining`
+    pub fn remaining(&self) -> D {
+        self.counter.remaining_at(self.now())
+    }
+
+    /// Like `Counter::elapsed`
+    pub fn elapsed(&self) -> D {
+        self.counter.elapsed_at(self.now())
+    }
+
+    /// Like `Counter::is_finished`
+    pub fn is_finished(&self) -> bool
+    where
+        D: PartialOrd,
+    {
+        self.counter.is_finished_at(self.now())
+    }
+
+    fn scaled_at(&self, real: T) -> T {
+        let passed = real - self.real_anchor;
+        let forward = as_secs_f64(&passed) * self.speed >= 0.0;
+        self.try_scaled_at(real)
+            .ok()
+            .or_else(|| match forward {
+                true => T::max_value(),
+                false => T::min_value(),
+            })
+            .unwrap_or(self.scaled_anchor)
+    }
+
+    fn try_scaled_at(&self, real: T) -> Result<T, TimeOverflow> {
+        let passed = real - self.real_anchor;
+        if self.speed == 1.0 {
+            return self.scaled_anchor.add_seconds(passed);
+        }
+        let seconds = (as_secs_f64(&passed) * self.speed).floor();
+        // Also rules out NaN, which `as` would turn into zero
+        if !(i64::MIN as f64..i64::MAX as f64).contains(&seconds) {
+            return Err(TimeOverflow);
+        }
+        self.scaled_anchor
+            .add_seconds(D::try_seconds(seconds as i64)?)
+    }
+}
+
+/// Shows the value at the current scaled time, as "HH:MM:SS"
+impl<T, D, C> Display for ScaledCounter<T, C>
+where
+    T: Copy + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits,
+    C: Clock<T>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_hms(f, self.counter())
+    }
+}
@@ -0,0 +1,232 @@
+//! This module provides `Sequence`, which counts down a list of phases one
+//! after the other, e.g. for pomodoros.
+use crate::as_nanos;
+use crate::errors::{InvalidPhase, TimeOverflow};
+use crate::reading::{write_hms, CounterReading};
+use crate::set::{escape, unescape};
+use crate::times::{Time, TimeUnits};
+use std::fmt::{self, Display, Formatter};
+use std::fs::{self, read_to_string};
+use std::io;
+use std::ops::Sub;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Counts down labelled phases in order, moving on to the next phase as soon
+/// as one ends. Which phase is current is worked out from the time passed
+/// since `start`, so nothing needs to poll the sequence for it to advance.
+///
+/// Phases are whole seconds; sub-second parts are dropped.
+/// ```rust
+/// # use countrs::{Sequence, Time};
+/// # use countrs::types::TimeStamp;
+/// let pomodoro = Sequence::pomodoro(TimeStamp::now() - 26 * 60);
+/// assert_eq!(pomodoro.phase_index(), 1);
+/// let (label, reading) = pomodoro.current();
+/// assert_eq!((label, reading.to_string()), ("break", "00:03:59".to_string()));
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Sequence<T> {
+    start: T,
+    phases: Vec<(String, i64)>,
+}
+
+impl<T, D> Sequence<T>
+where
+    T: Copy + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits,
+{
+    /// A sequence without phases, starting at `start`. Add phases with `phase`.
+    pub fn new(start: T) -> Sequence<T> {
+        Sequence {
+            start,
+            phases: Vec::new(),
+        }
+    }
+
+    /// Four rounds of 25 minutes "work" and 5 minutes "break", with the last
+    /// break replaced by a "long break" of 15 minutes.
+    pub fn pomodoro(start: T) -> Sequence<T> {
+        let mut sequence = Sequence::new(start);
+        for round in 1..=4 {
+            sequence.phases.push(("work".into(), 25 * 60));
+            sequence.phases.push(match round {
+                4 => ("long break".into(), 15 * 60),
+                _ => ("break".into(), 5 * 60),
+            });
+        }
+        sequence
+    }
+
+    /// Appends a phase lasting `duration`, which must not be negative.
+    pub fn phase(
+        mut self,
+        label: impl Into<String>,
+        duration: impl Into<D>,
+    ) -> Result<Sequence<T>, InvalidPhase> {
+        let seconds = duration.into().num_seconds();
+        if seconds < 0 {
+            return Err(InvalidPhase);
+        }
+        self.phases.push((label.into(), seconds));
+        Ok(self)
+    }
+
+    pub fn start(&self) -> T {
+        self.start
+    }
+
+    /// The labels and lengths of all phases, in order
+    pub fn phases(&self) -> impl Iterator<Item = (&str, D)> {
+        self.phases
+            .iter()
+            .map(|(label, seconds)| (label.as_str(), D::seconds(*seconds)))
+    }
+
+    /// Which phase is running, or the number of phases once `is_complete`.
+    /// Before `start`, this is the first phase that is not empty.
+    pub fn phase_index(&self) -> usize {
+        self.phase_index_at(T::now())
+    }
+
+    /// `phase_index` if the clock showed `reference`
+    pub fn phase_index_at(&self, reference: T) -> usize {
+        let since = as_nanos(&(reference - self.start)).max(0);
+        let mut end = 0;
+        self.phases
+            .iter()
+            .position(|(_, seconds)| {
+                end += i128::from(*seconds) * 1_000_000_000;
+                since < end
+            })
+            .unwrap_or(self.phases.len())
+    }
+
+    /// The label of the current phase and the time left in it. Once the
+    /// sequence is complete, this is the last phase, showing zero.
+    pub fn current(&self) -> (&str, CounterReading) {
+        self.current_at(T::now())
+    }
+
+    /// `current` if the clock showed `reference`
+    pub fn current_at(&self, reference: T) -> (&str, CounterReading) {
+        let index = self.phase_index_at(reference);
+        let label = self
+            .phases
+            .get(index)
+            .or(self.phases.last())
+            .map_or("", |(label, _)| label.as_str());
+        let left = match index < self.phases.len() {
+            true => self.until_end_of(index, reference).num_seconds(),
+            false => 0,
+        };
+        (label, CounterReading::new(left))
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.phase_index() == self.phases.len()
+    }
+
+    /// The time until the last phase ends, negative once past it, like
+    /// `Counter::remaining`
+    pub fn remaining(&self) -> D {
+        self.remaining_at(T::now())
+    }
+
+    /// `remaining` if the clock showed `reference`
+    pub fn remaining_at(&self, reference: T) -> D {
+        self.until_end_of(self.phases.len().saturating_sub(1), reference)
+    }
+
+    /// Ends the current phase now, so the next one starts right away.
+    /// Does nothing once the sequence is complete.
+    pub fn skip(&mut self) {
+        let now = T::now();
+        let index = self.phase_index_at(now);
+        let Some(ends) = self.ends_before(index) else {
+            return;
+        };
+        if let Some((_, seconds)) = self.phases.get_mut(index) {
+            let into_phase = (now - self.start).num_seconds() - ends;
+            *seconds = into_phase.max(0);
+        }
+    }
+
+    /// Makes the current phase last `duration` longer, moving all later
+    /// phases back. A negative `duration` shortens the phase, but not below
+    /// zero. Does nothing once the sequence is complete.
+    pub fn extend_current(&mut self, duration: impl Into<D>) -> Result<(), TimeOverflow> {
+        let index = self.phase_index();
+        if let Some((_, seconds)) = self.phases.get_mut(index) {
+            *seconds = seconds
+                .checked_add(duration.into().num_seconds())
+                .ok_or(TimeOverflow)?
+                .max(0);
+        }
+        Ok(())
+    }
+
+    /// The seconds from `start` until phase `index` starts
+    fn ends_before(&self, index: usize) -> Option<i64> {
+        self.phases[..index.min(self.phases.len())]
+            .iter()
+            .try_fold(0_i64, |total, (_, seconds)| total.checked_add(*seconds))
+    }
+
+    /// The time from `reference` until phase `index` ends, or zero if that
+    /// is past the backend's limits
+    fn until_end_of(&self, index: usize, reference: T) -> D {
+        self.ends_before(index + 1)
+            .and_then(|seconds| D::try_seconds(seconds).ok())
+            .and_then(|seconds| self.start.add_seconds(seconds).ok())
+            .map_or_else(|| D::seconds(0), |end| end - reference)
+    }
+}
+
+impl<T, D> Sequence<T>
+where
+    T: Copy + Display + FromStr + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits,
+{
+    /// Writes `start` on the first line, then one line per phase with its
+    /// length in seconds and its label, separated by a space. Labels escape
+    /// backslashes and line breaks like `CounterSet::to_file`.
+    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut contents = self.start.to_string();
+        for (label, seconds) in &self.phases {
+            contents.push_str(&format!("\n{} {}", seconds, escape(label)));
+        }
+        fs::write(path, contents)
+    }
+
+    /// Reads a file written by `to_file`.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Sequence<T>> {
+        let invalid = |what| io::Error::new(io::ErrorKind::InvalidData, what);
+        let contents = read_to_string(path)?;
+        let mut lines = contents.split('\n');
+        let start = lines
+            .next()
+            .and_then(|line| T::from_str(line).ok())
+            .ok_or_else(|| invalid("File does not contain valid start data"))?;
+        let phases = lines
+            .map(|line| {
+                let (seconds, label) = line.split_once(' ')?;
+                let seconds = seconds.parse::<i64>().ok().filter(|s| *s >= 0)?;
+                Some((unescape(label)?, seconds))
+            })
+            .collect::<Option<_>>()
+            .ok_or_else(|| invalid("File does not contain valid phase data"))?;
+        Ok(Sequence { start, phases })
+    }
+}
+
+/// Shows the time left in the current phase, as "HH:MM:SS"
+impl<T, D> Display for Sequence<T>
+where
+    T: Copy + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_hms(f, self.current().1.clamped_hms())
+    }
+}
@@ -0,0 +1,248 @@
+//! `Serialize` and `Deserialize` for `Counter` and `CounterSet`, behind the
+//! `serde` feature. Both are maps with a `version` key, so the format can
+//! change later without misreading older data. Unknown and duplicate keys are
+//! rejected, including duplicate counter names in a set.
+//!
+//! A counter is written with all of its fields, leaving out `paused_at`,
+//! `created_at` and `modified_at` while they are unset:
+//! ```json
+//! {"version":1,"start":0,"end":600,"direction":"Down","initial_start":0,
+//!  "initial_end":600,"initial_direction":"Down","laps":[],"snoozes":0,
+//!  "stop_at_end":false,"auto_flip":false,"rounding":"Floor"}
+//! ```
+//! Only `version`, `start`, `end` and `direction` are required when reading;
+//! the `initial_*` fields default to the current ones. A set maps each name,
+//! in insertion order, to its counter and metadata:
+//! ```json
+//! {"version":1,"counters":{"tea":{"counter":{...},"tags":["kitchen"]}}}
+//! ```
+use crate::set::{CounterSet, Metadata};
+use crate::{Audit, Counter, Direction, Rounding};
+use serde::de::{self, Deserializer, MapAccess, Visitor};
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Formatter};
+use std::marker::PhantomData;
+
+/// The format version written by this crate, and the only one it reads
+const VERSION: u32 = 1;
+
+fn check_version<E: de::Error>(version: u32) -> Result<(), E> {
+    match version {
+        VERSION => Ok(()),
+        _ => Err(E::custom(format_args!(
+            "unsupported version {}, expected {}",
+            version, VERSION
+        ))),
+    }
+}
+
+#[derive(Serialize)]
+struct CounterRef<'a, T> {
+    version: u32,
+    start: &'a T,
+    end: &'a T,
+    direction: Direction,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    paused_at: Option<&'a T>,
+    initial_start: &'a T,
+    initial_end: &'a T,
+    initial_direction: Direction,
+    laps: &'a [T],
+    snoozes: u32,
+    stop_at_end: bool,
+    auto_flip: bool,
+    rounding: Rounding,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    created_at: Option<&'a T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    modified_at: Option<&'a T>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CounterFields<T> {
+    version: u32,
+    start: T,
+    end: T,
+    direction: Direction,
+    paused_at: Option<T>,
+    initial_start: Option<T>,
+    initial_end: Option<T>,
+    initial_direction: Option<Direction>,
+    #[serde(default = "Vec::new")]
+    laps: Vec<T>,
+    #[serde(default)]
+    snoozes: u32,
+    #[serde(default)]
+    stop_at_end: bool,
+    #[serde(default)]
+    auto_flip: bool,
+    #[serde(default = "floor")]
+    rounding: Rounding,
+    created_at: Option<T>,
+    modified_at: Option<T>,
+}
+
+fn floor() -> Rounding {
+    Rounding::Floor
+}
+
+impl<T: Serialize> Serialize for Counter<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        CounterRef {
+            version: VERSION,
+            start: &self.start,
+            end: &self.end,
+            direction: self.direction,
+            paused_at: self.paused_at.as_ref(),
+            initial_start: &self.initial_start,
+            initial_end: &self.initial_end,
+            initial_direction: self.initial_direction,
+            laps: &self.laps,
+            snoozes: self.snoozes,
+            stop_at_end: self.stop_at_end,
+            auto_flip: self.auto_flip,
+            rounding: self.rounding,
+            created_at: self.audit.created_at.as_ref(),
+            modified_at: self.audit.modified_at.as_ref(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T: Copy + Deserialize<'de>> Deserialize<'de> for Counter<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let fields = CounterFields::<T>::deserialize(deserializer)?;
+        check_version(fields.version)?;
+        Ok(Counter {
+            start: fields.start,
+            end: fields.end,
+            direction: fields.direction,
+            paused_at: fields.paused_at,
+            initial_start: fields.initial_start.unwrap_or(fields.start),
+            initial_end: fields.initial_end.unwrap_or(fields.end),
+            initial_direction: fields.initial_direction.unwrap_or(fields.direction),
+            laps: fields.laps,
+            snoozes: fields.snoozes,
+            stop_at_end: fields.stop_at_end,
+            auto_flip: fields.auto_flip,
+            rounding: fields.rounding,
+            audit: Audit {
+                created_at: fields.created_at,
+                modified_at: fields.modified_at,
+            },
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct SetRef<'a, T> {
+    version: u32,
+    counters: Entries<'a, T>,
+}
+
+/// The counters of a set, as a map from name to `EntryRef`
+struct Entries<'a, T>(&'a CounterSet<T>);
+
+#[derive(Serialize)]
+struct EntryRef<'a, T> {
+    counter: &'a Counter<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<&'a str>,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    tags: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    note: Option<&'a str>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, bound(deserialize = "T: Copy + Deserialize<'de>"))]
+struct SetFields<T> {
+    version: u32,
+    counters: NamedCounters<T>,
+}
+
+/// The counters of a set, read from a map that must not repeat a name
+struct NamedCounters<T>(CounterSet<T>);
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, bound(deserialize = "T: Copy + Deserialize<'de>"))]
+struct EntryFields<T> {
+    counter: Counter<T>,
+    label: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    note: Option<String>,
+}
+
+impl<'a, T: Serialize> Serialize for Entries<'a, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (name, counter, metadata) in self.0.iter_with_metadata() {
+            let Metadata { label, tags, note } = metadata;
+            let entry = EntryRef {
+                counter,
+                label: label.as_deref(),
+                tags,
+                note: note.as_deref(),
+            };
+            map.serialize_entry(name, &entry)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de, T: Copy + Deserialize<'de>> Deserialize<'de> for NamedCounters<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct NamedVisitor<T>(PhantomData<T>);
+
+        impl<'de, T: Copy + Deserialize<'de>> Visitor<'de> for NamedVisitor<T> {
+            type Value = NamedCounters<T>;
+
+            fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                write!(f, "a map from counter names to counters")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut set = CounterSet::new();
+                while let Some(name) = map.next_key::<String>()? {
+                    if set.get(&name).is_some() {
+                        return Err(de::Error::custom(format_args!(
+                            "duplicate counter name `{}`",
+                            name
+                        )));
+                    }
+                    let entry: EntryFields<T> = map.next_value()?;
+                    let metadata = Metadata {
+                        label: entry.label,
+                        tags: entry.tags,
+                        note: entry.note,
+                    };
+                    set.insert_with(name, entry.counter, metadata);
+                }
+                Ok(NamedCounters(set))
+            }
+        }
+
+        deserializer.deserialize_map(NamedVisitor(PhantomData))
+    }
+}
+
+impl<T: Serialize> Serialize for CounterSet<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SetRef {
+            version: VERSION,
+            counters: Entries(self),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T: Copy + Deserialize<'de>> Deserialize<'de> for CounterSet<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let fields = SetFields::<T>::deserialize(deserializer)?;
+        check_version(fields.version)?;
+        Ok(fields.counters.0)
+    }
+}
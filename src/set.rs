@@ -0,0 +1,768 @@
+//! This module provides `CounterSet`, a collection of named counters.
+use crate::errors::{InvalidMove, PersistError, TimeOverflow};
+use crate::reading::{write_hms, CounterReading};
+use crate::times::{Time, TimeUnits};
+use crate::{checked_sum, is_positive, Counter, CounterState};
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::fs::{self, read_to_string};
+use std::hash::Hash;
+use std::io;
+use std::ops::Sub;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Counters by name, kept in the order they were first inserted. Inserting
+/// under a name that is already taken replaces that counter in place.
+/// ```rust
+/// # use countrs::{Counter, CounterSet, Time};
+/// # use countrs::types::TimeStamp;
+/// let now = TimeStamp::now();
+/// let mut timers = CounterSet::new();
+/// timers.insert("pasta", Counter::down(None, Some(now + 541)));
+/// timers.insert("sauce", Counter::up(Some(now - 300), None));
+/// assert_eq!(timers.to_string(), "pasta: 00:09:00\nsauce: 00:05:00");
+/// ```
+#[derive(PartialEq, Eq, Clone, Hash)]
+pub struct CounterSet<T> {
+    entries: Vec<Entry<T>>,
+    #[cfg(feature = "tokio")]
+    pub(crate) changes: crate::expiry::Changes,
+}
+
+/// The files `CounterSet::load_dir` skipped, with the reason for each
+pub type LoadErrors = Vec<(PathBuf, PersistError)>;
+
+/// Descriptive data kept alongside a counter in a `CounterSet`
+#[derive(Debug, PartialEq, Eq, Default, Clone, Hash)]
+pub struct Metadata {
+    /// A human-readable name, e.g. for display instead of the set's key
+    pub label: Option<String>,
+    pub tags: Vec<String>,
+    pub note: Option<String>,
+}
+
+impl Metadata {
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|own| own == tag)
+    }
+}
+
+/// What `CounterSet::iter_sorted` orders by. Ties keep insertion order.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum SortKey {
+    Name,
+    /// Like `Counter::cmp_remaining`, most urgent first
+    Remaining,
+    /// Earliest `end` first
+    End,
+}
+
+#[derive(PartialEq, Eq, Clone, Hash)]
+struct Entry<T> {
+    name: String,
+    counter: Counter<T>,
+    metadata: Metadata,
+}
+
+impl<T> CounterSet<T> {
+    pub fn new() -> CounterSet<T> {
+        CounterSet {
+            entries: Vec::new(),
+            #[cfg(feature = "tokio")]
+            changes: crate::expiry::Changes::default(),
+        }
+    }
+
+    /// Adds `counter` under `name`, returning the counter it replaces, if any.
+    /// A replaced counter keeps its position and its metadata.
+    pub fn insert(&mut self, name: impl Into<String>, counter: Counter<T>) -> Option<Counter<T>> {
+        let name = name.into();
+        match self.get_mut(&name) {
+            Some(existing) => Some(std::mem::replace(existing, counter)),
+            None => {
+                self.changed();
+                self.entries.push(Entry {
+                    name,
+                    counter,
+                    metadata: Metadata::default(),
+                });
+                None
+            }
+        }
+    }
+
+    /// Like `insert`, but also replaces the metadata.
+    pub fn insert_with(
+        &mut self,
+        name: impl Into<String>,
+        counter: Counter<T>,
+        metadata: Metadata,
+    ) -> Option<Counter<T>> {
+        let name = name.into();
+        let replaced = self.insert(name.as_str(), counter);
+        if let Some(existing) = self.metadata_mut(&name) {
+            *existing = metadata;
+        }
+        replaced
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Counter<T>> {
+        self.entry(name).map(|entry| &entry.counter)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Counter<T>> {
+        self.entry_mut(name).map(|entry| &mut entry.counter)
+    }
+
+    pub fn metadata(&self, name: &str) -> Option<&Metadata> {
+        self.entry(name).map(|entry| &entry.metadata)
+    }
+
+    pub fn metadata_mut(&mut self, name: &str) -> Option<&mut Metadata> {
+        self.entry_mut(name).map(|entry| &mut entry.metadata)
+    }
+
+    /// Removes the counter under `name`, along with its metadata, keeping the
+    /// order of the others.
+    pub fn remove(&mut self, name: &str) -> Option<Counter<T>> {
+        let index = self.entries.iter().position(|entry| entry.name == name)?;
+        self.changed();
+        Some(self.entries.remove(index).counter)
+    }
+
+    /// The names and counters, in insertion order
+    pub fn iter(&self) -> SetIter<'_, T> {
+        SetIter {
+            entries: self.entries.iter(),
+        }
+    }
+
+    /// Like `iter`, along with each counter's metadata
+    #[cfg(feature = "serde")]
+    pub(crate) fn iter_with_metadata(
+        &self,
+    ) -> impl Iterator<Item = (&str, &Counter<T>, &Metadata)> {
+        self.entries
+            .iter()
+            .map(|entry| (entry.name.as_str(), &entry.counter, &entry.metadata))
+    }
+
+    /// Like `iter`, with mutable counters
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&str, &mut Counter<T>)> {
+        self.changed();
+        self.entries
+            .iter_mut()
+            .map(|entry| (entry.name.as_str(), &mut entry.counter))
+    }
+
+    /// The names and counters whose metadata has `tag`, in insertion order
+    pub fn filter_by_tag(&self, tag: &str) -> Vec<(&str, &Counter<T>)> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.metadata.has_tag(tag))
+            .map(|entry| (entry.name.as_str(), &entry.counter))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn entry(&self, name: &str) -> Option<&Entry<T>> {
+        self.entries.iter().find(|entry| entry.name == name)
+    }
+
+    fn entry_mut(&mut self, name: &str) -> Option<&mut Entry<T>> {
+        self.changed();
+        self.entries.iter_mut().find(|entry| entry.name == name)
+    }
+
+    /// Wakes the `expiry_stream`s of the set, if any, to recompute which
+    /// counter finishes next. Called before handing out mutable access, so the
+    /// streams see the change once the caller releases the set's lock.
+    fn changed(&self) {
+        #[cfg(feature = "tokio")]
+        self.changes.notify();
+    }
+}
+
+/// Totals over the whole set, all measured from a single reading of the clock.
+/// They fail with `TimeOverflow` once they leave the range of the backend.
+/// ```rust
+/// # use countrs::{Counter, CounterSet, Time, TimeUnits};
+/// # use countrs::types::TimeStamp;
+/// let now = TimeStamp::now();
+/// let mut week = CounterSet::new();
+/// week.insert("work: emails", Counter::up(Some(now - 1800), None));
+/// week.insert("work: review", Counter::up(Some(now - 5400), None));
+/// week.insert("lunch", Counter::up(Some(now - 2700), None));
+///
+/// let total = week.total_elapsed().unwrap();
+/// let hms = (total.num_hours(), total.num_minutes() % 60, total.num_seconds() % 60);
+/// assert_eq!(format!("{:02}:{:02}:{:02}", hms.0, hms.1, hms.2), "02:45:00");
+///
+/// let totals = week.totals_by(|name| name.starts_with("work")).unwrap();
+/// assert_eq!(totals[&true].num_seconds(), 7200);
+/// ```
+impl<T, D> CounterSet<T>
+where
+    T: Copy + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits,
+{
+    /// The `elapsed` times of all counters added up, like `Counter::sum_elapsed`.
+    /// Counters that have not started yet add nothing.
+    pub fn total_elapsed(&self) -> Result<D, TimeOverflow> {
+        let now = T::now();
+        checked_sum(
+            now,
+            self.iter().map(|(_, counter)| elapsed_so_far(counter, now)),
+        )
+    }
+
+    /// The time left until each counter's `completion_time`, added up.
+    /// Finished and open-ended counters add nothing.
+    pub fn total_remaining(&self) -> Result<D, TimeOverflow>
+    where
+        D: PartialOrd,
+    {
+        let now = T::now();
+        let remaining = self.iter().filter_map(|(_, counter)| {
+            counter.completion_time()?;
+            (!counter.is_finished_at(now)).then(|| counter.remaining_at(now))
+        });
+        checked_sum(now, remaining)
+    }
+
+    /// Like `total_elapsed`, but grouped by the `key` each counter's name maps to.
+    pub fn totals_by<K, F>(&self, key: F) -> Result<HashMap<K, D>, TimeOverflow>
+    where
+        K: Eq + Hash,
+        F: Fn(&str) -> K,
+    {
+        let now = T::now();
+        let mut totals = HashMap::new();
+        for (name, counter) in self.iter() {
+            let total = totals.entry(key(name)).or_insert(now);
+            *total = total.add_seconds(elapsed_so_far(counter, now))?;
+        }
+        Ok(totals
+            .into_iter()
+            .map(|(key, total)| (key, total - now))
+            .collect())
+    }
+}
+
+/// `Counter::elapsed_at`, or zero for a counter that has not started yet
+fn elapsed_so_far<T, D>(counter: &Counter<T>, now: T) -> D
+where
+    T: Copy + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits,
+{
+    let elapsed = counter.elapsed_at(now);
+    match is_positive(&elapsed) {
+        true => elapsed,
+        false => D::seconds(0),
+    }
+}
+
+/// Changes to every counter at once
+impl<T, D> CounterSet<T>
+where
+    T: Copy + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits + PartialOrd + Copy,
+{
+    /// Moves the `end` of every counter that has a `completion_time` by
+    /// `amount`, like `Counter::try_move_end_strict`, e.g. to push a whole
+    /// plan back. Open-ended counters are left alone. If any counter would
+    /// overflow or end before it starts, none of them is moved, and the error
+    /// names the first such counter in insertion order.
+    pub fn try_move_all_ends(&mut self, amount: impl Into<D>) -> Result<(), InvalidMove> {
+        let amount = amount.into();
+        let mut moved = Vec::with_capacity(self.entries.len());
+        for entry in &self.entries {
+            let mut counter = entry.counter.clone();
+            if counter.completion_time().is_some() {
+                counter
+                    .try_move_end_strict(amount)
+                    .map_err(|violation| InvalidMove::new(entry.name.clone(), violation))?;
+            }
+            moved.push(counter);
+        }
+        self.changed();
+        for (entry, counter) in self.entries.iter_mut().zip(moved) {
+            entry.counter = counter;
+        }
+        Ok(())
+    }
+
+    /// Calls `Counter::flip` on every counter
+    pub fn flip_all(&mut self) {
+        self.changed();
+        for entry in &mut self.entries {
+            entry.counter.flip();
+        }
+    }
+
+    /// The counters that have started and are not finished yet, in insertion
+    /// order. Use `SetEntries::sorted` to order them; the clock is only read
+    /// once for both.
+    pub fn running(&self) -> SetEntries<'_, T> {
+        let now = T::now();
+        let running = self
+            .iter()
+            .filter(|(_, counter)| counter.state_at(now) == CounterState::Running);
+        SetEntries::new(running, now)
+    }
+
+    /// The counters that are finished, in insertion order, like `running`
+    pub fn finished(&self) -> SetEntries<'_, T> {
+        let now = T::now();
+        let finished = self
+            .iter()
+            .filter(|(_, counter)| counter.is_finished_at(now));
+        SetEntries::new(finished, now)
+    }
+
+    /// Removes every counter that `is_finished`, along with its metadata,
+    /// keeping the order of the others. Counters that have not started yet
+    /// are kept.
+    pub fn retain_running(&mut self) {
+        let now = T::now();
+        self.changed();
+        self.entries
+            .retain(|entry| !entry.counter.is_finished_at(now));
+    }
+}
+
+/// The names and counters of a `CounterSet`, returned by `CounterSet::iter`
+pub struct SetIter<'a, T> {
+    entries: std::slice::Iter<'a, Entry<T>>,
+}
+
+impl<'a, T> Iterator for SetIter<'a, T> {
+    type Item = (&'a str, &'a Counter<T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.entries.next()?;
+        Some((entry.name.as_str(), &entry.counter))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a CounterSet<T> {
+    type Item = (&'a str, &'a Counter<T>);
+    type IntoIter = SetIter<'a, T>;
+
+    fn into_iter(self) -> SetIter<'a, T> {
+        self.iter()
+    }
+}
+
+/// The names and counters of a `CounterSet`, moved out in insertion order
+pub struct SetIntoIter<T> {
+    entries: std::vec::IntoIter<Entry<T>>,
+}
+
+impl<T> Iterator for SetIntoIter<T> {
+    type Item = (String, Counter<T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.entries.next()?;
+        Some((entry.name, entry.counter))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+/// Drops the metadata
+impl<T> IntoIterator for CounterSet<T> {
+    type Item = (String, Counter<T>);
+    type IntoIter = SetIntoIter<T>;
+
+    fn into_iter(self) -> SetIntoIter<T> {
+        SetIntoIter {
+            entries: self.entries.into_iter(),
+        }
+    }
+}
+
+/// Some of the names and counters of a `CounterSet`, together with the
+/// reading of the clock they were selected by, returned by
+/// `CounterSet::running`, `CounterSet::finished` and `CounterSet::iter_sorted`
+pub struct SetEntries<'a, T> {
+    entries: std::vec::IntoIter<(&'a str, &'a Counter<T>)>,
+    now: T,
+}
+
+impl<'a, T> SetEntries<'a, T> {
+    fn new(entries: impl Iterator<Item = (&'a str, &'a Counter<T>)>, now: T) -> Self {
+        SetEntries {
+            entries: entries.collect::<Vec<_>>().into_iter(),
+            now,
+        }
+    }
+}
+
+impl<'a, T, D> SetEntries<'a, T>
+where
+    T: Copy + Ord + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits + Ord,
+{
+    /// The remaining entries ordered `by` the given key, compared against
+    /// the same reading of the clock they were selected by
+    pub fn sorted(self, by: SortKey) -> SetEntries<'a, T> {
+        let now = self.now;
+        let mut entries: Vec<_> = self.entries.collect();
+        match by {
+            SortKey::Name => entries.sort_by_key(|(name, _)| *name),
+            SortKey::Remaining => {
+                entries.sort_by(|(_, left), (_, right)| left.cmp_remaining_at(right, now))
+            }
+            SortKey::End => entries.sort_by_key(|(_, counter)| counter.end),
+        }
+        SetEntries {
+            entries: entries.into_iter(),
+            now,
+        }
+    }
+}
+
+impl<'a, T> Iterator for SetEntries<'a, T> {
+    type Item = (&'a str, &'a Counter<T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<T> Default for CounterSet<T> {
+    fn default() -> Self {
+        CounterSet::new()
+    }
+}
+
+/// Queries by time left, all measured from a single reading of the clock, and
+/// ordered by `Counter::cmp_remaining`: counters past their `end` come first,
+/// most overdue first, and open-ended counters counting up come last.
+impl<T, D> CounterSet<T>
+where
+    T: Copy + Ord + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits + Ord,
+{
+    /// The counter with the least time left, including those already past
+    /// their `end`. Open-ended counters never expire, so they are left out.
+    pub fn next_to_expire(&self) -> Option<(&str, &Counter<T>)> {
+        let now = T::now();
+        self.iter()
+            .filter(|(_, counter)| counter.completion_time().is_some())
+            .min_by(|(_, left), (_, right)| left.cmp_remaining_at(right, now))
+    }
+
+    /// The running counter that finishes first, e.g. the timer that fires
+    /// next. Unlike `next_to_expire`, counters that are finished or have not
+    /// started yet are left out, as are open-ended ones.
+    pub fn soonest(&self) -> Option<(&str, &Counter<T>)> {
+        self.soonest_at(T::now())
+    }
+
+    /// The names of the counters that are finished, in insertion order
+    pub fn expired(&self) -> Vec<&str> {
+        let now = T::now();
+        self.iter()
+            .filter(|(_, counter)| counter.is_finished_at(now))
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    /// The time until the `soonest` counter finishes, e.g. for how long to
+    /// sleep before checking the set again
+    pub fn next_event_in(&self) -> Option<D> {
+        let now = T::now();
+        self.soonest_at(now)
+            .map(|(_, counter)| counter.remaining_at(now))
+    }
+
+    fn soonest_at(&self, now: T) -> Option<(&str, &Counter<T>)> {
+        self.iter()
+            .filter(|(_, counter)| {
+                counter.completion_time().is_some()
+                    && counter.state_at(now) == CounterState::Running
+            })
+            .min_by(|(_, left), (_, right)| left.cmp_remaining_at(right, now))
+    }
+
+    /// The names and counters ordered `by` the given key, all compared
+    /// against a single reading of the clock
+    pub fn iter_sorted(&self, by: SortKey) -> SetEntries<'_, T> {
+        SetEntries::new(self.iter(), T::now()).sorted(by)
+    }
+
+    /// What each counter shows, most urgent first
+    pub fn sorted_by_remaining(&self) -> Vec<(&str, CounterReading)> {
+        let now = T::now();
+        let mut counters: Vec<_> = self.iter().collect();
+        counters.sort_by(|(_, left), (_, right)| left.cmp_remaining_at(right, now));
+        counters
+            .into_iter()
+            .map(|(name, counter)| (name, counter.at(now)))
+            .collect()
+    }
+
+    /// The names of the counters with at most `duration` left, most urgent
+    /// first, including those already past their `end`
+    pub fn expiring_within(&self, duration: impl Into<D>) -> Vec<&str> {
+        let (now, duration) = (T::now(), duration.into());
+        let mut counters: Vec<_> = self
+            .iter()
+            .filter(|(_, counter)| {
+                counter.completion_time().is_some() && counter.remaining_at(now) <= duration
+            })
+            .collect();
+        counters.sort_by(|(_, left), (_, right)| left.cmp_remaining_at(right, now));
+        counters.into_iter().map(|(name, _)| name).collect()
+    }
+}
+
+impl<T, D> CounterSet<T>
+where
+    T: Copy + Display + FromStr + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits,
+{
+    /// Writes each name on a line of its own, followed by one `#key=value`
+    /// line per `label`, tag and `note`, and then the counter as written by
+    /// `Counter::to_file`, with an empty line between counters. Names and
+    /// metadata escape backslashes and line breaks as `\\` and `\n`.
+    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let contents: Vec<_> = self
+            .entries
+            .iter()
+            .map(|entry| format!("{}\n{}", escape(&entry.name), entry.to_block()))
+            .collect();
+        fs::write(path, contents.join("\n\n"))
+    }
+
+    /// Reads a file written by `to_file`.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<CounterSet<T>> {
+        let contents = read_to_string(path)?;
+        let mut set = CounterSet::new();
+        if contents.is_empty() {
+            return Ok(set);
+        }
+        for block in contents.split("\n\n") {
+            let (name, block) = block.split_once('\n').unwrap_or((block, ""));
+            let name =
+                unescape(name).ok_or_else(|| invalid("File does not contain valid name data"))?;
+            let (counter, metadata) = Entry::from_block(block)?;
+            set.insert_with(name, counter, metadata);
+        }
+        Ok(set)
+    }
+
+    /// Writes one `<name>.counter` file per counter into `dir`, creating it if
+    /// needed, and removes the `.counter` files of counters no longer in the
+    /// set. Each file holds the metadata and counter lines of a `to_file`
+    /// block. Names are kept readable, with anything but ASCII letters,
+    /// digits, `-` and `_` written as `%XX` bytes, e.g. `tea%20time.counter`.
+    pub fn save_dir<P: AsRef<Path>>(&self, dir: P) -> io::Result<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        let mut written = Vec::with_capacity(self.entries.len());
+        for entry in &self.entries {
+            let path = dir.join(format!("{}.{}", file_stem(&entry.name), EXTENSION));
+            fs::write(&path, entry.to_block())?;
+            written.push(path);
+        }
+        for path in counter_files(dir)? {
+            if !written.contains(&path) {
+                fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads every `.counter` file in `dir`, as written by `save_dir`, in
+    /// order of their file names. Files that cannot be read are skipped and
+    /// returned alongside the set, so one corrupt file does not lose the
+    /// others. Only failing to list `dir` itself is an error.
+    pub fn load_dir<P: AsRef<Path>>(dir: P) -> io::Result<(CounterSet<T>, LoadErrors)> {
+        let mut paths = counter_files(dir.as_ref())?;
+        paths.sort();
+        let mut set = CounterSet::new();
+        let mut errors = Vec::new();
+        for path in paths {
+            let name = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(name_from_stem);
+            let Some(name) = name else {
+                errors.push((path, PersistError::InvalidName));
+                continue;
+            };
+            match read_to_string(&path).map_err(PersistError::Io) {
+                Ok(block) => match Entry::from_block(&block) {
+                    Ok((counter, metadata)) => {
+                        set.insert_with(name, counter, metadata);
+                    }
+                    Err(error) => errors.push((path, PersistError::Invalid(error))),
+                },
+                Err(error) => errors.push((path, error)),
+            }
+        }
+        Ok((set, errors))
+    }
+}
+
+impl<T, D> Entry<T>
+where
+    T: Copy + Display + FromStr + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits,
+{
+    /// One `#key=value` line per `label`, tag and `note`, then the counter
+    fn to_block(&self) -> String {
+        let mut block = String::new();
+        let Metadata { label, tags, note } = &self.metadata;
+        let lines = (label.iter().map(|label| ("label", label)))
+            .chain(tags.iter().map(|tag| ("tag", tag)))
+            .chain(note.iter().map(|note| ("note", note)));
+        for (key, value) in lines {
+            block.push_str(&format!("#{}={}\n", key, escape(value)));
+        }
+        block.push_str(&self.counter.to_contents());
+        block
+    }
+
+    /// Reverses `to_block`
+    fn from_block(block: &str) -> io::Result<(Counter<T>, Metadata)> {
+        let mut lines = block.split('\n').peekable();
+        let mut metadata = Metadata::default();
+        while let Some(line) = lines.next_if(|line| line.starts_with('#')) {
+            let (key, value) = line[1..]
+                .split_once('=')
+                .and_then(|(key, value)| Some((key, unescape(value)?)))
+                .ok_or_else(|| invalid("File does not contain valid metadata"))?;
+            match key {
+                "label" => metadata.label = Some(value),
+                "tag" => metadata.tags.push(value),
+                "note" => metadata.note = Some(value),
+                _ => return Err(invalid("File contains unknown metadata")),
+            }
+        }
+        let counter = lines.collect::<Vec<_>>().join("\n");
+        let counter = Counter::from_contents_with(&counter, |s| T::from_str(s).ok())?;
+        Ok((counter, metadata))
+    }
+}
+
+fn invalid(what: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, what)
+}
+
+/// The file extension used by `CounterSet::save_dir`
+const EXTENSION: &str = "counter";
+
+/// The `.counter` files directly inside `dir`
+fn counter_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == EXTENSION) {
+            paths.push(path);
+        }
+    }
+    Ok(paths)
+}
+
+/// `name` with every byte outside `[A-Za-z0-9_-]` written as `%XX`
+fn file_stem(name: &str) -> String {
+    let mut stem = String::with_capacity(name.len());
+    for byte in name.bytes() {
+        match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' => stem.push(byte as char),
+            _ => stem.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    stem
+}
+
+/// Reverses `file_stem`
+fn name_from_stem(stem: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(stem.len());
+    let mut rest = stem.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = std::str::from_utf8(tail.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// Escapes backslashes and line breaks, so `value` fits on one line.
+pub(crate) fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+/// Reverses `escape`, failing on unknown escapes.
+pub(crate) fn unescape(value: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(char) = chars.next() {
+        unescaped.push(match char {
+            '\\' => match chars.next()? {
+                '\\' => '\\',
+                'n' => '\n',
+                _ => return None,
+            },
+            char => char,
+        });
+    }
+    Some(unescaped)
+}
+
+/// Shows the counters as a map from name to counter, in insertion order
+impl<T, D> fmt::Debug for CounterSet<T>
+where
+    T: fmt::Debug + Copy + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// One "name: HH:MM:SS" line per counter, all read at the same instant
+impl<T, D> Display for CounterSet<T>
+where
+    T: Copy + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let now = T::now();
+        for (index, (name, counter)) in self.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}: ", name)?;
+            write_hms(f, counter.at(now).clamped_hms())?;
+        }
+        Ok(())
+    }
+}
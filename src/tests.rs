@@ -12,6 +12,8 @@ impl TimeUnits for i64 {
     }
 }
 
+impl_duration_cmp!(i64 => i64);
+
 impl Time for i64 {
     type Duration = i64;
 
@@ -20,7 +22,15 @@ impl Time for i64 {
     }
 
     fn add_seconds(self, duration: Self::Duration) -> Result<Self, TimeOverflow> {
-        Ok(self + duration)
+        self.checked_add(duration).ok_or(TimeOverflow)
+    }
+
+    fn max_value() -> Option<Self> {
+        Some(i64::MAX)
+    }
+
+    fn min_value() -> Option<Self> {
+        Some(i64::MIN)
     }
 }
 
@@ -63,13 +73,45 @@ fn hours_until() {
 #[test]
 fn days_since() {
     let counter = Counter::up(Some(-86400 * 10), None);
-    assert_eq!(counter.to_string(), "240:00:00")
+    assert_eq!(counter.to_string(), "240:00:00");
+    assert_eq!(counter.days(), 10);
 }
 
 #[test]
 fn days_until() {
     let counter = Counter::down(None, Some(86400 * 10));
-    assert_eq!(counter.to_string(), "240:00:00")
+    assert_eq!(counter.to_string(), "240:00:00");
+    assert_eq!(counter.days(), 10);
+}
+
+#[test]
+fn weeks_since() {
+    let counter = Counter::up(Some(-86400 * 7 * 3), None);
+    assert_eq!(counter.weeks(), 3);
+    assert_eq!(counter.days(), 21);
+}
+
+#[test]
+fn weeks_until() {
+    let counter = Counter::down(None, Some(86400 * 7 * 3 - 1));
+    assert_eq!(counter.weeks(), 2);
+    assert_eq!(counter.days(), 20);
+}
+
+#[test]
+fn days_and_weeks_boundaries() {
+    // 6 days 23:59:59
+    let counter = Counter::down(None, Some(86400 * 7 - 1));
+    assert_eq!(
+        (counter.weeks(), counter.days(), counter.hours()),
+        (0, 6, 167)
+    );
+    let counter = Counter::down(None, Some(86400 * 7));
+    assert_eq!((counter.weeks(), counter.days()), (1, 7));
+    let counter = Counter::down(None, Some(86400 - 1));
+    assert_eq!(counter.days(), 0);
+    let counter = Counter::down(None, Some(-86400 * 7));
+    assert_eq!((counter.weeks(), counter.days()), (0, 0));
 }
 
 #[test]
@@ -136,6 +178,45 @@ fn too_much_time_causes_overflow() {
     counter.try_move_start(1).unwrap();
 }
 
+#[test]
+fn too_little_time_causes_underflow() {
+    let mut counter = Counter::<i64>::up(Some(i64::MIN + 1), Some(0));
+    assert!(counter.try_move_start(-1).is_ok());
+    assert!(counter.try_move_start(-1).is_err());
+    assert!(counter.try_move_end(i64::MIN).is_ok());
+    assert!(counter.try_move_end(-1).is_err());
+    assert_eq!((counter.start, counter.end), (i64::MIN, i64::MIN));
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono_underflow_is_an_error() {
+    use ::chrono::{DateTime, Duration, Utc};
+
+    let mut counter = Counter::up(Some(DateTime::<Utc>::MIN_UTC), None);
+    assert!(counter.try_move_start(Duration::seconds(-1)).is_err());
+    assert_eq!(counter.start, DateTime::<Utc>::MIN_UTC);
+    counter.set_end_unchecked(DateTime::<Utc>::MAX_UTC);
+    assert!(counter.try_move_end(Duration::seconds(1)).is_err());
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono_counter_compares_with_duration() {
+    use ::chrono::{DateTime, Duration, FixedOffset, Utc};
+
+    let now = Utc::now();
+    let counter = Counter::down(None, Some(now + Duration::minutes(3)));
+    assert!(counter < Duration::minutes(5));
+    assert!(counter > Duration::minutes(2));
+
+    let berlin = FixedOffset::east_opt(2 * 3600).unwrap();
+    let start: DateTime<FixedOffset> = (now - Duration::minutes(10)).with_timezone(&berlin);
+    let counter = Counter::up(Some(start), None);
+    assert!(counter >= Duration::minutes(10));
+    assert!(counter != Duration::minutes(9));
+}
+
 #[test]
 fn write_and_read_down() {
     let start = 0;
@@ -172,6 +253,3983 @@ fn flip_up_and_down() {
     assert_eq!(counter.to_string(), "00:00:10");
 }
 
+#[test]
+fn humanize_unit_boundaries() {
+    let cases = [
+        (0, "0s"),
+        (1, "1s"),
+        (59, "59s"),
+        (60, "1m"),
+        (61, "1m 1s"),
+        (3599, "59m 59s"),
+        (3600, "1h"),
+        (3601, "1h 1s"),
+        (3660, "1h 1m"),
+        (86399, "23h 59m"),
+        (86400, "1d"),
+        (86401, "1d 1s"),
+        (90000, "1d 1h"),
+        (101520, "1d 4h"),
+    ];
+    for (seconds, expected) in cases {
+        assert_eq!(i64::seconds(seconds).humanize(), expected);
+    }
+}
+
+#[test]
+fn humanize_negative() {
+    assert_eq!(i64::seconds(-61).humanize(), "-1m 1s");
+    assert_eq!(i64::seconds(-86400).humanize(), "-1d");
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn humanize_subsecond() {
+    use crate::types::Duration;
+    let half = Duration::from(::chrono::Duration::milliseconds(500));
+    assert_eq!(half.humanize(), "<1s");
+    let negative = Duration::from(::chrono::Duration::milliseconds(-500));
+    assert_eq!(negative.humanize(), "-<1s");
+    let zero = Duration::seconds(0);
+    assert_eq!(zero.humanize(), "0s");
+}
+
+#[test]
+fn one_week() {
+    let week = i64::weeks(1).unwrap();
+    assert_eq!(week, 604800);
+    assert_eq!(week.num_weeks(), 1);
+    assert_eq!(week.wdhms(), (1, 0, 0, 0, 0));
+    assert_eq!((week - 1).num_weeks(), 0);
+    assert_eq!((week - 1).wdhms(), (0, 6, 23, 59, 59));
+}
+
+#[test]
+fn negative_weeks() {
+    let weeks = i64::weeks(-2).unwrap();
+    assert_eq!(weeks.num_weeks(), -2);
+    assert_eq!((weeks - 3600).wdhms(), (-2, 0, -1, 0, 0));
+    assert_eq!((weeks - 3600).dhms(), (-14, -1, 0, 0));
+}
+
+#[test]
+fn weeks_overflow() {
+    assert!(i64::weeks(i64::MAX).is_err());
+    assert!(i64::weeks(i64::MIN).is_err());
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn weeks_overflow_of_backend_range() {
+    use crate::types::Duration;
+    // Fits into i64 seconds, but not into chrono's millisecond range
+    assert!(Duration::weeks(i64::MAX / 604800).is_err());
+    assert_eq!(Duration::weeks(3).unwrap().num_weeks(), 3);
+}
+
+#[test]
+fn parse_duration_forms() {
+    use crate::parse::parse_duration;
+    let cases = [
+        ("5400", 5400),
+        ("1h30m", 5400),
+        ("1h 30m", 5400),
+        ("90m", 5400),
+        ("PT90M", 5400),
+        ("PT1H30M", 5400),
+        ("P2DT3H", 2 * 86400 + 3 * 3600),
+        ("1w2d", 9 * 86400),
+        ("P1W", 604800),
+        ("-1h30m", -5400),
+        ("-PT5M", -300),
+        ("+45s", 45),
+    ];
+    for (string, expected) in cases {
+        assert_eq!(parse_duration(string), Ok(expected), "{string}");
+    }
+}
+
+#[test]
+fn parse_duration_errors() {
+    use crate::parse::parse_duration;
+    let cases = [
+        ("", 0, "empty duration"),
+        ("1h1h", 3, "duplicate unit"),
+        ("1m1h", 3, "out of order"),
+        ("5x", 1, "unknown unit"),
+        ("h", 0, "expected a number"),
+        ("10h5", 4, "missing unit"),
+        ("PT1M1M", 5, "duplicate designator"),
+        ("PT1M1H", 5, "out of order"),
+        ("P1Y", 2, "calendar years and months are not supported"),
+        ("P1H", 2, "time designator before 'T'"),
+        ("P1DT", 4, "expected a time component after 'T'"),
+        ("PTT1H", 2, "duplicate 'T'"),
+        ("P", 1, "expected at least one component"),
+        ("99999999999999999999s", 0, "number is too large"),
+    ];
+    for (string, position, reason) in cases {
+        let error = parse_duration(string).unwrap_err();
+        assert_eq!(
+            (error.position(), error.reason()),
+            (position, reason),
+            "{string}"
+        );
+    }
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn parse_duration_equivalence() {
+    use crate::types::Duration;
+    let expected = Duration::seconds(5400);
+    for string in ["PT90M", "1h30m", "5400"] {
+        assert_eq!(string.parse::<Duration>().unwrap(), expected);
+    }
+    assert!("106751991167301d".parse::<Duration>().is_err());
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn parse_rfc2822_timestamp() {
+    use crate::types::TimeStamp;
+    let time: TimeStamp = "Tue, 1 Jul 2003 10:52:37 +0200".parse().unwrap();
+    assert_eq!(time.to_string(), "2003-07-01T08:52:37+00:00");
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn parse_rfc3339_timestamp() {
+    use crate::types::TimeStamp;
+    let time: TimeStamp = "2003-07-01T10:52:37+02:00".parse().unwrap();
+    assert_eq!(time.to_string(), "2003-07-01T08:52:37+00:00");
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn parse_invalid_timestamp() {
+    use crate::types::TimeStamp;
+    let error = "next tuesday".parse::<TimeStamp>().unwrap_err();
+    assert_eq!(error.tried(), ["RFC 3339", "RFC 2822"]);
+    assert_eq!(
+        error.to_string(),
+        "Tried to parse invalid time string (tried RFC 3339, RFC 2822)"
+    );
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn parse_flexible_timestamps() {
+    use crate::types::TimeStamp;
+    use ::chrono::{DateTime, Utc};
+    let cases = [
+        ("2025-06-01", 1748736000),
+        ("2025-06-01 14:30", 1748788200),
+        ("2025-06-01T14:30", 1748788200),
+        ("2025-06-01T14:30:00", 1748788200),
+        ("2025-06-01 14:30:00.5", 1748788200),
+        ("2025-06-01T14:30:00+02:00", 1748781000),
+        ("2025-06-01 14:30:00+02:00", 1748781000),
+        ("2025-06-01 14:30+02:00", 1748781000),
+        ("Sun, 1 Jun 2025 14:30:00 +0000", 1748788200),
+    ];
+    for (string, epoch) in cases {
+        let time = TimeStamp::parse_flexible(string).unwrap();
+        assert_eq!(DateTime::<Utc>::from(time).timestamp(), epoch, "{string}");
+    }
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn parse_flexible_with_offset() {
+    use crate::types::TimeStamp;
+    use ::chrono::FixedOffset;
+    let offset = FixedOffset::east_opt(2 * 3600).unwrap();
+    let time = TimeStamp::parse_flexible_in("2025-06-01 14:30", offset).unwrap();
+    assert_eq!(time.to_string(), "2025-06-01T12:30:00+00:00");
+    // Explicit offsets win over the supplied one
+    let time = TimeStamp::parse_flexible_in("2025-06-01T14:30:00Z", offset).unwrap();
+    assert_eq!(time.to_string(), "2025-06-01T14:30:00+00:00");
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn parse_flexible_lists_formats() {
+    use crate::types::TimeStamp;
+    let error = TimeStamp::parse_flexible("June 1st").unwrap_err();
+    assert_eq!(error.tried().len(), 11);
+    assert!(error.to_string().contains("%Y-%m-%d %H:%M"));
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn read_lenient_file() {
+    use crate::types::TimeStamp;
+    let path = "/tmp/counter_test_file_lenient.txt";
+    std::fs::write(path, "2025-06-01 14:30\n2025-06-02\nDown").unwrap();
+    let counter = Counter::<TimeStamp>::from_file_lenient(path).unwrap();
+    assert_eq!(counter.start.to_string(), "2025-06-01T14:30:00+00:00");
+    assert_eq!(counter.end.to_string(), "2025-06-02T00:00:00+00:00");
+    assert!(Counter::<TimeStamp>::from_file(path).is_err());
+}
+
+#[test]
+fn position() {
+    let mut counter = Counter::up(Some(-83), Some(2617));
+    let (elapsed, total) = counter.position();
+    assert_eq!((elapsed.total_seconds(), total.total_seconds()), (83, 2700));
+    assert_eq!(counter.position_string(), "01:23 / 45:00");
+    // Read from the same sample as the value shown
+    counter.paused_at = Some(-60);
+    assert_eq!(counter.position_string(), "00:23 / 45:00");
+    assert_eq!(counter.to_string(), "00:00:23");
+
+    assert_eq!(counter.position_at(-100).0.total_seconds(), 23);
+
+    // Before `start` and past `end`, only the string is clamped
+    counter.paused_at = None;
+    assert_eq!(counter.position_at(-200).0.total_seconds(), -117);
+    assert_eq!(counter.position_string_at(-200), "00:00 / 45:00");
+    assert_eq!(counter.position_at(3000).0.total_seconds(), 3083);
+    assert_eq!(counter.position_string_at(3000), "45:00 / 45:00");
+
+    let long = Counter::down(Some(-83), Some(90000));
+    assert_eq!(long.position_string(), "00:01:23 / 25:01:23");
+    let open_ended = Counter::up(Some(-83), None);
+    assert_eq!(open_ended.position().0.total_seconds(), 83);
+    assert_eq!(open_ended.position_string(), "00:00 / 00:00");
+}
+
+#[test]
+fn deadline() {
+    let counter = Counter::down(Some(-10), Some(100));
+    assert_eq!(*counter.deadline(), 100);
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn deadline_string_around_midnight() {
+    use crate::types::TimeStamp;
+    use ::chrono::{FixedOffset, TimeZone, Utc};
+    let berlin = FixedOffset::east_opt(3600).unwrap();
+    let local = |day, hour, minute| {
+        berlin
+            .with_ymd_and_hms(2024, 3, day, hour, minute, 0)
+            .unwrap()
+    };
+    let due = |day, hour, minute| {
+        let end = TimeStamp::from(local(day, hour, minute).with_timezone(&Utc));
+        Counter::down(None, Some(end))
+    };
+    // 2024-03-04 is a Monday
+    let just_before_midnight = local(4, 23, 59);
+    assert_eq!(
+        due(4, 23, 59).deadline_string_at(&just_before_midnight),
+        "23:59"
+    );
+    assert_eq!(
+        due(5, 0, 0).deadline_string_at(&just_before_midnight),
+        "Tue 00:00"
+    );
+    let midnight = local(5, 0, 0);
+    assert_eq!(due(5, 0, 0).deadline_string_at(&midnight), "00:00");
+    assert_eq!(due(5, 23, 59).deadline_string_at(&midnight), "23:59");
+    assert_eq!(due(4, 23, 59).deadline_string_at(&midnight), "2024-03-04");
+    // Up to six days ahead, then dates
+    assert_eq!(due(11, 9, 0).deadline_string_at(&midnight), "Mon 09:00");
+    assert_eq!(due(12, 9, 0).deadline_string_at(&midnight), "2024-03-12");
+    // The day is the one seen in the time zone of `now`
+    let in_utc = midnight.with_timezone(&Utc);
+    assert_eq!(due(5, 0, 30).deadline_string_at(&in_utc), "23:30");
+    assert_eq!(due(5, 0, 30).deadline_string_at(&midnight), "00:30");
+
+    let dated = due(5, 17, 30).into_datetime(&berlin);
+    assert_eq!(dated.deadline_string_at(&midnight), "17:30");
+    assert!(!due(5, 0, 0).deadline_string().is_empty());
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn parse_relative_timestamps() {
+    use crate::types::TimeStamp;
+    let now: TimeStamp = "2025-06-01T12:00:00Z".parse().unwrap();
+    let cases = [
+        ("now", "2025-06-01T12:00:00+00:00"),
+        ("now+1h", "2025-06-01T13:00:00+00:00"),
+        ("now -15m", "2025-06-01T11:45:00+00:00"),
+        (" now - PT1H30M ", "2025-06-01T10:30:00+00:00"),
+        ("now+90", "2025-06-01T12:01:30+00:00"),
+    ];
+    for (string, expected) in cases {
+        let time = TimeStamp::parse_relative(string, now).unwrap();
+        assert_eq!(time.to_string(), expected, "{string}");
+    }
+    for string in ["now*5", "now+", "now+1x", "later", "nowhere"] {
+        assert!(TimeStamp::parse_relative(string, now).is_err(), "{string}");
+    }
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn parse_flexible_accepts_relative() {
+    use crate::types::TimeStamp;
+    let parsed = TimeStamp::parse_flexible("now+1h").unwrap();
+    let lower = TimeStamp::now() + 3599;
+    assert!(lower < parsed && parsed <= TimeStamp::now() + 3600);
+}
+
+#[cfg(feature = "natural")]
+#[test]
+fn parse_natural_accepted() {
+    use crate::types::TimeStamp;
+    use ::chrono::Utc;
+    // A Wednesday
+    let now: TimeStamp = "2025-06-04T10:00:00Z".parse().unwrap();
+    let cases = [
+        ("tomorrow 9am", "2025-06-05T09:00:00+00:00"),
+        ("Tomorrow at 9:30 PM", "2025-06-05T21:30:00+00:00"),
+        ("today 17:00", "2025-06-04T17:00:00+00:00"),
+        ("today 9am", "2025-06-04T09:00:00+00:00"),
+        ("17:00", "2025-06-04T17:00:00+00:00"),
+        ("9am", "2025-06-05T09:00:00+00:00"),
+        ("noon", "2025-06-04T12:00:00+00:00"),
+        ("midnight", "2025-06-05T00:00:00+00:00"),
+        ("12am", "2025-06-05T00:00:00+00:00"),
+        ("tomorrow", "2025-06-05T00:00:00+00:00"),
+        ("friday", "2025-06-06T00:00:00+00:00"),
+        ("fri 17:00", "2025-06-06T17:00:00+00:00"),
+        ("next friday 17:00", "2025-06-06T17:00:00+00:00"),
+        ("wednesday 11am", "2025-06-04T11:00:00+00:00"),
+        ("wednesday 9am", "2025-06-11T09:00:00+00:00"),
+        ("wednesday", "2025-06-11T00:00:00+00:00"),
+        ("next wednesday 11am", "2025-06-11T11:00:00+00:00"),
+        ("monday", "2025-06-09T00:00:00+00:00"),
+    ];
+    for (phrase, expected) in cases {
+        let time = TimeStamp::parse_natural(phrase, now, &Utc).unwrap();
+        assert_eq!(time.to_string(), expected, "{phrase}");
+    }
+}
+
+#[cfg(feature = "natural")]
+#[test]
+fn parse_natural_in_time_zone() {
+    use crate::types::TimeStamp;
+    use ::chrono::FixedOffset;
+    let tz = FixedOffset::east_opt(2 * 3600).unwrap();
+    // Already 01:00 on June 5th locally, so "tomorrow" is June 6th
+    let now: TimeStamp = "2025-06-04T23:00:00Z".parse().unwrap();
+    let time = TimeStamp::parse_natural("tomorrow 9am", now, &tz).unwrap();
+    assert_eq!(time.to_string(), "2025-06-06T07:00:00+00:00");
+}
+
+#[cfg(feature = "natural")]
+#[test]
+fn parse_natural_rejected() {
+    use crate::types::TimeStamp;
+    use ::chrono::Utc;
+    let now: TimeStamp = "2025-06-04T10:00:00Z".parse().unwrap();
+    let phrases = [
+        "",
+        "yesterday",
+        "tomorrow 25:00",
+        "13pm",
+        "0am",
+        "9",
+        "9:60am",
+        "9:5pm",
+        "friday friday",
+        "next",
+        "next tomorrow",
+        "tomorrow 9am please",
+        "at 9am",
+    ];
+    for phrase in phrases {
+        assert!(
+            TimeStamp::parse_natural(phrase, now, &Utc).is_err(),
+            "{phrase}"
+        );
+    }
+
+    // Days past the last date chrono can represent
+    let now = TimeStamp::from(::chrono::DateTime::<Utc>::MAX_UTC);
+    for phrase in ["tomorrow", "next friday", "monday 9am", "9am"] {
+        assert!(
+            TimeStamp::parse_natural(phrase, now, &Utc).is_err(),
+            "{phrase}"
+        );
+    }
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn format_timestamps() {
+    use crate::types::TimeStamp;
+    let time: TimeStamp = "2025-06-01T14:30:05Z".parse().unwrap();
+    assert_eq!(time.format("%H:%M").unwrap(), "14:30");
+    assert_eq!(
+        time.format("%Y-%m-%d %H:%M:%S").unwrap(),
+        "2025-06-01 14:30:05"
+    );
+    assert_eq!(time.format("%a %d %b").unwrap(), "Sun 01 Jun");
+    assert_eq!(time.display_as("%M:%S").unwrap().to_string(), "30:05");
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn format_timestamp_in_zone() {
+    use crate::types::TimeStamp;
+    use ::chrono::FixedOffset;
+    let time: TimeStamp = "2025-06-01T23:30:00Z".parse().unwrap();
+    let tz = FixedOffset::west_opt(5 * 3600).unwrap();
+    assert_eq!(
+        time.format_in("%Y-%m-%d %H:%M %:z", &tz).unwrap(),
+        "2025-06-01 18:30 -05:00"
+    );
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn format_invalid_specifier() {
+    use crate::types::TimeStamp;
+    let time = TimeStamp::now();
+    assert_eq!(time.format("%Q"), Err(InvalidFormat));
+    assert!(time.display_as("%H:%").is_err());
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn day_boundaries_in_utc() {
+    use crate::types::TimeStamp;
+    let time: TimeStamp = "2025-06-01T14:30:05Z".parse().unwrap();
+    assert_eq!(time.start_of_day().to_string(), "2025-06-01T00:00:00+00:00");
+    assert_eq!(time.end_of_day().to_string(), "2025-06-02T00:00:00+00:00");
+    let midnight = time.start_of_day();
+    assert_eq!(midnight.start_of_day(), midnight);
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn day_boundaries_with_dst() {
+    use crate::types::TimeStamp;
+    use chrono_tz::America::Havana;
+    // Clocks jump from 00:00 to 01:00 on 2023-03-12
+    let time: TimeStamp = "2023-03-12T16:00:00Z".parse().unwrap();
+    assert_eq!(
+        time.start_of_day_in(&Havana).to_string(),
+        "2023-03-12T05:00:00+00:00"
+    );
+    // That is the earliest valid instant after the gap, shown locally as 01:00
+    let local = |time: TimeStamp| {
+        ::chrono::DateTime::<::chrono::Utc>::from(time)
+            .with_timezone(&Havana)
+            .format("%F %T")
+            .to_string()
+    };
+    let start = time.start_of_day_in(&Havana);
+    assert_eq!(local(start), "2023-03-12 01:00:00");
+    assert_eq!(local(start - 1), "2023-03-11 23:59:59");
+    let day_before: TimeStamp = "2023-03-11T16:00:00Z".parse().unwrap();
+    assert_eq!(day_before.end_of_day_in(&Havana), start);
+    // Clocks go back from 01:00 to 00:00 on 2023-11-05, so midnight happens twice
+    let time: TimeStamp = "2023-11-05T17:00:00Z".parse().unwrap();
+    assert_eq!(
+        time.start_of_day_in(&Havana).to_string(),
+        "2023-11-05T04:00:00+00:00"
+    );
+    assert_eq!(
+        time.end_of_day_in(&Havana).to_string(),
+        "2023-11-06T05:00:00+00:00"
+    );
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn count_down_to_midnight() {
+    use crate::types::TimeStamp;
+    let counter = Counter::<TimeStamp>::down_to_midnight();
+    assert_eq!(counter.end, counter.start.end_of_day());
+    assert!(counter.hours() < 24);
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn round_timestamps() {
+    use crate::types::TimeStamp;
+    use Rounding::*;
+    use Unit::*;
+    let cases = [
+        ("14:30:00", Minute, [("14:30:00"); 3]),
+        ("14:30:29.999", Minute, ["14:30:00", "14:31:00", "14:30:00"]),
+        ("14:30:30", Minute, ["14:30:00", "14:31:00", "14:31:00"]),
+        ("14:30:30.001", Minute, ["14:30:00", "14:31:00", "14:31:00"]),
+        ("14:30:05.4", Second, ["14:30:05", "14:30:06", "14:30:05"]),
+        ("14:30:05.5", Second, ["14:30:05", "14:30:06", "14:30:06"]),
+        ("14:29:59.999", Hour, ["14:00:00", "15:00:00", "14:00:00"]),
+        ("14:30:00", Hour, ["14:00:00", "15:00:00", "15:00:00"]),
+        ("11:59:59", Day, ["00:00:00", "00:00:00", "00:00:00"]),
+        ("12:00:00", Day, ["00:00:00", "00:00:00", "00:00:00"]),
+    ];
+    for (time, unit, expected) in cases {
+        let stamp: TimeStamp = format!("2025-06-01T{time}Z").parse().unwrap();
+        for (mode, expected) in [Floor, Ceil, Nearest].into_iter().zip(expected) {
+            let rounded = stamp.round_to(unit, mode).format("%H:%M:%S").unwrap();
+            assert_eq!(rounded, expected, "{time} {unit:?} {mode:?}");
+        }
+    }
+    let noon: TimeStamp = "2025-06-01T12:00:00Z".parse().unwrap();
+    assert_eq!(
+        noon.round_to(Day, Nearest).to_string(),
+        "2025-06-02T00:00:00+00:00"
+    );
+    assert_eq!(
+        noon.round_to(Day, Ceil).to_string(),
+        "2025-06-02T00:00:00+00:00"
+    );
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn snap_counter_start() {
+    use crate::types::TimeStamp;
+    let start: TimeStamp = "2025-06-01T14:30:31Z".parse().unwrap();
+    let mut counter = Counter::up(Some(start), None);
+    counter.snap_start(Unit::Minute, Rounding::Nearest);
+    assert_eq!(counter.start.to_string(), "2025-06-01T14:31:00+00:00");
+
+    // Snapping is a modification like any other move of `start`
+    counter.audit.modified_at = Some(start);
+    counter.snap_start(Unit::Hour, Rounding::Floor);
+    assert!(counter.modified_at().unwrap() > start);
+}
+
+#[test]
+fn headroom_at_limits() {
+    let counter = Counter::down(Some(i64::MIN), Some(i64::MAX));
+    assert_eq!(counter.headroom(), Some((0, 0)));
+    let counter = Counter::down(Some(i64::MIN + 5), Some(i64::MAX - 7));
+    assert_eq!(counter.headroom(), Some((5, 7)));
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn timestamp_headroom_at_limits() {
+    use crate::types::{Duration, TimeStamp};
+    let (min, max) = (TimeStamp::min_value(), TimeStamp::max_value());
+    let mut counter = Counter::down(min, max);
+    let zero = Duration::seconds(0);
+    assert_eq!(counter.headroom(), Some((zero, zero)));
+    assert!(counter.try_move_end(1).is_err());
+    assert!(counter.try_move_start(-1).is_err());
+
+    let counter = Counter::down(min.map(|t| t + 60), max.map(|t| t - 1));
+    let (down, up) = counter.headroom().unwrap();
+    assert_eq!((down.num_seconds(), up.num_seconds()), (60, 1));
+}
+
+mod unix_seconds {
+    use crate::unix::{Seconds, UnixSeconds};
+    use crate::*;
+
+    /// The clock may tick between constructing a counter and reading it
+    fn assert_shows(counter: &Counter<UnixSeconds>, seconds: i64) {
+        let shown = counter.seconds();
+        assert!((shown - seconds).abs() <= 1, "{shown} vs {seconds}");
+    }
+
+    #[test]
+    fn seconds_since() {
+        let counter = Counter::up(Some(UnixSeconds::now() - 10), None);
+        assert_shows(&counter, 10);
+    }
+
+    #[test]
+    fn seconds_until() {
+        let counter = Counter::down(None, Some(UnixSeconds::now() + 10));
+        assert_shows(&counter, 10);
+    }
+
+    #[test]
+    fn days_until() {
+        let counter = Counter::down(None, Some(UnixSeconds::now() + 86400 * 10));
+        assert_shows(&counter, 86400 * 10);
+        assert!(counter.to_string().starts_with("2"));
+    }
+
+    #[test]
+    fn display_format() {
+        let counter = Counter::down(None, Some(UnixSeconds::now() + 3725));
+        assert!(["01:02:05", "01:02:04"].contains(&counter.to_string().as_str()));
+    }
+
+    #[test]
+    fn add_and_remove_time() {
+        let mut counter = Counter::down(None, Some(UnixSeconds::now()));
+        counter.try_move_end(20).unwrap();
+        counter.try_move_end(Seconds::seconds(-10)).unwrap();
+        assert_shows(&counter, 10);
+
+        let mut counter = Counter::up(Some(UnixSeconds::now()), None);
+        counter.try_move_start(-10).unwrap();
+        assert_shows(&counter, 10);
+    }
+
+    #[test]
+    fn remove_time_past_zero() {
+        let mut counter = Counter::down(None, Some(UnixSeconds::now()));
+        counter.try_move_end(-10).unwrap();
+        assert_eq!(counter.to_string(), "00:00:00");
+    }
+
+    #[test]
+    fn too_much_time_causes_overflow() {
+        let mut counter = Counter::up(Some(UnixSeconds(i64::MAX - 1)), Some(UnixSeconds(0)));
+        assert!(counter.try_move_start(1).is_ok());
+        assert!(counter.try_move_start(1).is_err());
+        assert_eq!(counter.headroom().unwrap().1, Seconds(i64::MAX));
+    }
+
+    #[test]
+    fn write_and_read() {
+        let now = UnixSeconds::now();
+        let counter = Counter::down(Some(now), Some(now + 86400 * 3));
+        counter.to_file("/tmp/counter_test_file_unix.txt").unwrap();
+        let read_counter = Counter::from_file("/tmp/counter_test_file_unix.txt").unwrap();
+        assert_eq!(counter, read_counter);
+    }
+
+    #[test]
+    fn parse_and_display() {
+        assert_eq!("-42".parse::<UnixSeconds>().unwrap(), UnixSeconds(-42));
+        assert_eq!(UnixSeconds(1700000000).to_string(), "1700000000");
+        assert!("12.5".parse::<UnixSeconds>().is_err());
+        assert_eq!("1h30m".parse::<Seconds>().unwrap(), Seconds(5400));
+    }
+
+    #[test]
+    fn flip_up_and_down() {
+        let now = UnixSeconds::now();
+        let mut counter = Counter::down(Some(now - 100), Some(now + 100));
+        assert_shows(&counter, 100);
+        counter.flip();
+        assert_shows(&counter, 100);
+    }
+}
+
+mod wrapping_ticks {
+    use crate::ticks::{TickDuration, WrappingTicks};
+    use crate::*;
+
+    type Millis = WrappingTicks<1000>;
+
+    #[test]
+    fn subtraction_across_wrap() {
+        let (before, after): (Millis, Millis) = (WrappingTicks(u32::MAX - 499), WrappingTicks(499));
+        assert_eq!(after - before, TickDuration(999));
+        assert_eq!(before - after, TickDuration(-999));
+        assert_eq!((after - before).num_seconds(), 0);
+        let half: Millis = WrappingTicks(i32::MAX as u32);
+        assert_eq!(half - Millis::default(), TickDuration(i32::MAX as i64));
+        let past_half: Millis = WrappingTicks(i32::MAX as u32 + 1);
+        assert_eq!(past_half - Millis::default(), TickDuration(i32::MIN as i64));
+    }
+
+    #[test]
+    fn add_across_wrap() {
+        let start = WrappingTicks::<1000>(u32::MAX - 999);
+        let end = start.add_seconds(TickDuration::seconds(2)).unwrap();
+        assert_eq!(end, WrappingTicks(1000));
+        assert!(start.add_seconds(TickDuration(i64::from(i32::MAX))).is_ok());
+        assert!(start.add_seconds(TickDuration(1 << 31)).is_err());
+    }
+
+    // The only test touching the shared tick source, so that it cannot race
+    #[test]
+    fn counters_across_wrap() {
+        Millis::set_current(u32::MAX - 1499);
+        let now = Millis::now();
+        let mut down = Counter::down(Some(now), Some(now));
+        down.try_move_end(5).unwrap();
+        let up = Counter::up(Some(now), None);
+        assert_eq!(down.to_string(), "00:00:05");
+
+        Millis::set_current(2500);
+        assert_eq!(up.counter(), (0, 0, 4));
+        assert_eq!(up.to_string(), "00:00:04");
+        assert_eq!(down.to_string(), "00:00:01");
+
+        Millis::set_current(3500);
+        assert_eq!(down.to_string(), "00:00:00");
+        assert_eq!(up.to_string(), "00:00:05");
+    }
+}
+
+#[cfg(feature = "defmt")]
+#[test]
+fn defmt_format_implemented() {
+    // Rendering needs a global defmt logger, so only check the impls exist
+    fn assert_format<T: defmt::Format>() {}
+    assert_format::<Counter<i64>>();
+    assert_format::<Direction>();
+    assert_format::<crate::unix::Seconds>();
+    assert_format::<crate::ticks::TickDuration<1000>>();
+    #[cfg(feature = "types")]
+    assert_format::<crate::types::Duration>();
+}
+
+#[test]
+fn up_from_now_and_until() {
+    let counter = Counter::<i64>::up_from_now();
+    assert_eq!(counter, Counter::up(Some(0), Some(0)));
+    assert_eq!(counter.to_string(), "00:00:00");
+    assert!(!counter.is_finished());
+
+    let counter = Counter::until(90);
+    assert_eq!(counter, Counter::down(Some(0), Some(90)));
+    assert!(!counter.is_finished());
+
+    let counter = Counter::until(-90);
+    assert_eq!(counter.to_string(), "00:00:00");
+    assert!(counter.is_finished());
+    assert_eq!(counter.overtime(), Some(90));
+}
+
+#[test]
+fn down_for() {
+    use crate::unix::{Seconds, UnixSeconds};
+
+    let counter = Counter::<i64>::down_for(1500).unwrap();
+    assert_eq!(counter, Counter::down(Some(0), Some(1500)));
+    assert_eq!(counter.to_string(), "00:25:00");
+    assert!(Counter::<i64>::down_for(-1).unwrap().is_finished());
+    assert!(Counter::<UnixSeconds>::down_for(Seconds(i64::MAX)).is_err());
+}
+
+#[test]
+fn down_showing() {
+    let counter = Counter::<i64>::down_showing("01:30:00").unwrap();
+    assert_eq!(counter, Counter::down(Some(0), Some(5400)));
+    assert_eq!(counter.to_string(), "01:30:00");
+    assert_eq!(
+        Counter::<i64>::down_showing("123:00:05")
+            .unwrap()
+            .to_string(),
+        "123:00:05"
+    );
+}
+
+#[test]
+fn down_showing_rejects_invalid() {
+    let reject = |hms| Counter::<i64>::down_showing(hms).unwrap_err();
+    assert_eq!(
+        reject("00:60:00"),
+        DurationParserError::new(3, "must be below 60")
+    );
+    assert_eq!(
+        reject("00:00:60"),
+        DurationParserError::new(6, "must be below 60")
+    );
+    assert_eq!(
+        reject("00:0:00"),
+        DurationParserError::new(3, "expected two digits")
+    );
+    assert_eq!(reject("01:30"), DurationParserError::new(5, "expected ':'"));
+    assert_eq!(
+        reject(":30:00"),
+        DurationParserError::new(0, "expected a number")
+    );
+    assert_eq!(
+        reject("01:30:000"),
+        DurationParserError::new(8, "unexpected trailing input")
+    );
+    assert_eq!(reject("-01:00:00").position(), 0);
+}
+
+#[test]
+fn compare_with_duration() {
+    let counter = Counter::down(Some(-100), Some(300));
+    assert!(counter > 299);
+    assert!(counter == 300);
+    assert!(counter <= 300);
+    assert!(counter >= 300);
+    assert!(counter < 301);
+
+    let counter = Counter::up(Some(-10), Some(300));
+    assert!(counter == 10);
+    assert!(counter < 11 && counter > 9);
+
+    // Past the end, the remaining time is negative
+    let counter = Counter::down(Some(-10), Some(-5));
+    assert!(counter < 0);
+    assert!(counter == -5);
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn compare_timestamp_counter_with_duration() {
+    use crate::types::{Duration, TimeStamp};
+
+    let now = TimeStamp::now();
+    let counter = Counter::down(Some(now), Some(now + 600));
+    assert!(counter < Duration::seconds(600));
+    assert!(counter > Duration::seconds(590));
+}
+
+#[test]
+fn cmp_remaining() {
+    use std::cmp::Ordering;
+
+    let soon = Counter::down(Some(-100), Some(10));
+    let later = Counter::down(Some(-100), Some(20));
+    assert_eq!(soon.cmp_remaining(&later), Ordering::Less);
+    assert_eq!(later.cmp_remaining(&soon), Ordering::Greater);
+
+    // Same end, so the tie breaks by start
+    let early_start = Counter::down(Some(-200), Some(10));
+    assert_eq!(early_start.cmp_remaining(&soon), Ordering::Less);
+    assert_eq!(soon.cmp_remaining(&soon), Ordering::Equal);
+
+    // Up counters with an end after start have a deadline too
+    let up = Counter::up(Some(-100), Some(15));
+    assert_eq!(soon.cmp_remaining(&up), Ordering::Less);
+    assert_eq!(up.cmp_remaining(&later), Ordering::Less);
+
+    // Open-ended up counters are never due
+    let open = Counter::up(Some(-100), Some(-100));
+    assert_eq!(open.cmp_remaining(&later), Ordering::Greater);
+    assert_eq!(later.cmp_remaining(&open), Ordering::Less);
+}
+
+#[test]
+fn sort_by_urgency() {
+    let mut counters = vec![
+        Counter::up(Some(-5), Some(-10)),
+        Counter::down(Some(0), Some(30)),
+        Counter::up(Some(-5), Some(-20)),
+        Counter::down(Some(-50), Some(-1)),
+        Counter::down(Some(-60), Some(30)),
+        Counter::up(Some(-5), Some(20)),
+    ];
+    crate::sort_by_urgency(&mut counters);
+    assert_eq!(
+        counters,
+        vec![
+            Counter::down(Some(-50), Some(-1)),
+            Counter::up(Some(-5), Some(20)),
+            Counter::down(Some(-60), Some(30)),
+            Counter::down(Some(0), Some(30)),
+            Counter::up(Some(-5), Some(-20)),
+            Counter::up(Some(-5), Some(-10)),
+        ]
+    );
+}
+
+#[test]
+fn intervals() {
+    let counter = Counter::down(Some(0), Some(30));
+    assert_eq!(
+        counter.intervals(10).collect::<Vec<_>>(),
+        vec![0, 10, 20, 30]
+    );
+    assert_eq!(counter.interval_count(10), 4);
+
+    assert_eq!(
+        counter.intervals(7).collect::<Vec<_>>(),
+        vec![0, 7, 14, 21, 28]
+    );
+    assert_eq!(counter.interval_count(7), 5);
+
+    assert_eq!(counter.intervals(31).collect::<Vec<_>>(), vec![0]);
+    assert_eq!(Counter::<i64>::zero().interval_count(5), 1);
+}
+
+#[test]
+fn intervals_yield_nothing_when_invalid() {
+    let counter = Counter::down(Some(0), Some(30));
+    assert_eq!(counter.interval_count(0), 0);
+    assert_eq!(counter.interval_count(-10), 0);
+    assert_eq!(Counter::down(Some(30), Some(0)).interval_count(10), 0);
+}
+
+#[test]
+fn intervals_stop_on_overflow() {
+    use crate::unix::{Seconds, UnixSeconds};
+
+    let counter = Counter::up(Some(UnixSeconds(i64::MIN)), Some(UnixSeconds(i64::MAX)));
+    let times: Vec<_> = counter.intervals(Seconds(i64::MAX)).collect();
+    assert_eq!(
+        times,
+        vec![
+            UnixSeconds(i64::MIN),
+            UnixSeconds(-1),
+            UnixSeconds(i64::MAX - 1)
+        ]
+    );
+}
+
+#[test]
+fn map_times() {
+    let counter = Counter::up(Some(-90), Some(30)).map_times(|time| time * 2);
+    assert_eq!(counter, Counter::up(Some(-180), Some(60)));
+    assert_eq!(counter.to_string(), "00:03:00");
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn into_datetime_and_back() {
+    use crate::types::TimeStamp;
+    use ::chrono::{FixedOffset, Utc};
+
+    let start = TimeStamp::from_str("2023-01-01T12:00:00Z").unwrap();
+    let end = TimeStamp::from_str("2023-01-01T13:30:00Z").unwrap();
+    let counter = Counter::down(Some(start), Some(end));
+
+    let offset = FixedOffset::east_opt(5 * 3600).unwrap();
+    let local = Counter::down(Some(start), Some(end)).into_datetime(&offset);
+    assert_eq!(local.start.to_rfc3339(), "2023-01-01T17:00:00+05:00");
+    assert_eq!(local.end.to_rfc3339(), "2023-01-01T18:30:00+05:00");
+    assert_eq!(local.direction, Direction::Down);
+    assert_eq!(
+        local.end.with_timezone(&Utc),
+        ::chrono::DateTime::<Utc>::from(end)
+    );
+    assert_eq!(local.to_string(), "00:00:00");
+
+    assert_eq!(local.into_timestamps(), counter);
+}
+
+/// A time type with no string representation, `Default` or ordering, to
+/// check that counting only needs `Copy + Time + Sub`.
+mod bare_time {
+    use crate::*;
+
+    #[derive(Clone, Copy)]
+    struct Instant(i64);
+
+    impl Sub for Instant {
+        type Output = i64;
+
+        fn sub(self, other: Instant) -> i64 {
+            self.0 - other.0
+        }
+    }
+
+    impl Time for Instant {
+        type Duration = i64;
+
+        fn now() -> Self {
+            Instant(0)
+        }
+
+        fn add_seconds(self, duration: i64) -> Result<Self, TimeOverflow> {
+            self.0
+                .checked_add(duration)
+                .map(Instant)
+                .ok_or(TimeOverflow)
+        }
+    }
+
+    #[test]
+    fn counts_without_string_bounds() {
+        let mut counter = Counter::until(Instant(3725));
+        assert_eq!(counter.counter(), (1, 2, 5));
+        assert_eq!(
+            (counter.hours(), counter.minutes(), counter.seconds()),
+            (1, 62, 3725)
+        );
+        counter.try_move_end(60).unwrap();
+        counter.try_move_start(-10).unwrap();
+        assert_eq!(counter.to_string(), "01:03:05");
+        counter.flip();
+        assert_eq!(counter.to_string(), "00:00:10");
+
+        let built = CounterBuilder::new()
+            .start(Instant(0))
+            .lasting(30)
+            .build()
+            .unwrap();
+        assert_eq!(built.remaining(), 30);
+    }
+}
+
+/// A time type whose `Default` is not its `now`, to tell the two apart.
+mod later_now {
+    use crate::*;
+
+    #[derive(Debug, PartialEq, Default, Clone, Copy)]
+    struct Later(i64);
+
+    impl Sub for Later {
+        type Output = i64;
+
+        fn sub(self, other: Later) -> i64 {
+            self.0 - other.0
+        }
+    }
+
+    impl Time for Later {
+        type Duration = i64;
+
+        fn now() -> Self {
+            Later(1000)
+        }
+
+        fn add_seconds(self, duration: i64) -> Result<Self, TimeOverflow> {
+            self.0.checked_add(duration).map(Later).ok_or(TimeOverflow)
+        }
+    }
+
+    #[test]
+    fn missing_endpoints_are_now() {
+        let counter = Counter::down(None, Some(Later(1600)));
+        assert_eq!(counter.start, Later(1000));
+        assert_eq!(counter.to_string(), "00:10:00");
+        assert_eq!(Counter::down(Some(Later(400)), None).end, Later(1000));
+
+        let mut counter = Counter::up(Some(Later(400)), None);
+        assert_eq!(counter.to_string(), "00:10:00");
+        assert_eq!(counter.completion_time(), None);
+        counter.flip();
+        assert_eq!(counter.to_string(), "00:00:00");
+
+        let counter = Counter::up(None, None);
+        assert_eq!((counter.start, counter.end), (Later(1000), Later(1000)));
+        let counter = Counter::<Later>::up_since_hms(0, 1, 0).unwrap();
+        assert_eq!(counter.start, Later(940));
+        assert_eq!(counter.to_string(), "00:01:00");
+    }
+}
+
+/// A time type whose clock panics when read, to check which paths read it.
+mod no_clock {
+    use crate::*;
+    use std::fmt::{self, Display, Formatter};
+    use std::str::FromStr;
+
+    #[derive(Debug, PartialEq, Default, Clone, Copy)]
+    struct Unread(i64);
+
+    impl Sub for Unread {
+        type Output = i64;
+
+        fn sub(self, other: Unread) -> i64 {
+            self.0 - other.0
+        }
+    }
+
+    impl Time for Unread {
+        type Duration = i64;
+
+        fn now() -> Self {
+            panic!("the clock was read")
+        }
+
+        fn add_seconds(self, duration: i64) -> Result<Self, TimeOverflow> {
+            self.0.checked_add(duration).map(Unread).ok_or(TimeOverflow)
+        }
+    }
+
+    impl Display for Unread {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            write!(f, "{}", self.0)
+        }
+    }
+
+    impl FromStr for Unread {
+        type Err = std::num::ParseIntError;
+
+        fn from_str(string: &str) -> Result<Self, Self::Err> {
+            string.parse().map(Unread)
+        }
+    }
+
+    #[test]
+    fn given_endpoints_do_not_read_the_clock() {
+        let mut counter = Counter::down(Some(Unread(0)), Some(Unread(600)));
+        counter.try_move_end(60).unwrap();
+        counter.flip();
+        assert_eq!(counter.at(Unread(90)).total_seconds(), 90);
+        assert_eq!(counter.created_at(), None);
+        assert_eq!(Counter::up(Some(Unread(5)), None).end, Unread(5));
+        assert!(Counter::try_down(Unread(0), Unread(10)).is_ok());
+        assert!(Counter::try_up(Unread(0), Unread(10)).is_ok());
+        let built = CounterBuilder::new()
+            .start(Unread(0))
+            .lasting(30)
+            .build()
+            .unwrap();
+        assert_eq!(built.end, Unread(30));
+
+        counter
+            .to_file("/tmp/counter_test_file_no_clock.txt")
+            .unwrap();
+        let loaded = Counter::from_file("/tmp/counter_test_file_no_clock.txt").unwrap();
+        assert_eq!(loaded, counter);
+    }
+
+    #[test]
+    fn relative_counters_do_not_read_the_clock() {
+        let mut countdown = RelativeCounter::<Unread>::down(90).unwrap();
+        countdown.tick(30).unwrap();
+        assert_eq!(countdown.to_string(), "00:01:00");
+        assert_eq!((countdown.elapsed(), countdown.remaining()), (30, 60));
+        assert!(!countdown.is_finished());
+        assert!(format!("{:?}", countdown).contains("00:01:00"));
+
+        let mut stopwatch = RelativeCounter::<Unread>::up();
+        stopwatch.tick(5).unwrap();
+        assert_eq!(stopwatch.breakdown().total_seconds(), 5);
+
+        countdown
+            .to_file("/tmp/counter_test_file_no_clock_relative.txt")
+            .unwrap();
+        let loaded =
+            RelativeCounter::from_file("/tmp/counter_test_file_no_clock_relative.txt").unwrap();
+        assert_eq!(loaded, countdown);
+        countdown.rewind();
+        assert_eq!(countdown.counter(), (0, 1, 30));
+    }
+}
+
+#[cfg(feature = "types")]
+mod business {
+    use crate::business::Schedule;
+    use crate::types::TimeStamp;
+    use crate::*;
+    use ::chrono::{FixedOffset, NaiveTime, TimeZone, Utc, Weekday};
+
+    /// 2024-03-04 is a Monday
+    fn at(day: u32, hour: u32, minute: u32) -> TimeStamp {
+        Utc.with_ymd_and_hms(2024, 3, day, hour, minute, 0)
+            .unwrap()
+            .into()
+    }
+
+    fn time(hour: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, 0, 0).unwrap()
+    }
+
+    fn office() -> Schedule {
+        Schedule::new().weekdays(time(9), time(17))
+    }
+
+    #[test]
+    fn active_time_between() {
+        let office = office();
+        let hours = |from, to| office.active_between(from, to).num_seconds() as f64 / 3600.0;
+        // Thursday noon across the weekend to Monday noon
+        assert_eq!(hours(at(7, 12, 0), at(11, 12, 0)), 16.0);
+        // Across a night
+        assert_eq!(hours(at(7, 16, 0), at(8, 10, 0)), 2.0);
+        // Within a window, and outside of any
+        assert_eq!(hours(at(7, 10, 30), at(7, 11, 15)), 0.75);
+        assert_eq!(hours(at(9, 10, 0), at(10, 20, 0)), 0.0);
+        assert_eq!(hours(at(7, 17, 0), at(8, 9, 0)), 0.0);
+        // Whole weeks and then some, and backwards
+        assert_eq!(hours(at(4, 9, 0), at(25, 10, 0)), 121.0);
+        assert_eq!(hours(at(11, 12, 0), at(7, 12, 0)), -16.0);
+        assert_eq!(office.weekly_total().num_seconds(), 40 * 3600);
+    }
+
+    #[test]
+    fn windows_past_midnight_and_offsets() {
+        let night = Schedule::new().window(Weekday::Fri, time(22), time(2));
+        let active = night.active_between(at(8, 23, 0), at(9, 1, 0));
+        assert_eq!(active.num_seconds(), 2 * 3600);
+        assert_eq!(night.weekly_total().num_seconds(), 4 * 3600);
+
+        let berlin = FixedOffset::east_opt(2 * 3600).unwrap();
+        let office = Schedule::in_offset(berlin).weekdays(time(9), time(17));
+        let active = office.active_between(at(7, 6, 0), at(7, 16, 0));
+        assert_eq!(active.num_seconds(), 8 * 3600);
+    }
+
+    #[test]
+    fn counters_on_schedule() {
+        let office = office();
+        let mut deadline = Counter::down(Some(at(7, 12, 0)), Some(at(11, 12, 0)));
+        assert_eq!(
+            deadline.with_schedule(&office).at(at(7, 12, 0)).to_string(),
+            "16:00:00"
+        );
+        deadline.paused_at = Some(at(8, 16, 0));
+        let scheduled = deadline.with_schedule(&office);
+        assert_eq!(scheduled.to_string(), "04:00:00");
+        assert_eq!(scheduled.elapsed().num_seconds(), 12 * 3600);
+        assert_eq!(scheduled.remaining().num_seconds(), 4 * 3600);
+
+        deadline.paused_at = Some(at(12, 9, 0));
+        assert_eq!(deadline.with_schedule(&office).to_string(), "00:00:00");
+        deadline.flip();
+        assert_eq!(deadline.with_schedule(&office).to_string(), "21:00:00");
+    }
+}
+
+#[cfg(feature = "types")]
+mod recurring {
+    use crate::recurring::{Recurring, RecurringCounter};
+    use crate::types::TimeStamp;
+    use crate::*;
+    use ::chrono::{FixedOffset, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+
+    /// 2024-03-04 is a Monday
+    fn at(day: u32, hour: u32, minute: u32) -> TimeStamp {
+        Utc.with_ymd_and_hms(2024, 3, day, hour, minute, 0)
+            .unwrap()
+            .into()
+    }
+
+    fn standup() -> Recurring {
+        Recurring::weekly(Weekday::Mon, NaiveTime::from_hms_opt(9, 0, 0).unwrap())
+    }
+
+    #[test]
+    fn next_occurrence() {
+        let standup = standup();
+        assert_eq!(standup.weekday(), Weekday::Mon);
+        assert_eq!(standup.days(), 7);
+        // Exactly at the occurrence, earlier and later the same day
+        assert_eq!(standup.next_occurrence(at(4, 9, 0)), at(4, 9, 0));
+        assert_eq!(standup.next_occurrence(at(4, 8, 59)), at(4, 9, 0));
+        assert_eq!(standup.next_occurrence(at(4, 9, 1)), at(11, 9, 0));
+        // Rolling over into the next week, and over the month's end
+        assert_eq!(standup.next_occurrence(at(10, 23, 59)), at(11, 9, 0));
+        let april = TimeStamp::from(Utc.with_ymd_and_hms(2024, 4, 1, 9, 0, 0).unwrap());
+        assert_eq!(standup.next_occurrence(at(25, 12, 0)), april);
+        // Long before the first occurrence it is computed from
+        let early = TimeStamp::from(Utc.with_ymd_and_hms(1969, 12, 31, 0, 0, 0).unwrap());
+        let monday = TimeStamp::from(Utc.with_ymd_and_hms(1970, 1, 5, 9, 0, 0).unwrap());
+        assert_eq!(standup.next_occurrence(early), monday);
+    }
+
+    #[test]
+    fn every_few_days_and_offsets() {
+        let first = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let bins = Recurring::every_days(3, first, NaiveTime::from_hms_opt(7, 0, 0).unwrap());
+        assert_eq!(bins.next_occurrence(at(4, 7, 0)), at(4, 7, 0));
+        assert_eq!(bins.next_occurrence(at(5, 7, 0)), at(7, 7, 0));
+        let daily = Recurring::daily(NaiveTime::from_hms_opt(18, 30, 0).unwrap());
+        assert_eq!(daily.next_occurrence(at(5, 19, 0)), at(6, 18, 30));
+        assert_eq!(Recurring::every_days(0, first, daily.time()).days(), 1);
+        // Nothing happens before the first occurrence
+        assert_eq!(bins.next_occurrence(at(1, 7, 0)), at(1, 7, 0));
+        let february = TimeStamp::from(Utc.with_ymd_and_hms(2024, 2, 27, 7, 0, 0).unwrap());
+        assert_eq!(bins.next_occurrence(february), at(1, 7, 0));
+
+        let berlin = FixedOffset::east_opt(2 * 3600).unwrap();
+        let standup = standup().in_offset(berlin);
+        assert_eq!(standup.next_occurrence(at(4, 6, 0)), at(4, 7, 0));
+        // Already Monday in Berlin
+        assert_eq!(standup.next_occurrence(at(10, 23, 0)), at(11, 7, 0));
+    }
+
+    #[test]
+    fn recurring_counter() {
+        let counter = RecurringCounter::new(standup());
+        assert_eq!(counter.target(), standup());
+        assert_eq!(counter.at(at(4, 9, 0)).to_string(), "00:00:00");
+        assert_eq!(counter.at(at(4, 8, 0)).to_string(), "01:00:00");
+        assert_eq!(counter.end_at(at(4, 10, 0)), at(11, 9, 0));
+        assert_eq!(counter.remaining_at(at(10, 9, 0)).num_seconds(), 86400);
+        assert!(counter.remaining().num_seconds() <= 7 * 86400);
+        assert!(counter.end() >= TimeStamp::now());
+    }
+
+    #[test]
+    fn recurring_counter_file_round_trip() {
+        let first = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let time = NaiveTime::from_hms_milli_opt(7, 0, 0, 250).unwrap();
+        let berlin = FixedOffset::east_opt(3600).unwrap();
+        let counter =
+            RecurringCounter::new(Recurring::every_days(3, first, time).in_offset(berlin));
+        let path = "/tmp/counter_test_file_recurring.txt";
+        counter.to_file(path).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(path).unwrap(),
+            "2024-03-01T07:00:00.250\n3\n3600"
+        );
+        assert_eq!(RecurringCounter::from_file(path).unwrap(), counter);
+
+        let weekly = RecurringCounter::new(standup());
+        weekly.to_file(path).unwrap();
+        assert_eq!(RecurringCounter::from_file(path).unwrap(), weekly);
+
+        std::fs::write(path, "2024-03-01T07:00:00\n0\n0").unwrap();
+        assert!(RecurringCounter::from_file(path).is_err());
+        std::fs::write(path, "2024-03-01T07:00:00\n3\n0\nextra").unwrap();
+        assert!(RecurringCounter::from_file(path).is_err());
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serialize {
+    use crate::*;
+    use serde_json::{from_str, json, to_string, to_value};
+
+    #[test]
+    fn counter_round_trip() {
+        let mut counter = Counter::up(Some(-30), Some(60)).stamped(-40);
+        counter.paused_at = Some(-20);
+        counter.lap().unwrap();
+        counter.clamp_to_end(true);
+        counter.set_rounding(Rounding::Nearest);
+        counter.try_move_end(5).unwrap();
+        let json = to_string(&counter).unwrap();
+        let loaded: Counter<i64> = from_str(&json).unwrap();
+        assert_eq!(loaded, counter);
+        assert_eq!(loaded.laps(), counter.laps());
+        assert!(loaded.clamps_to_end());
+        assert_eq!(loaded.rounding(), Rounding::Nearest);
+        assert_eq!(loaded.created_at(), Some(-40));
+        assert_eq!(loaded.modified_at(), counter.modified_at());
+        assert_eq!(loaded.initial_end, 60);
+    }
+
+    #[test]
+    fn counter_shape() {
+        let counter = Counter::down(Some(0), Some(600));
+        assert_eq!(
+            to_value(&counter).unwrap(),
+            json!({
+                "version": 1, "start": 0, "end": 600, "direction": "Down",
+                "initial_start": 0, "initial_end": 600, "initial_direction": "Down",
+                "laps": [], "snoozes": 0, "stop_at_end": false, "auto_flip": false,
+                "rounding": "Floor",
+            })
+        );
+        let minimal: Counter<i64> =
+            from_str(r#"{"version":1,"start":0,"end":600,"direction":"Down"}"#).unwrap();
+        assert_eq!(minimal, counter);
+        assert_eq!((minimal.initial_start, minimal.initial_end), (0, 600));
+    }
+
+    #[test]
+    fn counter_rejects_bad_keys() {
+        let error = |json: &str| from_str::<Counter<i64>>(json).unwrap_err().to_string();
+        assert!(
+            error(r#"{"version":1,"start":0,"start":1,"end":600,"direction":"Down"}"#)
+                .contains("duplicate field `start`")
+        );
+        assert!(
+            error(r#"{"version":1,"start":0,"end":600,"direction":"Down","alarm":5}"#)
+                .contains("unknown field `alarm`")
+        );
+        assert!(
+            error(r#"{"version":2,"start":0,"end":600,"direction":"Down"}"#)
+                .contains("unsupported version 2, expected 1")
+        );
+        assert!(error(r#"{"start":0,"end":600,"direction":"Down"}"#)
+            .contains("missing field `version`"));
+    }
+
+    #[test]
+    fn set_round_trip() {
+        let mut set = CounterSet::new();
+        set.insert_with(
+            "tea",
+            Counter::down(Some(0), Some(180)),
+            Metadata {
+                label: Some("Green tea".into()),
+                tags: vec!["kitchen".into(), "hot".into()],
+                note: Some("two\nlines".into()),
+            },
+        );
+        set.insert("bread", Counter::up(Some(-5), None));
+        let json = to_value(&set).unwrap();
+        assert_eq!(json["version"], 1);
+        assert_eq!(json["counters"]["tea"]["label"], "Green tea");
+        assert_eq!(json["counters"]["bread"].get("tags"), None);
+        let loaded: CounterSet<i64> = from_str(&to_string(&set).unwrap()).unwrap();
+        assert_eq!(loaded, set);
+        assert_eq!(loaded.metadata("tea"), set.metadata("tea"));
+        let names: Vec<_> = loaded.iter().map(|(name, _)| name).collect();
+        assert_eq!(names, ["tea", "bread"]);
+    }
+
+    #[test]
+    fn set_rejects_duplicate_names() {
+        let counter = r#"{"counter":{"version":1,"start":0,"end":1,"direction":"Down"}}"#;
+        let json = format!(
+            r#"{{"version":1,"counters":{{"tea":{},"tea":{}}}}}"#,
+            counter, counter
+        );
+        let error = from_str::<CounterSet<i64>>(&json).unwrap_err();
+        assert!(error.to_string().contains("duplicate counter name `tea`"));
+        let json = format!(
+            r#"{{"version":1,"counters":{{"tea":{}}},"extra":0}}"#,
+            counter
+        );
+        assert!(from_str::<CounterSet<i64>>(&json).is_err());
+        let json = format!(r#"{{"version":3,"counters":{{"tea":{}}}}}"#, counter);
+        assert!(from_str::<CounterSet<i64>>(&json).is_err());
+    }
+
+    #[cfg(feature = "types")]
+    #[test]
+    fn timestamp_round_trip() {
+        use crate::types::{TimeOfDay, TimeStamp};
+        let now = TimeStamp::now();
+        let counter = Counter::down(Some(now), Some(now + 600));
+        let loaded: Counter<TimeStamp> = from_str(&to_string(&counter).unwrap()).unwrap();
+        assert_eq!(loaded, counter);
+        let counter = Counter::down(Some(TimeOfDay::now()), None);
+        let loaded: Counter<TimeOfDay> = from_str(&to_string(&counter).unwrap()).unwrap();
+        assert_eq!(loaded, counter);
+    }
+}
+
+#[cfg(feature = "tokio")]
+mod expiry {
+    use crate::*;
+    use futures_util::StreamExt;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+    use tokio::time::{sleep, timeout, Duration, Instant};
+
+    /// Whole seconds on tokio's clock, which the tests pause, so that it only
+    /// moves forward while every task waits
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+    struct Virtual(i64);
+
+    thread_local! {
+        static START: Instant = Instant::now();
+    }
+
+    impl Sub for Virtual {
+        type Output = i64;
+
+        fn sub(self, other: Virtual) -> i64 {
+            self.0 - other.0
+        }
+    }
+
+    impl Time for Virtual {
+        type Duration = i64;
+
+        fn now() -> Self {
+            START.with(|start| Virtual(start.elapsed().as_secs() as i64))
+        }
+
+        fn add_seconds(self, duration: i64) -> Result<Self, TimeOverflow> {
+            self.0
+                .checked_add(duration)
+                .map(Virtual)
+                .ok_or(TimeOverflow)
+        }
+    }
+
+    fn event(name: &str, end: i64) -> Option<ExpiryEvent<Virtual>> {
+        Some(ExpiryEvent {
+            name: name.into(),
+            end: Virtual(end),
+        })
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn sooner_counter_added_mid_wait_fires_first() {
+        let set = Arc::new(RwLock::new(CounterSet::new()));
+        set.write()
+            .await
+            .insert("late", Counter::down(None, Some(Virtual(100))));
+        let mut events = Box::pin(CounterSet::expiry_stream(Arc::clone(&set)));
+        let (first, ()) = tokio::join!(events.next(), async {
+            sleep(Duration::from_secs(10)).await;
+            let soon = Counter::down(None, Some(Virtual(30)));
+            set.write().await.insert("soon", soon);
+        });
+        assert_eq!(first, event("soon", 30));
+        assert_eq!(Virtual::now(), Virtual(30));
+        assert_eq!(events.next().await, event("late", 100));
+        assert_eq!(Virtual::now(), Virtual(100));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn adjusted_and_removed_counters() {
+        let mut set = CounterSet::new();
+        set.insert(
+            "done",
+            Counter::down(Some(Virtual(-20)), Some(Virtual(-10))),
+        );
+        set.insert("tea", Counter::down(None, Some(Virtual(100))));
+        set.insert("eggs", Counter::down(None, Some(Virtual(200))));
+        set.insert("open", Counter::up(None, None));
+        let set = Arc::new(RwLock::new(set));
+        let mut events = Box::pin(CounterSet::expiry_stream(Arc::clone(&set)));
+        let (first, ()) = tokio::join!(events.next(), async {
+            sleep(Duration::from_secs(10)).await;
+            set.write().await.remove("tea");
+            sleep(Duration::from_secs(10)).await;
+            set.write()
+                .await
+                .get_mut("eggs")
+                .unwrap()
+                .try_move_end(-150)
+                .unwrap();
+        });
+        assert_eq!(first, event("eggs", 50));
+        assert_eq!(Virtual::now(), Virtual(50));
+
+        // Moved to a new `end`, a counter fires again
+        set.write()
+            .await
+            .get_mut("eggs")
+            .unwrap()
+            .try_move_end(10)
+            .unwrap();
+        assert_eq!(events.next().await, event("eggs", 60));
+        assert!(timeout(Duration::from_secs(3600), events.next())
+            .await
+            .is_err());
+    }
+}
+
+#[cfg(feature = "types")]
+mod time_of_day {
+    use crate::types::{Duration, TimeOfDay};
+    use crate::*;
+    use std::str::FromStr;
+
+    fn at(string: &str) -> TimeOfDay {
+        TimeOfDay::from_str(string).unwrap()
+    }
+
+    #[test]
+    fn difference_wraps_at_midnight() {
+        assert_eq!(
+            at("23:00:00") - at("07:00:00"),
+            Duration::seconds(16 * 3600)
+        );
+        assert_eq!(at("07:00:00") - at("23:00:00"), Duration::seconds(8 * 3600));
+        assert_eq!(at("07:00:00") - at("07:00:00"), Duration::seconds(0));
+        assert_eq!(
+            at("00:00:00").add_seconds(Duration::seconds(-1)).unwrap(),
+            at("23:59:59")
+        );
+    }
+
+    #[test]
+    fn display_and_parse() {
+        assert_eq!(at("07:05:09").to_string(), "07:05:09");
+        let error = TimeOfDay::from_str("7am").unwrap_err();
+        assert_eq!(error.tried(), ["%H:%M:%S"]);
+        assert!(TimeOfDay::from_str("24:00:00").is_err());
+    }
+
+    #[test]
+    fn down_to_earlier_target_wraps_to_tomorrow() {
+        let hour_ago = TimeOfDay::now()
+            .add_seconds(Duration::seconds(-3600))
+            .unwrap();
+        let counter = Counter::down_to(hour_ago);
+        assert!((23 * 3600 - 1..=23 * 3600).contains(&counter.seconds()));
+
+        let counter = Counter::down(Some(hour_ago), Some(hour_ago));
+        assert_eq!(counter.end - counter.start, Duration::seconds(0));
+    }
+
+    #[test]
+    fn down_to_now_starts_finished() {
+        let now = TimeOfDay::now();
+        let counter = Counter::down_to(now);
+        assert_eq!(counter.end, now);
+        assert_eq!(counter.at(now).to_string(), "00:00:00");
+        let second_later = now.add_seconds(Duration::seconds(1)).unwrap();
+        assert_eq!(counter.at(second_later).to_string(), "-00:00:01");
+        assert_eq!(counter.state_at(second_later), CounterState::Finished);
+    }
+
+    #[test]
+    fn running_countdown_is_not_finished() {
+        let mut counter = Counter::down(Some(at("10:00:00")), Some(at("11:00:00")));
+        assert_eq!(counter.state_at(at("10:00:01")), CounterState::Running);
+        assert!(!counter.is_finished_at(at("10:59:59")));
+        assert_eq!(
+            counter.remaining_at(at("10:30:00")),
+            Duration::seconds(1800)
+        );
+        assert_eq!(counter.at(at("10:30:00")).to_string(), "00:30:00");
+
+        assert!(counter.is_finished_at(at("11:00:00")));
+        assert_eq!(counter.state_at(at("12:00:00")), CounterState::Finished);
+        assert_eq!(
+            counter.remaining_at(at("12:00:00")),
+            Duration::seconds(-3600)
+        );
+        assert_eq!(counter.at(at("12:00:00")).to_string(), "-01:00:00");
+
+        counter.paused_at = Some(at("10:15:00"));
+        assert_eq!(counter.eta(), Some(Duration::seconds(2700)));
+        assert!(!counter.in_final_seconds(60));
+        assert_eq!(counter.overtime(), None);
+    }
+
+    #[test]
+    fn countdown_across_midnight() {
+        let counter = Counter::down(Some(at("23:30:00")), Some(at("00:30:00")));
+        assert_eq!(counter.state_at(at("23:45:00")), CounterState::Running);
+        assert_eq!(counter.remaining_at(at("00:15:00")), Duration::seconds(900));
+        assert!(counter.is_finished_at(at("00:30:00")));
+        assert!(counter.is_finished_at(at("01:00:00")));
+    }
+
+    #[test]
+    fn set_filters_running_countdowns() {
+        let mut set = CounterSet::new();
+        let mut running = Counter::down(Some(at("10:00:00")), Some(at("11:00:00")));
+        running.paused_at = Some(at("10:30:00"));
+        let mut done = Counter::down(Some(at("08:00:00")), Some(at("09:00:00")));
+        done.paused_at = Some(at("10:30:00"));
+        set.insert("running", running);
+        set.insert("done", done);
+        let names = |entries: SetEntries<'_, TimeOfDay>| {
+            entries
+                .map(|(name, _)| name.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(set.running()), ["running"]);
+        assert_eq!(names(set.finished()), ["done"]);
+        assert_eq!(set.expired(), ["done"]);
+    }
+
+    #[test]
+    fn file_round_trip() {
+        let counter = Counter::down(Some(at("06:00:00")), Some(at("07:00:00")));
+        counter
+            .to_file("/tmp/counter_test_file_time_of_day.txt")
+            .unwrap();
+        assert_eq!(
+            Counter::from_file("/tmp/counter_test_file_time_of_day.txt").unwrap(),
+            counter
+        );
+    }
+}
+
+#[test]
+fn try_constructors() {
+    assert_eq!(
+        Counter::try_down(0, 10),
+        Ok(Counter::down(Some(0), Some(10)))
+    );
+    assert_eq!(Counter::try_up(-10, 0), Ok(Counter::up(Some(-10), Some(0))));
+    assert_eq!(Counter::try_down(5, 5), Ok(Counter::down(Some(5), Some(5))));
+
+    let error = Counter::try_down(10, -3).unwrap_err();
+    assert_eq!((error.start(), error.end()), ("10", "-3"));
+    assert_eq!(error.to_string(), "Counter ends (-3) before it starts (10)");
+    assert!(Counter::try_up(1, 0).is_err());
+}
+
+#[test]
+fn pause_and_resume() {
+    let mut counter = Counter::down(Some(-10), Some(100));
+    assert!(!counter.is_paused());
+    counter.pause();
+    assert_eq!(counter.paused_at, Some(0));
+    assert!(counter.is_paused());
+
+    // Paused 50 seconds ago, so the value from then is still shown
+    counter.paused_at = Some(-50);
+    assert_eq!(counter.to_string(), "00:02:30");
+    assert_eq!(counter.seconds(), 150);
+    // Pausing again does not move the pause moment
+    counter.pause();
+    assert_eq!(counter.paused_at, Some(-50));
+
+    counter.resume().unwrap();
+    assert!(!counter.is_paused());
+    assert_eq!(counter.end, 150);
+    assert_eq!(counter.to_string(), "00:02:30");
+    // Resuming again does nothing
+    counter.resume().unwrap();
+    assert_eq!(counter.end, 150);
+
+    let mut counter = Counter::up(Some(-10), None);
+    counter.paused_at = Some(-4);
+    assert_eq!(counter.seconds(), 6);
+    counter.resume().unwrap();
+    assert_eq!(counter.start, -6);
+    assert_eq!(counter.seconds(), 6);
+}
+
+#[test]
+fn resume_overflow_stays_paused() {
+    let mut counter = Counter::down(Some(0), Some(i64::MAX));
+    counter.paused_at = Some(-1);
+    assert!(counter.resume().is_err());
+    assert!(counter.is_paused());
+    assert_eq!(counter.end, i64::MAX);
+}
+
+#[test]
+fn paused_file_round_trip() {
+    let mut counter = Counter::down(Some(-10), Some(100));
+    counter.paused_at = Some(-5);
+    counter
+        .to_file("/tmp/counter_test_file_paused.txt")
+        .unwrap();
+    assert_eq!(
+        std::fs::read_to_string("/tmp/counter_test_file_paused.txt").unwrap(),
+        "-10\n100\nDown\npaused_at=-5"
+    );
+    assert_eq!(
+        Counter::from_file("/tmp/counter_test_file_paused.txt").unwrap(),
+        counter
+    );
+
+    std::fs::write(
+        "/tmp/counter_test_file_paused.txt",
+        "-10\n100\nDown\nlabel=tea",
+    )
+    .unwrap();
+    let error = Counter::<i64>::from_file("/tmp/counter_test_file_paused.txt").unwrap_err();
+    assert_eq!(error.to_string(), "File contains unknown counter data");
+}
+
+#[test]
+fn clocks() {
+    use crate::clock::{Clock, FixedClock, SystemClock, TestClock};
+
+    let counter = Counter::down(Some(0), Some(3600));
+    assert_eq!(counter.with_clock(FixedClock(600)).to_string(), "00:50:00");
+    assert_eq!(counter.with_clock(SystemClock).to_string(), "01:00:00");
+
+    let clock = TestClock::new(0);
+    let clocked = counter.with_clock(&clock);
+    assert_eq!(clocked.to_string(), "01:00:00");
+    clock.advance(61).unwrap();
+    assert_eq!(clocked.to_string(), "00:58:59");
+    assert_eq!(clocked.counter(), (0, 58, 59));
+    clock.set(3700);
+    assert_eq!(clocked.to_string(), "00:00:00");
+    assert_eq!(clocked.breakdown().total_seconds(), -100);
+
+    clock.set(i64::MAX);
+    assert!(clock.advance(1).is_err());
+    assert_eq!(clock.now(), i64::MAX);
+}
+
+#[test]
+fn total() {
+    let counter = Counter::down(Some(-600), Some(900));
+    assert_eq!(counter.total(), 1500);
+    assert_eq!(counter.elapsed() + counter.remaining(), counter.total());
+    assert_eq!(Counter::down(Some(0), Some(0)).total(), 0);
+    assert_eq!(Counter::up(Some(10), Some(-10)).total(), 0);
+}
+
+#[test]
+fn at_reference() {
+    let counter = Counter::down(Some(-100), Some(3600));
+    assert_eq!(counter.at(1800).to_string(), "00:30:00");
+    assert_eq!(counter.at(3690).total_seconds(), -90);
+    assert_eq!(counter.remaining_at(1800), 1800);
+    assert_eq!(counter.elapsed_at(1800), 1900);
+    assert_eq!(counter.remaining(), 3600);
+    assert_eq!(counter.elapsed(), 100);
+    assert_eq!(counter.at(0), counter.breakdown());
+
+    let mut counter = Counter::up(Some(-100), None);
+    assert_eq!(counter.at(-160).total_seconds(), -60);
+    counter.paused_at = Some(-40);
+    assert_eq!(counter.at(1000).total_seconds(), 60);
+    assert_eq!(counter.elapsed_at(1000), 60);
+}
+
+#[test]
+fn breakdown() {
+    let reading = Counter::down(None, Some(86400)).breakdown();
+    assert_eq!(
+        reading,
+        CounterReading {
+            days: 1,
+            hours: 0,
+            minutes: 0,
+            seconds: 0,
+            negative: false
+        }
+    );
+    assert_eq!(reading.to_string(), "1d 00:00:00");
+
+    let reading = Counter::down(None, Some(86399)).breakdown();
+    assert_eq!((reading.days, reading.hours), (0, 23));
+    assert_eq!(reading.to_string(), "23:59:59");
+    assert_eq!(reading.total_seconds(), 86399);
+
+    let counter = Counter::up(Some(-(3 * 86400 + 4 * 3600 + 5 * 60 + 6)), None);
+    let reading = counter.breakdown();
+    assert_eq!(reading.to_string(), "3d 04:05:06");
+    assert_eq!(reading.total_seconds(), counter.seconds());
+    assert_eq!(counter.counter(), (76, 5, 6));
+}
+
+#[test]
+fn breakdown_negative() {
+    let reading = Counter::down(None, Some(-90061)).breakdown();
+    assert!(reading.negative);
+    assert_eq!(reading.to_string(), "-1d 01:01:01");
+    assert_eq!(reading.total_seconds(), -90061);
+    assert_eq!(Counter::down(None, Some(-90061)).counter(), (0, 0, 0));
+
+    let reading = CounterReading::new(i64::MIN);
+    assert_eq!(reading.total_seconds(), i64::MIN);
+}
+
+#[test]
+fn signed_readings() {
+    let counter = Counter::down(Some(-200), Some(-90));
+    assert_eq!(counter.seconds(), 0);
+    assert_eq!(counter.signed_seconds(), -90);
+    assert_eq!(counter.signed_counter(), (0, -1, -30));
+
+    let counter = Counter::up(Some(3725), None);
+    assert_eq!(counter.counter(), (0, 0, 0));
+    assert_eq!(counter.signed_counter(), (-1, -2, -5));
+
+    let counter = Counter::up(Some(-3725), None);
+    assert_eq!(counter.signed_seconds(), 3725);
+    assert_eq!(counter.signed_counter(), counter.counter());
+}
+
+#[test]
+fn swap() {
+    let mut counter = Counter::down(Some(90), Some(-30));
+    assert_eq!(counter.to_string(), "00:00:00");
+    counter.swap();
+    assert_eq!((counter.start, counter.end), (-30, 90));
+    assert_eq!(counter.to_string(), "00:01:30");
+
+    let counter = Counter::down(Some(90), Some(-30)).swapped();
+    assert_eq!((counter.start, counter.end), (-30, 90));
+}
+
+#[test]
+fn swap_and_flip() {
+    // Symmetric around now
+    let mut counter = Counter::down(Some(-60), Some(60));
+    assert_eq!(counter.to_string(), "00:01:00");
+    counter.flip();
+    assert_eq!(counter.to_string(), "00:01:00");
+    counter.flip();
+
+    counter.swap();
+    counter.flip();
+    assert_eq!(counter.to_string(), "00:00:00");
+
+    let mut finished = Counter::down(Some(-100), Some(-40));
+    finished.swap();
+    finished.flip();
+    assert_eq!(finished.to_string(), "00:00:40");
+}
+
+#[test]
+fn snapshot_stays_put() {
+    let mut counter = Counter::down(Some(-5), Some(600));
+    let snapshot = counter.snapshot();
+    counter.paused_at = Some(300);
+    assert_eq!(counter.to_string(), "00:05:00");
+    assert_eq!(snapshot.to_string(), "00:10:00");
+    assert_eq!(snapshot.counter(), (0, 10, 0));
+    assert_eq!(snapshot.direction, Direction::Down);
+}
+
+#[test]
+fn snapshot_line_round_trip() {
+    let counter = Counter::up(Some(-(3 * 86400 + 4 * 3600 + 5 * 60 + 6)), Some(0));
+    let snapshot = counter.snapshot();
+    assert_eq!(snapshot.to_line(), "Up 3d 04:05:06");
+    assert_eq!(snapshot.to_string(), "76:05:06");
+    assert_eq!(
+        snapshot.to_line().parse::<FrozenCounter>().unwrap(),
+        snapshot
+    );
+
+    let overdue = Counter::down(Some(-70), Some(-10)).snapshot();
+    assert_eq!(overdue.to_line(), "Down -00:00:10");
+    assert_eq!(overdue.to_string(), "00:00:00");
+    assert_eq!(overdue.to_line().parse::<FrozenCounter>().unwrap(), overdue);
+
+    for bad in [
+        "",
+        "Down",
+        "Sideways 00:00:01",
+        "Up 1:2:3",
+        "Up xd 00:00:01",
+    ] {
+        assert_eq!(bad.parse::<FrozenCounter>(), Err(InvalidSnapshot));
+    }
+}
+
+#[test]
+fn clone_is_independent() {
+    let original = Counter::down(Some(0), Some(600));
+    let mut copy = original.clone();
+    copy.try_move_end(60).unwrap();
+    assert_eq!(copy.end, 660);
+    assert_eq!(original.end, 600);
+    assert_ne!(copy, original);
+
+    // Laps are copied too, not shared
+    let mut original = Counter::up(Some(-30), None);
+    original.laps.push(-20);
+    let mut copy = original.clone();
+    copy.laps.push(-10);
+    assert_eq!(original.laps(), [-20]);
+    assert_eq!(copy.laps(), [-20, -10]);
+}
+
+#[test]
+fn ticks_end_at_zero_or_when_paused() {
+    let finished = Counter::down(Some(-10), Some(0));
+    let readings: Vec<String> = finished.ticks().map(|r| r.to_string()).collect();
+    assert_eq!(readings, ["00:00:00"]);
+
+    let mut paused = Counter::up(Some(-90), None);
+    paused.paused_at = Some(0);
+    let readings: Vec<String> = paused.ticks().map(|r| r.to_string()).collect();
+    assert_eq!(readings, ["00:01:30"]);
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn ticks_follow_the_counter() {
+    use crate::types::TimeStamp;
+
+    let counter = Counter::<TimeStamp>::down_for(::chrono::Duration::milliseconds(1500)).unwrap();
+    let readings: Vec<String> = counter.ticks().map(|r| r.to_string()).collect();
+    assert_eq!(readings, ["00:00:01", "00:00:00"]);
+}
+
+#[test]
+fn subsec_nanos() {
+    assert_eq!(i64::seconds(5).subsec_nanos(), 0);
+    let ticks = crate::ticks::TickDuration::<4>(-6);
+    assert_eq!(ticks.subsec_nanos(), -500_000_000);
+}
+
+#[test]
+fn from_hms_round_trip() {
+    for shown in ["00:00:00", "00:09:59", "01:30:00", "123:45:06"] {
+        for direction in [Direction::Down, Direction::Up] {
+            let counter = Counter::<i64>::from_hms(shown, direction).unwrap();
+            assert_eq!(counter.to_string(), shown);
+        }
+    }
+}
+
+#[test]
+fn from_hms_lenient_forms() {
+    let shown = |hms| Counter::<i64>::from_hms(hms, Direction::Down).map(|c| c.to_string());
+    assert_eq!(shown("1:2:3").unwrap(), "01:02:03");
+    assert_eq!(shown("05:30").unwrap(), "00:05:30");
+    assert_eq!(shown("5:3").unwrap(), "00:05:03");
+    assert_eq!(shown("100:00:00").unwrap(), "100:00:00");
+
+    for (bad, position, reason) in [
+        ("1:2:x", 4, "expected a number"),
+        ("90", 2, "expected ':'"),
+        ("1:60:00", 2, "must be below 60"),
+        ("75:00", 0, "must be below 60"),
+        ("1:234:00", 2, "expected at most two digits"),
+        ("1:00:00:00", 7, "unexpected trailing input"),
+        ("", 0, "expected a number"),
+    ] {
+        let error = shown(bad).unwrap_err();
+        assert_eq!(
+            (error.position(), error.reason()),
+            (position, reason),
+            "{bad}"
+        );
+    }
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn parse_spec() {
+    use crate::types::TimeStamp;
+
+    let at = |s: &str| s.parse::<TimeStamp>().unwrap();
+    let now = at("2025-06-01T12:00:00Z");
+    for (spec, direction, start, end) in [
+        ("25m", Direction::Down, now, at("2025-06-01T12:25:00Z")),
+        (
+            "down 1h30m",
+            Direction::Down,
+            now,
+            at("2025-06-01T13:30:00Z"),
+        ),
+        (
+            "  DOWN   90S ",
+            Direction::Down,
+            now,
+            at("2025-06-01T12:01:30Z"),
+        ),
+        (
+            "down 1h 30m",
+            Direction::Down,
+            now,
+            at("2025-06-01T13:30:00Z"),
+        ),
+        ("Up 10m", Direction::Up, now, at("2025-06-01T12:10:00Z")),
+        (
+            "down PT5M",
+            Direction::Down,
+            now,
+            at("2025-06-01T12:05:00Z"),
+        ),
+        (
+            "until 13:15",
+            Direction::Down,
+            now,
+            at("2025-06-01T13:15:00Z"),
+        ),
+        (
+            "until 09:00",
+            Direction::Down,
+            now,
+            at("2025-06-02T09:00:00Z"),
+        ),
+        (
+            "UNTIL 2025-06-03T00:00:00Z",
+            Direction::Down,
+            now,
+            at("2025-06-03T00:00:00Z"),
+        ),
+        (
+            "up until 2025-06-01 18:00",
+            Direction::Up,
+            now,
+            at("2025-06-01T18:00:00Z"),
+        ),
+        (
+            "up from 09:00",
+            Direction::Up,
+            at("2025-06-01T09:00:00Z"),
+            at("2025-06-01T09:00:00Z"),
+        ),
+        (
+            "from 13:00:30",
+            Direction::Up,
+            at("2025-05-31T13:00:30Z"),
+            at("2025-05-31T13:00:30Z"),
+        ),
+    ] {
+        let counter = Counter::parse_spec_at(spec, now).unwrap();
+        assert_eq!(
+            (counter.direction, counter.start, counter.end),
+            (direction, start, end),
+            "{spec}"
+        );
+    }
+
+    for (spec, token, reason) in [
+        ("", "", "expected a duration, `until` or `from`"),
+        ("down", "", "expected a duration, `until` or `from`"),
+        ("down 25x", "25x", "unknown unit"),
+        ("up -5m", "-5m", "duration must not be negative"),
+        ("until", "until", "expected a time"),
+        ("until noon", "noon", "expected a time or HH:MM"),
+        (
+            "down from 09:00",
+            "from",
+            "only counting up can start at a time",
+        ),
+        ("sideways 5m", "sideways 5m", "expected a number"),
+    ] {
+        let error = Counter::parse_spec_at(spec, now).unwrap_err();
+        assert_eq!((error.token(), error.reason()), (token, reason), "{spec}");
+    }
+}
+
+#[test]
+fn move_by_units() {
+    let mut counter = Counter::down(Some(0), Some(0));
+    counter.move_end_by(Adjust::Hours(2)).unwrap();
+    counter.move_end_by(Adjust::Minutes(-30)).unwrap();
+    counter.move_start_by(Adjust::Days(-1)).unwrap();
+    counter.move_start_by(Adjust::Seconds(15)).unwrap();
+    assert_eq!((counter.start, counter.end), (-86385, 5400));
+
+    assert!(counter.move_end_by(Adjust::Days(i64::MAX)).is_err());
+    let mut counter = Counter::down(Some(0), Some(i64::MAX - 60));
+    assert!(counter.move_end_by(Adjust::Minutes(2)).is_err());
+    assert_eq!(counter.end, i64::MAX - 60);
+}
+
+#[test]
+fn completion_time_and_eta() {
+    let countdown = Counter::down(Some(-60), Some(30));
+    assert_eq!(countdown.completion_time(), Some(30));
+    assert_eq!(countdown.eta(), Some(30));
+
+    let finished = Counter::down(Some(-60), Some(0));
+    assert_eq!(finished.completion_time(), Some(0));
+    assert_eq!(finished.eta(), None);
+    assert_eq!(Counter::down(Some(-60), Some(-30)).eta(), None);
+
+    let goal = Counter::up(Some(-10), Some(50));
+    assert_eq!((goal.completion_time(), goal.eta()), (Some(50), Some(50)));
+    let open = Counter::up(Some(-10), Some(-10));
+    assert_eq!((open.completion_time(), open.eta()), (None, None));
+
+    let mut paused = Counter::down(Some(-60), Some(30));
+    paused.paused_at = Some(-20);
+    assert_eq!(paused.eta(), Some(50));
+}
+
+#[test]
+fn scheduled_start() {
+    for counter in [
+        Counter::up(Some(90), None),
+        Counter::down(Some(90), Some(690)),
+    ] {
+        assert!(!counter.has_started());
+        assert_eq!(counter.starts_in(), Some(90));
+        assert_eq!(counter.display_prestart().to_string(), "-00:01:30");
+    }
+    assert_eq!(Counter::up(Some(90), None).to_string(), "00:00:00");
+    assert_eq!(Counter::down(Some(90), Some(690)).to_string(), "00:11:30");
+
+    let started = Counter::down(Some(0), Some(600));
+    assert!(started.has_started());
+    assert_eq!(started.starts_in(), None);
+    assert_eq!(started.display_prestart().to_string(), "00:10:00");
+}
+
+#[test]
+fn state_at_boundaries() {
+    use CounterState::*;
+    for direction in [Direction::Up, Direction::Down] {
+        let mut counter = Counter::new(100, 200, direction);
+        for (now, state) in [
+            (99, Pending),
+            (100, Running),
+            (199, Running),
+            (200, Finished),
+        ] {
+            counter.paused_at = Some(now);
+            assert_eq!(counter.state(), state, "{direction} at {now}");
+            assert_eq!(counter.is_running(), state == Running);
+            assert_eq!(counter.has_started(), state != Pending);
+        }
+    }
+
+    // Open-ended counters run forever once started
+    let mut counter = Counter::up(Some(100), None);
+    counter.paused_at = Some(i64::MAX);
+    assert_eq!(counter.state(), Running);
+
+    // A zero-length countdown finishes the moment it starts
+    assert_eq!(Counter::down(Some(0), Some(0)).state(), Finished);
+    assert_eq!(Running.to_string(), "running");
+}
+
+#[test]
+fn goal() {
+    let mut counter = Counter::up(Some(-600), None);
+    assert_eq!(counter.goal(), None);
+    assert!(!counter.reached_goal());
+    assert_eq!(counter.remaining_to_goal(), 0);
+
+    counter.set_goal(900).unwrap();
+    assert_eq!(counter.goal(), Some(900));
+    assert_eq!(counter.remaining_to_goal(), 300);
+    counter.paused_at = Some(300);
+    assert!(counter.reached_goal());
+    assert_eq!(counter.remaining_to_goal(), 0);
+    // Reaching the goal does not stop the counter
+    counter.paused_at = Some(400);
+    assert_eq!(counter.to_string(), "00:16:40");
+
+    counter.paused_at = None;
+    counter.to_file("/tmp/counter_test_file_goal.txt").unwrap();
+    let restored = Counter::<i64>::from_file("/tmp/counter_test_file_goal.txt").unwrap();
+    assert_eq!(restored.goal(), Some(900));
+
+    assert!(Counter::up(Some(0), None).set_goal(i64::MAX).is_ok());
+    assert!(Counter::up(Some(1), None).set_goal(i64::MAX).is_err());
+}
+
+#[test]
+fn accumulate_sessions() {
+    let mut first = Counter::up(Some(-600), None);
+    first.paused_at = Some(-300);
+    let second = Counter::up(Some(-120), None);
+    let pending = Counter::up(Some(100), None);
+
+    let mut total = Counter::up(None, None);
+    total.accumulate(&first).unwrap();
+    total.accumulate(&second).unwrap();
+    assert_eq!(total.elapsed(), 420);
+    assert_eq!((total.start, total.end), (-420, -420));
+    assert_eq!(total.goal(), None);
+
+    let mut goal = Counter::up(Some(0), Some(600));
+    goal.accumulate(&first).unwrap();
+    assert_eq!((goal.start, goal.end), (-300, 600));
+    assert_eq!(Counter::sum_elapsed(&[first, second.clone()]).unwrap(), 420);
+    assert_eq!(Counter::sum_elapsed(&[second, pending]).unwrap(), 20);
+    assert_eq!(Counter::<i64>::sum_elapsed(&[]).unwrap(), 0);
+
+    let mut total = Counter::up(Some(i64::MIN + 10), None);
+    assert!(total.accumulate(&Counter::up(Some(-11), None)).is_err());
+    assert_eq!(total.start, i64::MIN + 10);
+    let long = Counter::up(Some(-i64::MAX), None);
+    assert!(Counter::sum_elapsed(&[long.clone(), long]).is_err());
+}
+
+#[test]
+fn auto_flip_counts_up_past_end() {
+    let mut counter = Counter::down(Some(-100), Some(100));
+    counter.set_auto_flip(true);
+    let shown: Vec<_> = [98, 99, 100, 101, 160]
+        .into_iter()
+        .map(|now| {
+            counter.paused_at = Some(now);
+            (counter.to_string(), counter.is_overdue())
+        })
+        .collect();
+    assert_eq!(
+        shown,
+        [
+            ("00:00:02".to_string(), false),
+            ("00:00:01".to_string(), false),
+            ("00:00:00".to_string(), true),
+            ("00:00:01".to_string(), true),
+            ("00:01:00".to_string(), true),
+        ]
+    );
+    assert_eq!(counter.direction, Direction::Down);
+
+    counter.paused_at = None;
+    counter
+        .to_file("/tmp/counter_test_file_auto_flip.txt")
+        .unwrap();
+    assert_eq!(
+        std::fs::read_to_string("/tmp/counter_test_file_auto_flip.txt").unwrap(),
+        "-100\n100\nDown\nauto_flip=true"
+    );
+    let loaded = Counter::<i64>::from_file("/tmp/counter_test_file_auto_flip.txt").unwrap();
+    assert!(loaded.auto_flips());
+
+    let mut counter = Counter::up(Some(-100), Some(100));
+    counter.set_auto_flip(true);
+    counter.paused_at = Some(200);
+    assert!(!counter.is_overdue());
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn auto_flip_shows_zero_for_one_second() {
+    use crate::types::TimeStamp;
+    let end = TimeStamp::now();
+    let mut counter = Counter::down(Some(end - 60), Some(end));
+    counter.set_auto_flip(true);
+    for (millis, shown) in [
+        (-1500, "00:00:01"),
+        (-500, "00:00:00"),
+        (0, "00:00:00"),
+        (500, "00:00:01"),
+        (1500, "00:00:02"),
+    ] {
+        counter.paused_at = Some(end + ::chrono::Duration::milliseconds(millis));
+        assert_eq!(counter.to_string(), shown, "{millis}ms");
+    }
+}
+
+#[test]
+fn shift() {
+    let mut counter = Counter::down(Some(-600), Some(300));
+    counter.shift(900).unwrap();
+    assert_eq!((counter.start, counter.end), (300, 1200));
+    counter.shift(-1200).unwrap();
+    assert_eq!((counter.start, counter.end), (-900, 0));
+}
+
+#[test]
+fn failing_shift_changes_nothing() {
+    let mut counter = Counter::down(Some(0), Some(i64::MAX - 10));
+    assert!(counter.shift(11).is_err());
+    assert_eq!((counter.start, counter.end), (0, i64::MAX - 10));
+
+    let mut counter = Counter::up(Some(i64::MIN + 5), Some(0));
+    assert!(counter.shift(-6).is_err());
+    assert_eq!((counter.start, counter.end), (i64::MIN + 5, 0));
+}
+
+#[test]
+fn strict_moves_reject_crossing() {
+    let mut counter = Counter::up(Some(0), Some(100));
+    assert_eq!(
+        counter.try_move_start_strict(101),
+        Err(WindowViolation::Crossed)
+    );
+    assert_eq!((counter.start, counter.end), (0, 100));
+    assert_eq!(counter.try_move_start_strict(100), Ok(()));
+    assert_eq!((counter.start, counter.end), (100, 100));
+
+    let mut counter = Counter::down(Some(0), Some(100));
+    assert_eq!(
+        counter.try_move_end_strict(-101),
+        Err(WindowViolation::Crossed)
+    );
+    assert_eq!((counter.start, counter.end), (0, 100));
+    assert_eq!(counter.try_move_end_strict(-40), Ok(()));
+    assert_eq!((counter.start, counter.end), (0, 60));
+
+    let mut counter = Counter::down(Some(0), Some(i64::MAX - 10));
+    assert_eq!(
+        counter.try_move_end_strict(11),
+        Err(WindowViolation::Overflow)
+    );
+    assert_eq!((counter.start, counter.end), (0, i64::MAX - 10));
+
+    // The permissive variants still allow it
+    let mut counter = Counter::up(Some(0), Some(100));
+    counter.try_move_start(101).unwrap();
+    assert_eq!((counter.start, counter.end), (101, 100));
+}
+
+#[test]
+fn operators_add_time_to_the_clock() {
+    let mut counter = Counter::down(Some(0), Some(100));
+    let mut moved = counter.clone();
+    moved.try_move_end(30).unwrap();
+    assert_eq!(counter.clone() + 30, moved);
+    counter += 30;
+    assert_eq!(counter, moved);
+    counter -= 50;
+    assert_eq!((counter.start, counter.end), (0, 80));
+
+    let mut counter = Counter::up(Some(-100), None);
+    let mut moved = counter.clone();
+    moved.try_move_start(-30).unwrap();
+    assert_eq!(counter.clone() + 30, moved);
+    assert_eq!((counter.clone() + 30).to_string(), "00:02:10");
+    counter -= 40;
+    assert_eq!(counter.to_string(), "00:01:00");
+}
+
+#[test]
+fn operators_saturate() {
+    let counter = Counter::down(Some(0), Some(i64::MAX - 10)) + 11;
+    assert_eq!(counter.end, i64::MAX);
+    let counter = Counter::down(Some(0), Some(i64::MIN + 10)) - 11;
+    assert_eq!(counter.end, i64::MIN);
+    let mut counter = Counter::up(Some(i64::MIN + 10), Some(0));
+    counter += 20;
+    assert_eq!((counter.start, counter.end), (i64::MIN, 0));
+}
+
+#[test]
+fn set_start_and_end() {
+    let mut counter = Counter::down(Some(0), Some(100));
+    assert_eq!(counter.set_end(50), Ok(()));
+    assert_eq!(counter.set_start(-50), Ok(()));
+    assert_eq!((counter.start, counter.end), (-50, 50));
+
+    assert_eq!(
+        counter.set_start(51),
+        Err(InvalidWindow::new("51".into(), "50".into()))
+    );
+    let error = counter.set_end(-51).unwrap_err();
+    assert_eq!((error.start(), error.end()), ("-50", "-51"));
+    assert_eq!(
+        error.to_string(),
+        "Counter ends (-51) before it starts (-50)"
+    );
+    assert_eq!((counter.start, counter.end), (-50, 50));
+
+    // Equal endpoints are fine
+    assert_eq!(counter.set_end(-50), Ok(()));
+    assert_eq!(counter.set_start(-50), Ok(()));
+    assert!(counter.is_zero_length());
+
+    counter.set_end_unchecked(-100);
+    assert_eq!(counter.end, -100);
+    counter.set_start_unchecked(10);
+    assert_eq!(counter.start, 10);
+}
+
+#[test]
+fn set_end_before_start_counting_up() {
+    let mut counter = Counter::up(Some(0), Some(100));
+    assert_eq!(counter.set_end(-10), Ok(()));
+    assert_eq!(counter.set_start(20), Ok(()));
+    assert_eq!((counter.start, counter.end), (20, -10));
+}
+
+#[test]
+fn laps() {
+    let mut counter = Counter::up(Some(-30), None);
+    // Pausing stands in for the clock moving on
+    counter.paused_at = Some(-20);
+    assert_eq!(counter.lap().unwrap(), 10);
+    counter.paused_at = Some(-5);
+    assert_eq!(counter.lap().unwrap(), 15);
+    counter.paused_at = None;
+    assert_eq!(counter.lap().unwrap(), 5);
+
+    assert_eq!(counter.laps(), [-20, -5, 0]);
+    assert_eq!(counter.lap_durations(), vec![10, 15, 5]);
+
+    counter.reset();
+    assert!(counter.laps().is_empty());
+    assert!(counter.lap_durations().is_empty());
+}
+
+#[test]
+fn lap_on_down_counter_fails() {
+    let mut counter = Counter::down(Some(-30), Some(30));
+    assert!(counter.lap().is_err());
+    assert!(counter.laps().is_empty());
+}
+
+#[test]
+fn laps_file_round_trip() {
+    let mut counter = Counter::up(Some(-30), Some(0));
+    counter.paused_at = Some(-20);
+    counter.lap().unwrap();
+    counter.paused_at = None;
+    counter.lap().unwrap();
+    counter.to_file("/tmp/counter_test_file_laps.txt").unwrap();
+    assert_eq!(
+        std::fs::read_to_string("/tmp/counter_test_file_laps.txt").unwrap(),
+        "-30\n0\nUp\nlap=-20\nlap=0"
+    );
+    assert_eq!(
+        Counter::from_file("/tmp/counter_test_file_laps.txt").unwrap(),
+        counter
+    );
+}
+
+#[test]
+fn created_and_modified() {
+    // Built from given endpoints without reading the clock, nothing is known
+    let mut given = Counter::down(Some(-10), Some(100));
+    given.try_move_end(5).unwrap();
+    assert_eq!((given.created_at(), given.modified_at()), (None, None));
+
+    let mut counter = Counter::down(None, Some(100));
+    assert_eq!(counter.created_at(), Some(0));
+    assert_eq!(counter.modified_at(), Some(0));
+    let changes: [fn(&mut Counter<i64>); 5] = [
+        |counter| counter.try_move_end(5).unwrap(),
+        |counter| counter.flip(),
+        |counter| counter.set_start(-20).unwrap(),
+        |counter| counter.set_rounding(Rounding::Ceil),
+        |counter| *counter += 5,
+    ];
+    for change in changes {
+        counter.audit.modified_at = Some(-50);
+        change(&mut counter);
+        assert_eq!(counter.modified_at(), Some(0));
+    }
+    // Neither pausing nor failed adjustments are modifications, but resuming
+    // moves the window
+    counter.audit.modified_at = Some(-50);
+    counter.pause();
+    assert!(counter.try_move_start(i64::MIN).is_err());
+    assert_eq!(counter.modified_at(), Some(-50));
+    counter.resume().unwrap();
+    assert_eq!(counter.modified_at(), Some(0));
+    assert_eq!(counter.created_at(), Some(0));
+
+    // The builder only reads the clock for a missing `start`
+    let built = CounterBuilder::<i64>::new().lasting(60).build().unwrap();
+    assert_eq!(
+        (built.created_at(), built.modified_at()),
+        (Some(0), Some(0))
+    );
+    let built = CounterBuilder::new().start(-10).end(50).build().unwrap();
+    assert_eq!((built.created_at(), built.modified_at()), (None, None));
+}
+
+/// A time type whose clock is set by hand, for tracking when changes happen
+/// and how often the clock is read
+mod settable_now {
+    use crate::*;
+    use std::cell::Cell;
+
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Default, Clone, Copy)]
+    struct Settable(i64);
+
+    thread_local! {
+        static NOW: Cell<i64> = const { Cell::new(0) };
+        static READS: Cell<u32> = const { Cell::new(0) };
+    }
+
+    fn set_now(now: i64) {
+        NOW.with(|cell| cell.set(now));
+    }
+
+    /// How often the clock was read since the last call
+    fn take_reads() -> u32 {
+        READS.with(|cell| cell.replace(0))
+    }
+
+    impl Sub for Settable {
+        type Output = i64;
+
+        fn sub(self, other: Settable) -> i64 {
+            self.0 - other.0
+        }
+    }
+
+    impl Time for Settable {
+        type Duration = i64;
+
+        fn now() -> Self {
+            READS.with(|cell| cell.set(cell.get() + 1));
+            Settable(NOW.with(Cell::get))
+        }
+
+        fn add_seconds(self, duration: i64) -> Result<Self, TimeOverflow> {
+            self.0
+                .checked_add(duration)
+                .map(Settable)
+                .ok_or(TimeOverflow)
+        }
+    }
+
+    #[test]
+    fn undo_and_redo_are_modifications() {
+        set_now(0);
+        let counter = Counter::down(None, Some(Settable(100)));
+        let mut tracked = TrackedCounter::new(counter, 10);
+        tracked.try_move_end(5).unwrap();
+        set_now(10);
+        tracked.undo().unwrap();
+        assert_eq!(tracked.counter().end, Settable(100));
+        assert_eq!(tracked.counter().modified_at(), Some(Settable(10)));
+        set_now(20);
+        tracked.redo().unwrap();
+        assert_eq!(tracked.counter().modified_at(), Some(Settable(20)));
+        assert_eq!(tracked.counter().created_at(), Some(Settable(0)));
+    }
+
+    #[test]
+    fn eta_and_overtime_read_the_clock_once() {
+        let counter = Counter::down(Some(Settable(0)), Some(Settable(100)));
+        set_now(40);
+        take_reads();
+        assert_eq!(counter.eta(), Some(60));
+        assert_eq!(take_reads(), 1);
+        assert_eq!(counter.overtime(), None);
+        assert_eq!(take_reads(), 1);
+        set_now(130);
+        assert_eq!(counter.eta(), None);
+        assert_eq!(take_reads(), 1);
+        assert_eq!(counter.overtime(), Some(30));
+        assert_eq!(take_reads(), 1);
+    }
+}
+
+#[test]
+fn created_and_modified_are_not_compared() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    let hash = |counter: &Counter<i64>| {
+        let mut hasher = DefaultHasher::new();
+        counter.hash(&mut hasher);
+        hasher.finish()
+    };
+    let counter = Counter::down(Some(-10), Some(100));
+    let mut older = counter.clone();
+    older.audit.created_at = Some(-500);
+    older.audit.modified_at = Some(-400);
+    assert_eq!(older, counter);
+    assert_eq!(older.cmp(&counter), std::cmp::Ordering::Equal);
+    assert_eq!(hash(&older), hash(&counter));
+}
+
+#[test]
+fn created_and_modified_file_round_trip() {
+    let mut counter = Counter::down(None, Some(100));
+    counter.audit.created_at = Some(-50);
+    counter.audit.modified_at = Some(-20);
+    counter.to_file("/tmp/counter_test_file_audit.txt").unwrap();
+    assert_eq!(
+        std::fs::read_to_string("/tmp/counter_test_file_audit.txt").unwrap(),
+        "0\n100\nDown\ncreated_at=-50\nmodified_at=-20"
+    );
+    let loaded = Counter::from_file("/tmp/counter_test_file_audit.txt").unwrap();
+    assert_eq!(
+        (loaded.created_at(), loaded.modified_at()),
+        (Some(-50), Some(-20))
+    );
+
+    // Files from before these were recorded still load
+    std::fs::write("/tmp/counter_test_file_audit_old.txt", "-10\n100\nDown").unwrap();
+    let old = Counter::<i64>::from_file("/tmp/counter_test_file_audit_old.txt").unwrap();
+    assert_eq!(
+        (old.start, old.end, old.direction),
+        (-10, 100, Direction::Down)
+    );
+    assert_eq!((old.created_at(), old.modified_at()), (None, None));
+}
+
+#[test]
+fn snooze() {
+    let mut alarm = Counter::down(Some(-100), Some(-10));
+    alarm.snooze(60).unwrap();
+    assert_eq!((alarm.end, alarm.snooze_count()), (60, 1));
+    alarm.snooze(30).unwrap();
+    assert_eq!((alarm.end, alarm.snooze_count()), (90, 2));
+
+    let mut near_limit = Counter::down(Some(0), Some(i64::MAX - 5));
+    assert!(near_limit.snooze(10).is_err());
+    assert_eq!(near_limit.snooze_count(), 0);
+
+    alarm.reset();
+    assert_eq!((alarm.end, alarm.snooze_count()), (-10, 0));
+}
+
+#[test]
+fn snooze_file_round_trip() {
+    let mut alarm = Counter::down(Some(-100), Some(-10));
+    alarm.snooze(60).unwrap();
+    alarm.snooze(60).unwrap();
+    alarm.to_file("/tmp/counter_test_file_snooze.txt").unwrap();
+    assert_eq!(
+        std::fs::read_to_string("/tmp/counter_test_file_snooze.txt").unwrap(),
+        "-100\n120\nDown\ninitial_end=-10\nsnoozes=2"
+    );
+    let loaded = Counter::<i64>::from_file("/tmp/counter_test_file_snooze.txt").unwrap();
+    assert_eq!(loaded.snooze_count(), 2);
+    assert_eq!(loaded, alarm);
+}
+
+#[test]
+fn split() {
+    let original = Counter::down(Some(-600), Some(900));
+    let (left, right) = original.split();
+    assert_eq!(
+        (left.direction, left.start, left.end),
+        (Direction::Up, -600, 0)
+    );
+    assert_eq!(
+        (right.direction, right.start, right.end),
+        (Direction::Down, 0, 900)
+    );
+    assert_eq!(left.total() + right.total(), original.total());
+    assert_eq!(
+        (left.to_string(), right.to_string()),
+        ("00:10:00".into(), "00:15:00".into())
+    );
+
+    for now in [-1000, -600, 300, 900, 5000] {
+        let mut original = Counter::down(Some(-600), Some(900));
+        original.paused_at = Some(now);
+        let (left, right) = original.split();
+        assert_eq!(left.total() + right.total(), original.total(), "{now}");
+        assert!(left.start <= left.end && right.start <= right.end, "{now}");
+    }
+
+    let (_, remaining) = Counter::down(Some(-600), Some(-60)).split();
+    assert_eq!((remaining.start, remaining.end), (-60, -60));
+}
+
+#[test]
+fn tracked_undo_redo() {
+    let mut tracked = TrackedCounter::new(Counter::down(Some(0), Some(60)), 10);
+    assert_eq!(tracked.undo(), Err(EmptyHistory));
+    tracked.try_move_end(600).unwrap();
+    tracked.shift(-30).unwrap();
+    tracked.flip();
+    tracked.try_move_start(5).unwrap();
+    let kinds: Vec<_> = tracked.history().iter().map(|a| a.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            AdjustmentKind::MoveEnd(600),
+            AdjustmentKind::Shift(-30),
+            AdjustmentKind::Flip,
+            AdjustmentKind::MoveStart(5),
+        ]
+    );
+    assert!(tracked.history().iter().all(|a| a.at == 0));
+
+    tracked.undo().unwrap();
+    tracked.undo().unwrap();
+    let counter = tracked.counter();
+    assert_eq!(
+        (counter.start, counter.end, counter.direction),
+        (-30, 630, Direction::Down)
+    );
+    tracked.redo().unwrap();
+    assert_eq!(tracked.counter().direction, Direction::Up);
+    assert_eq!(tracked.history().len(), 3);
+
+    tracked.try_move_end(1).unwrap();
+    assert_eq!(tracked.redo(), Err(EmptyHistory));
+    while tracked.undo().is_ok() {}
+    let counter = tracked.into_inner();
+    assert_eq!(
+        (counter.start, counter.end, counter.direction),
+        (0, 60, Direction::Down)
+    );
+}
+
+#[test]
+fn tracked_history_is_bounded() {
+    let mut tracked = TrackedCounter::new(Counter::down(Some(0), Some(0)), 2);
+    for _ in 0..5 {
+        tracked.try_move_end(10).unwrap();
+    }
+    assert!(tracked.try_move_end(i64::MAX).is_err());
+    assert_eq!(tracked.history().len(), 2);
+    tracked.undo().unwrap();
+    tracked.undo().unwrap();
+    assert_eq!(tracked.undo(), Err(EmptyHistory));
+    assert_eq!(tracked.counter().end, 30);
+}
+
+#[test]
+fn conversions_preserve_the_display() {
+    let stopwatch = Counter::up(Some(-754), None);
+    let shown = stopwatch.to_string();
+    let countdown = stopwatch.into_down_preserving().unwrap();
+    assert_eq!(
+        (countdown.direction, countdown.to_string()),
+        (Direction::Down, shown)
+    );
+
+    let countdown = Counter::down(Some(-100), Some(3600));
+    let shown = countdown.to_string();
+    let stopwatch = countdown.into_up_preserving().unwrap();
+    assert_eq!(
+        (stopwatch.direction, stopwatch.to_string()),
+        (Direction::Up, shown)
+    );
+    assert_eq!((stopwatch.start, stopwatch.end), (-3600, 0));
+
+    let expired = Counter::down(Some(-100), Some(-50))
+        .into_down_preserving()
+        .unwrap();
+    assert_eq!((expired.start, expired.end), (0, 0));
+    assert_eq!(expired.to_string(), "00:00:00");
+    let expired = Counter::down(Some(-100), Some(-50))
+        .into_up_preserving()
+        .unwrap();
+    assert_eq!(expired.to_string(), "00:00:00");
+
+    let mut paused = Counter::up(Some(-500), None);
+    paused.paused_at = Some(-400);
+    assert_eq!(
+        paused.into_down_preserving().unwrap().to_string(),
+        "00:01:40"
+    );
+}
+
+#[test]
+fn clamp_to_end() {
+    let mut session = Counter::up(Some(-3600), Some(0));
+    session.clamp_to_end(true);
+    for (now, shown) in [
+        (-60, "00:59:00"),
+        (0, "01:00:00"),
+        (1, "01:00:00"),
+        (86400, "01:00:00"),
+    ] {
+        session.paused_at = Some(now);
+        assert_eq!(session.to_string(), shown, "{now}");
+        assert_eq!(session.seconds(), (now.min(0) + 3600), "{now}");
+    }
+    assert_eq!(session.counter(), (1, 0, 0));
+    assert_eq!(session.elapsed(), 86400 + 3600);
+
+    session.clamp_to_end(false);
+    assert_eq!(session.to_string(), "25:00:00");
+
+    let mut open_ended = Counter::up(Some(-90), Some(-90));
+    open_ended.clamp_to_end(true);
+    assert_eq!(open_ended.to_string(), "00:01:30");
+}
+
+#[test]
+fn clamp_to_end_file_round_trip() {
+    let mut session = Counter::up(Some(-3600), Some(0));
+    session.clamp_to_end(true);
+    session.to_file("/tmp/counter_test_file_clamp.txt").unwrap();
+    assert_eq!(
+        std::fs::read_to_string("/tmp/counter_test_file_clamp.txt").unwrap(),
+        "-3600\n0\nUp\nstop_at_end=true"
+    );
+    let loaded = Counter::<i64>::from_file("/tmp/counter_test_file_clamp.txt").unwrap();
+    assert!(loaded.clamps_to_end());
+    assert_eq!(loaded, session);
+}
+
+#[test]
+fn rounding_file_round_trip() {
+    let mut counter = Counter::down(Some(0), Some(600));
+    counter.set_rounding(Rounding::Nearest);
+    counter
+        .to_file("/tmp/counter_test_file_rounding.txt")
+        .unwrap();
+    assert_eq!(
+        std::fs::read_to_string("/tmp/counter_test_file_rounding.txt").unwrap(),
+        "0\n600\nDown\nrounding=nearest"
+    );
+    let loaded = Counter::<i64>::from_file("/tmp/counter_test_file_rounding.txt").unwrap();
+    assert_eq!(loaded.rounding(), Rounding::Nearest);
+
+    counter.set_rounding(Rounding::Ceil);
+    assert_eq!(counter.to_contents(), "0\n600\nDown\nrounding=ceil");
+    let loaded = Counter::<i64>::from_contents_with(&counter.to_contents(), |s| s.parse().ok());
+    assert_eq!(loaded.unwrap().rounding(), Rounding::Ceil);
+    counter.set_rounding(Rounding::Floor);
+    assert_eq!(counter.to_contents(), "0\n600\nDown");
+    assert!(
+        Counter::<i64>::from_contents_with("0\n600\nDown\nrounding=up", |s| s.parse().ok())
+            .is_err()
+    );
+}
+
+#[test]
+fn final_thresholds() {
+    let mut countdown = Counter::down(Some(-540), Some(60));
+    for (now, final_minute, final_tenth) in [
+        (-1, false, false),
+        (0, true, true),
+        (1, true, true),
+        (59, true, true),
+        (60, false, false),
+        (600, false, false),
+    ] {
+        countdown.paused_at = Some(now);
+        assert_eq!(countdown.in_final_seconds(60), final_minute, "{now}");
+        assert_eq!(countdown.in_final_fraction(0.1), final_tenth, "{now}");
+    }
+
+    let open = Counter::up(Some(-10), None);
+    assert!(!open.in_final_seconds(i64::MAX));
+    assert!(!open.in_final_fraction(1.0));
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn final_thresholds_beyond_duration_range() {
+    use crate::types::TimeStamp;
+    let now = TimeStamp::now();
+    let countdown = Counter::down(Some(now), Some(now + 600));
+    assert!(countdown.in_final_seconds(i64::MAX));
+    assert!(!countdown.in_final_seconds(i64::MIN));
+    let open = Counter::up(Some(now), None);
+    assert!(!open.in_final_seconds(i64::MAX));
+}
+
+#[test]
+fn diff() {
+    let soon = Counter::down(Some(0), Some(60));
+    let later = Counter::down(Some(-1000), Some(600));
+    assert!(soon > later);
+    assert_eq!(soon.cmp_remaining(&later), Ordering::Less);
+    assert_eq!(soon.diff(&later), -540);
+    assert_eq!(later.diff(&soon), 540);
+    assert_eq!(soon.diff(&soon), 0);
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn rounding_modes() {
+    use crate::types::TimeStamp;
+
+    let start = TimeStamp::now();
+    let millis = |ms| start + ::chrono::Duration::milliseconds(ms);
+    let mut counter = Counter::down(Some(start), Some(millis(600_000)));
+    for (now, floor, ceil, nearest) in [
+        (300, "00:09:59", "00:10:00", "00:10:00"),
+        (700, "00:09:59", "00:10:00", "00:09:59"),
+        (599_500, "00:00:00", "00:00:01", "00:00:01"),
+        (599_999, "00:00:00", "00:00:01", "00:00:00"),
+        (600_000, "00:00:00", "00:00:00", "00:00:00"),
+    ] {
+        counter.paused_at = Some(millis(now));
+        for (rounding, shown) in [
+            (Rounding::Floor, floor),
+            (Rounding::Ceil, ceil),
+            (Rounding::Nearest, nearest),
+        ] {
+            counter.set_rounding(rounding);
+            assert_eq!(counter.to_string(), shown, "{now} {rounding:?}");
+        }
+    }
+
+    counter.paused_at = Some(millis(600_500));
+    counter.set_rounding(Rounding::Floor);
+    assert_eq!(counter.signed_seconds(), 0);
+    counter.set_rounding(Rounding::Ceil);
+    assert_eq!(counter.rounding(), Rounding::Ceil);
+    assert_eq!((counter.signed_seconds(), counter.seconds()), (-1, 0));
+}
+
+#[test]
+fn scaled_speeds() {
+    use crate::clock::TestClock;
+
+    let clock = TestClock::new(0_i64);
+    let fast = ScaledCounter::with_clock(Counter::down(Some(0), Some(600)), 2.0, &clock);
+    let slow = ScaledCounter::with_clock(Counter::down(Some(0), Some(600)), 0.5, &clock);
+    clock.advance(61).unwrap();
+    assert_eq!(
+        (fast.to_string(), fast.remaining()),
+        ("00:07:58".into(), 478)
+    );
+    assert_eq!((slow.to_string(), slow.elapsed()), ("00:09:30".into(), 30));
+    assert!(!fast.is_finished());
+    clock.advance(239).unwrap();
+    assert!(fast.is_finished());
+    assert_eq!(fast.to_string(), "00:00:00");
+}
+
+#[test]
+fn scaled_speed_changes_do_not_jump() {
+    use crate::clock::TestClock;
+
+    let clock = TestClock::new(0_i64);
+    let mut game = ScaledCounter::with_clock(Counter::up(Some(0), None), 2.0, &clock);
+    clock.advance(60).unwrap();
+    assert_eq!(game.to_string(), "00:02:00");
+    game.set_speed(0.5);
+    assert_eq!((game.to_string(), game.speed()), ("00:02:00".into(), 0.5));
+    clock.advance(60).unwrap();
+    assert_eq!(game.to_string(), "00:02:30");
+    game.set_speed(3.0);
+    clock.advance(10).unwrap();
+    assert_eq!(game.to_string(), "00:03:00");
+}
+
+#[test]
+fn scaled_overflow() {
+    use crate::clock::TestClock;
+
+    let clock = TestClock::new(0_i64);
+    let fast = ScaledCounter::with_clock(Counter::up(Some(0), None), 1e300, &clock);
+    let broken = ScaledCounter::with_clock(Counter::up(Some(0), None), f64::NAN, &clock);
+    let backward = ScaledCounter::with_clock(Counter::up(Some(0), None), -1e18, &clock);
+    assert_eq!(fast.try_now().unwrap(), 0);
+    clock.advance(60).unwrap();
+    assert!(fast.try_now().is_err());
+    assert_eq!(fast.now(), i64::MAX);
+    assert!(broken.try_now().is_err());
+    assert!(backward.try_now().is_err());
+    assert_eq!(backward.now(), i64::MIN);
+
+    let fine = ScaledCounter::with_clock(Counter::up(Some(0), None), 1e15, &clock);
+    clock.advance(60).unwrap();
+    assert_eq!(fine.try_now().unwrap(), 60 + 60_000_000_000_000_000);
+}
+
+#[test]
+fn scaled_at_normal_speed_is_unchanged() {
+    use crate::clock::TestClock;
+
+    let clock = TestClock::new(-5_000_000_i64);
+    let counter = Counter::down(Some(-5_000_000), Some(3_000_000));
+    let scaled = ScaledCounter::with_clock(counter.clone(), 1.0, &clock);
+    for step in [0, 1, 59, 3600, 86399, 10_000_000] {
+        clock.advance(step).unwrap();
+        assert_eq!(scaled.breakdown(), counter.at(clock.now()));
+        assert_eq!(scaled.to_string(), counter.with_clock(&clock).to_string());
+    }
+}
+
+#[test]
+fn repeating_cycles() {
+    let repeating = RepeatingCounter::new(-100, 30).unwrap();
+    assert_eq!((repeating.cycle(), repeating.until_next()), (3, 20));
+    assert_eq!(repeating.to_string(), "00:00:20");
+    for (now, cycle, until_next) in [
+        (-130, 0, 60),
+        (-100, 0, 30),
+        (-71, 0, 1),
+        (-70, 1, 30),
+        (29_900, 1000, 30),
+        (29_929, 1000, 1),
+    ] {
+        assert_eq!(repeating.cycle_at(now), cycle, "{now}");
+        assert_eq!(repeating.until_next_at(now), until_next, "{now}");
+    }
+
+    assert_eq!(RepeatingCounter::<i64>::new(0, 0), Err(InvalidPeriod));
+    assert_eq!(RepeatingCounter::<i64>::new(0, -30), Err(InvalidPeriod));
+}
+
+#[test]
+fn repeating_file_round_trip() {
+    let repeating = RepeatingCounter::new(-100, 30).unwrap();
+    repeating
+        .to_file("/tmp/counter_test_file_repeating.txt")
+        .unwrap();
+    assert_eq!(
+        std::fs::read_to_string("/tmp/counter_test_file_repeating.txt").unwrap(),
+        "-100\n30"
+    );
+    assert_eq!(
+        RepeatingCounter::from_file("/tmp/counter_test_file_repeating.txt").unwrap(),
+        repeating
+    );
+
+    std::fs::write("/tmp/counter_test_file_repeating_bad.txt", "-100\n0").unwrap();
+    assert!(
+        RepeatingCounter::<i64>::from_file("/tmp/counter_test_file_repeating_bad.txt").is_err()
+    );
+}
+
+#[test]
+fn sequence_advances_through_phases() {
+    let sequence = Sequence::new(-100)
+        .phase("work", 60)
+        .and_then(|sequence| sequence.phase("break", 30))
+        .and_then(|sequence| sequence.phase("work", 60))
+        .unwrap();
+    for (now, index) in [
+        (-101, 0),
+        (-41, 0),
+        (-40, 1),
+        (-11, 1),
+        (-10, 2),
+        (49, 2),
+        (50, 3),
+    ] {
+        assert_eq!(sequence.phase_index_at(now), index, "{now}");
+    }
+    assert_eq!(sequence.current_at(-100), ("work", CounterReading::new(60)));
+    assert_eq!(sequence.current_at(-40), ("break", CounterReading::new(30)));
+    assert_eq!(sequence.current_at(60), ("work", CounterReading::new(0)));
+    assert_eq!(sequence.remaining_at(-100), 150);
+    assert_eq!(sequence.remaining_at(60), -10);
+
+    assert_eq!(sequence.phase_index(), 2);
+    assert_eq!(sequence.to_string(), "00:00:50");
+    assert!(!sequence.is_complete());
+    assert_eq!(
+        Sequence::<i64>::new(0).current(),
+        ("", CounterReading::new(0))
+    );
+}
+
+#[test]
+fn sequence_skip_and_extend() {
+    let sequence = Sequence::new(-100)
+        .phase("work", 60)
+        .and_then(|sequence| sequence.phase("break", 30))
+        .and_then(|sequence| sequence.phase("work", 60))
+        .unwrap();
+
+    let mut extended = sequence.clone();
+    extended.extend_current(30).unwrap();
+    assert_eq!(extended.remaining(), 80);
+    assert_eq!(extended.current().1, CounterReading::new(80));
+
+    let mut skipped = sequence.clone();
+    skipped.skip();
+    assert!(skipped.is_complete());
+    assert_eq!(skipped.remaining(), 0);
+    skipped.skip();
+    skipped.extend_current(30).unwrap();
+    assert_eq!(skipped.remaining(), 0);
+
+    let mut early = Sequence::new(100)
+        .phase("work", 60)
+        .and_then(|sequence| sequence.phase("break", 30))
+        .unwrap();
+    early.skip();
+    assert_eq!(early.current(), ("break", CounterReading::new(130)));
+
+    // Shortening stops at an empty phase
+    let mut shortened = sequence.clone();
+    shortened.extend_current(-100).unwrap();
+    assert_eq!(shortened.phases().nth(2), Some(("work", 0)));
+    assert!(shortened.is_complete());
+}
+
+#[test]
+fn sequence_rejects_negative_phases() {
+    assert_eq!(Sequence::new(0).phase("work", -1), Err(InvalidPhase));
+    let empty = Sequence::new(0).phase("nothing", 0).unwrap();
+    assert_eq!(empty.phases().collect::<Vec<_>>(), [("nothing", 0)]);
+
+    std::fs::write(
+        "/tmp/counter_test_file_sequence_negative.txt",
+        "0\n60 work\n-30 break",
+    )
+    .unwrap();
+    assert!(Sequence::<i64>::from_file("/tmp/counter_test_file_sequence_negative.txt").is_err());
+}
+
+#[test]
+fn sequence_file_round_trip() {
+    let sequence = Sequence::<i64>::pomodoro(-100);
+    sequence
+        .to_file("/tmp/counter_test_file_sequence.txt")
+        .unwrap();
+    let contents = std::fs::read_to_string("/tmp/counter_test_file_sequence.txt").unwrap();
+    assert!(contents.starts_with("-100\n1500 work\n300 break\n"));
+    assert!(contents.ends_with("\n900 long break"));
+    assert_eq!(
+        Sequence::from_file("/tmp/counter_test_file_sequence.txt").unwrap(),
+        sequence
+    );
+    assert_eq!(sequence.phases().count(), 8);
+
+    let escaped = Sequence::new(0)
+        .phase("two\nlines", 60)
+        .and_then(|sequence| sequence.phase("back\\slash", 30))
+        .unwrap();
+    escaped
+        .to_file("/tmp/counter_test_file_sequence_escaped.txt")
+        .unwrap();
+    assert_eq!(
+        std::fs::read_to_string("/tmp/counter_test_file_sequence_escaped.txt").unwrap(),
+        "0\n60 two\\nlines\n30 back\\\\slash"
+    );
+    assert_eq!(
+        Sequence::from_file("/tmp/counter_test_file_sequence_escaped.txt").unwrap(),
+        escaped
+    );
+}
+
+#[test]
+fn counter_set() {
+    let mut set = CounterSet::new();
+    assert!(set.is_empty());
+    assert_eq!(set.insert("tea", Counter::down(None, Some(180))), None);
+    assert_eq!(set.insert("run", Counter::up(Some(-65), None)), None);
+    assert_eq!(set.insert("oven", Counter::down(None, Some(3600))), None);
+    assert_eq!(
+        set.to_string(),
+        "tea: 00:03:00\nrun: 00:01:05\noven: 01:00:00"
+    );
+
+    // Replacing keeps the position and hands back the old counter
+    let old = set.insert("tea", Counter::down(None, Some(240)));
+    assert_eq!(old, Some(Counter::down(None, Some(180))));
+    assert_eq!(set.len(), 3);
+    let names: Vec<_> = set.iter().map(|(name, _)| name).collect();
+    assert_eq!(names, ["tea", "run", "oven"]);
+    assert_eq!(set.get("tea").unwrap().remaining(), 240);
+
+    set.get_mut("run").unwrap().try_move_start(-5).unwrap();
+    assert_eq!(set.get("run").unwrap().elapsed(), 70);
+    assert_eq!(set.remove("tea").map(|c| c.remaining()), Some(240));
+    assert_eq!(set.remove("tea"), None);
+    assert_eq!(set.get("tea"), None);
+    assert_eq!(set.to_string(), "run: 00:01:10\noven: 01:00:00");
+    assert_eq!(CounterSet::<i64>::new().to_string(), "");
+}
+
+#[test]
+fn counter_set_by_remaining() {
+    let mut set = CounterSet::new();
+    set.insert("stream", Counter::up(Some(-50), None));
+    set.insert("oven", Counter::down(Some(-600), Some(300)));
+    set.insert("tea", Counter::down(Some(-200), Some(60)));
+    set.insert("eggs", Counter::down(Some(-400), Some(-20)));
+
+    let (name, counter) = set.next_to_expire().unwrap();
+    assert_eq!((name, counter.end), ("eggs", -20));
+    assert_eq!(
+        set.sorted_by_remaining(),
+        [
+            ("eggs", CounterReading::new(-20)),
+            ("tea", CounterReading::new(60)),
+            ("oven", CounterReading::new(300)),
+            ("stream", CounterReading::new(50)),
+        ]
+    );
+    assert_eq!(set.expiring_within(60), ["eggs", "tea"]);
+    assert_eq!(set.expiring_within(59), ["eggs"]);
+    assert_eq!(set.expiring_within(-21), Vec::<&str>::new());
+    assert_eq!(set.expiring_within(i64::MAX).len(), 3);
+
+    set.remove("eggs");
+    set.remove("tea");
+    set.remove("oven");
+    assert!(set.next_to_expire().is_none());
+}
+
+#[test]
+fn counter_set_batch_changes() {
+    let mut set = CounterSet::new();
+    set.insert("tea", Counter::down(Some(-200), Some(60)));
+    set.insert("stream", Counter::up(Some(-50), None));
+    set.insert("workout", Counter::up(Some(-60), Some(1800)));
+    set.insert("eggs", Counter::down(Some(-400), Some(-20)));
+
+    set.try_move_all_ends(900).unwrap();
+    let ends: Vec<_> = set.iter().map(|(_, counter)| counter.end).collect();
+    assert_eq!(ends, [960, -50, 2700, 880]);
+
+    // Open-ended counters are not given an end
+    assert_eq!(set.get("stream").unwrap().completion_time(), None);
+
+    set.flip_all();
+    let up: Vec<_> = set
+        .iter()
+        .map(|(_, counter)| counter.direction == Direction::Up)
+        .collect();
+    assert_eq!(up, [true, false, false, true]);
+    set.flip_all();
+
+    set.try_move_all_ends(-900).unwrap();
+    set.retain_running();
+    let names: Vec<_> = set.iter().map(|(name, _)| name).collect();
+    assert_eq!(names, ["tea", "stream", "workout"]);
+
+    // Counters that have not started are not dropped
+    set.insert("later", Counter::down(Some(100), Some(200)));
+    set.retain_running();
+    assert_eq!(set.len(), 4);
+}
+
+#[test]
+fn counter_set_move_all_ends_is_atomic() {
+    let mut set = CounterSet::new();
+    set.insert("tea", Counter::down(Some(-200), Some(60)));
+    set.insert("short", Counter::down(Some(-10), Some(10)));
+    set.insert("oven", Counter::down(Some(-600), Some(300)));
+    set.insert("far", Counter::down(Some(0), Some(i64::MAX - 10)));
+    let before = set.clone();
+
+    // "short" would end before it starts, so nothing moves
+    let error = set.try_move_all_ends(-30).unwrap_err();
+    assert_eq!(error.name(), "short");
+    assert_eq!(error.violation(), WindowViolation::Crossed);
+    assert_eq!(set, before);
+
+    // The last counter overflows, after the others were already moved
+    let error = set.try_move_all_ends(20).unwrap_err();
+    assert_eq!(error.name(), "far");
+    assert_eq!(error.violation(), WindowViolation::Overflow);
+    assert_eq!(
+        error.to_string(),
+        "far: Time could not be added due to an overflow"
+    );
+    assert_eq!(set, before);
+
+    set.remove("far");
+    set.try_move_all_ends(20).unwrap();
+    assert_eq!(set.get("short").unwrap().end, 30);
+}
+
+#[test]
+fn counter_set_soonest() {
+    let mut set = CounterSet::new();
+    set.insert("stream", Counter::up(Some(-50), None));
+    set.insert("eggs", Counter::down(Some(-400), Some(-20)));
+    set.insert("oven", Counter::down(Some(-600), Some(300)));
+    set.insert("later", Counter::down(Some(30), Some(60)));
+    set.insert("workout", Counter::up(Some(-60), Some(240)));
+
+    // "eggs" is past its end and "later" has not started yet
+    let (name, counter) = set.soonest().unwrap();
+    assert_eq!((name, counter.end), ("workout", 240));
+    assert_eq!(set.next_event_in(), Some(240));
+    assert_eq!(set.expired(), ["eggs"]);
+    assert_eq!(set.next_to_expire().unwrap().0, "eggs");
+
+    set.get_mut("oven").unwrap().try_move_end(-100).unwrap();
+    assert_eq!(set.soonest().unwrap().0, "oven");
+    assert_eq!(set.next_event_in(), Some(200));
+
+    set.remove("oven");
+    set.remove("workout");
+    assert_eq!(set.soonest(), None);
+    assert_eq!(set.next_event_in(), None);
+    assert!(CounterSet::<i64>::new().expired().is_empty());
+}
+
+#[test]
+fn counter_set_sorted_and_filtered() {
+    let mut set = CounterSet::new();
+    set.insert("tea", Counter::down(Some(-200), Some(60)));
+    set.insert("stream", Counter::up(Some(-50), None));
+    set.insert("eggs", Counter::down(Some(-400), Some(-20)));
+    set.insert("bread", Counter::down(Some(-100), Some(60)));
+    set.insert("later", Counter::down(Some(30), Some(90)));
+    set.insert("soup", Counter::down(Some(-200), Some(60)));
+
+    let names = |entries: SetEntries<'_, i64>| -> Vec<String> {
+        entries.map(|(name, _)| name.into()).collect()
+    };
+    assert_eq!(
+        names(set.iter_sorted(SortKey::Name)),
+        ["bread", "eggs", "later", "soup", "stream", "tea"]
+    );
+    // "tea" and "soup" tie on both end and start, so they keep their order
+    assert_eq!(
+        names(set.iter_sorted(SortKey::Remaining)),
+        ["eggs", "tea", "soup", "bread", "later", "stream"]
+    );
+    // So do "tea", "bread" and "soup", which all end at 60
+    assert_eq!(
+        names(set.iter_sorted(SortKey::End)),
+        ["stream", "eggs", "tea", "bread", "soup", "later"]
+    );
+
+    assert_eq!(names(set.running()), ["tea", "stream", "bread", "soup"]);
+    assert_eq!(names(set.finished()), ["eggs"]);
+    assert_eq!(
+        names(set.running().sorted(SortKey::Name)),
+        ["bread", "soup", "stream", "tea"]
+    );
+    assert_eq!(
+        names(set.running().sorted(SortKey::Remaining)),
+        ["tea", "soup", "bread", "stream"]
+    );
+    assert_eq!(
+        names(set.running().sorted(SortKey::End).sorted(SortKey::Name)),
+        ["bread", "soup", "stream", "tea"]
+    );
+
+    let borrowed: Vec<_> = (&set).into_iter().map(|(name, _)| name).collect();
+    assert_eq!(borrowed.len(), set.len());
+    let mut borrowed = 0;
+    for (_, counter) in &set {
+        borrowed += counter.end;
+    }
+    assert_eq!(borrowed, 60 - 50 - 20 + 60 + 90 + 60);
+
+    let owned: Vec<(String, Counter<i64>)> = set.clone().into_iter().collect();
+    assert_eq!(owned.len(), 6);
+    assert_eq!(
+        owned[2],
+        ("eggs".into(), Counter::down(Some(-400), Some(-20)))
+    );
+}
+
+#[test]
+fn counter_set_totals() {
+    let mut set = CounterSet::new();
+    set.insert("work/emails", Counter::up(Some(-1800), None));
+    set.insert("work/review", Counter::up(Some(-5400), Some(600)));
+    set.insert("home/laundry", Counter::down(Some(-600), Some(900)));
+    set.insert("home/oven", Counter::down(Some(-600), Some(-60)));
+
+    assert_eq!(set.total_elapsed().unwrap(), 1800 + 5400 + 600 + 600);
+    assert_eq!(set.total_remaining().unwrap(), 900 + 600);
+    let totals = set
+        .totals_by(|name| name.split('/').next().unwrap().to_string())
+        .unwrap();
+    assert_eq!(totals.len(), 2);
+    assert_eq!((totals["work"], totals["home"]), (7200, 1200));
+    assert_eq!(CounterSet::<i64>::new().total_elapsed().unwrap(), 0);
+
+    // Counters that have not started yet add nothing, rather than subtracting
+    set.insert("work/tomorrow", Counter::up(Some(3600), None));
+    set.insert("later/call", Counter::down(Some(600), Some(900)));
+    assert_eq!(set.total_elapsed().unwrap(), 1800 + 5400 + 600 + 600);
+    let totals = set
+        .totals_by(|name| name.split('/').next().unwrap().to_string())
+        .unwrap();
+    assert_eq!(
+        (totals["work"], totals["home"], totals["later"]),
+        (7200, 1200, 0)
+    );
+
+    set.insert("long", Counter::up(Some(-i64::MAX), None));
+    assert!(set.total_elapsed().is_err());
+    assert!(set.totals_by(|_| ()).is_err());
+}
+
+#[test]
+fn counter_set_metadata() {
+    let mut set = CounterSet::new();
+    let focus = Metadata {
+        label: Some("Deep work".into()),
+        tags: vec!["work".into(), "focus".into()],
+        note: None,
+    };
+    set.insert_with("a", Counter::up(Some(-60), None), focus.clone());
+    set.insert("b", Counter::down(None, Some(60)));
+    set.metadata_mut("b").unwrap().tags.push("work".into());
+    set.insert("c", Counter::down(None, Some(90)));
+
+    let names = |entries: Vec<(&str, &Counter<i64>)>| -> Vec<String> {
+        entries.into_iter().map(|(name, _)| name.into()).collect()
+    };
+    assert_eq!(names(set.filter_by_tag("work")), ["a", "b"]);
+    assert_eq!(names(set.filter_by_tag("focus")), ["a"]);
+    assert!(set.filter_by_tag("home").is_empty());
+
+    // Replacing the counter keeps the metadata, unless it is replaced too
+    set.insert("a", Counter::up(Some(-30), None));
+    assert_eq!(set.metadata("a"), Some(&focus));
+    set.insert_with("a", Counter::up(Some(-30), None), Metadata::default());
+    assert_eq!(set.metadata("a"), Some(&Metadata::default()));
+    assert_eq!(set.metadata("missing"), None);
+}
+
+#[test]
+fn counter_set_metadata_round_trip() {
+    let mut set = CounterSet::new();
+    let metadata = Metadata {
+        label: Some("Tee kochen ☕ – grün".into()),
+        tags: vec!["küche".into(), "with space".into(), "#hash=tag".into()],
+        note: Some("two\nlines and a \\ backslash\\n".into()),
+    };
+    set.insert_with(
+        "tea\nwith newline",
+        Counter::down(Some(0), Some(180)),
+        metadata.clone(),
+    );
+    set.insert("plain", Counter::up(Some(-5), None));
+    set.to_file("/tmp/counter_test_file_set_metadata.txt")
+        .unwrap();
+    assert_eq!(
+        std::fs::read_to_string("/tmp/counter_test_file_set_metadata.txt").unwrap(),
+        "tea\\nwith newline\n#label=Tee kochen ☕ – grün\n#tag=küche\n#tag=with space\n\
+         #tag=#hash=tag\n#note=two\\nlines and a \\\\ backslash\\\\n\n0\n180\nDown\n\n\
+         plain\n-5\n-5\nUp"
+    );
+    let loaded = CounterSet::from_file("/tmp/counter_test_file_set_metadata.txt").unwrap();
+    assert_eq!(loaded, set);
+    assert_eq!(loaded.metadata("tea\nwith newline"), Some(&metadata));
+
+    std::fs::write(
+        "/tmp/counter_test_file_set_metadata_bad.txt",
+        "tea\n#label=bad \\x escape\n0\n180\nDown",
+    )
+    .unwrap();
+    assert!(CounterSet::<i64>::from_file("/tmp/counter_test_file_set_metadata_bad.txt").is_err());
+}
+
+#[test]
+fn counter_set_file_round_trip() {
+    let mut set = CounterSet::new();
+    let mut laps = Counter::up(Some(-30), None);
+    laps.paused_at = Some(-20);
+    laps.lap().unwrap();
+    set.insert("with laps", laps);
+    set.insert("", Counter::down(Some(0), Some(100)));
+    set.insert("last", Counter::up(Some(-5), Some(10)));
+    set.to_file("/tmp/counter_test_file_set.txt").unwrap();
+    assert_eq!(
+        std::fs::read_to_string("/tmp/counter_test_file_set.txt").unwrap(),
+        "with laps\n-30\n-30\nUp\npaused_at=-20\nlap=-20\n\n\n0\n100\nDown\n\nlast\n-5\n10\nUp"
+    );
+    assert_eq!(
+        CounterSet::from_file("/tmp/counter_test_file_set.txt").unwrap(),
+        set
+    );
+
+    CounterSet::<i64>::new()
+        .to_file("/tmp/counter_test_file_set_empty.txt")
+        .unwrap();
+    assert!(
+        CounterSet::<i64>::from_file("/tmp/counter_test_file_set_empty.txt")
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn counter_set_dir_round_trip() {
+    let dir = std::env::temp_dir().join("countrs_test_set_dir");
+    let _ = std::fs::remove_dir_all(&dir);
+    let mut set = CounterSet::new();
+    set.insert_with(
+        "Tea/time",
+        Counter::down(Some(0), Some(180)),
+        Metadata {
+            tags: vec!["kitchen".into()],
+            ..Metadata::default()
+        },
+    );
+    set.insert("pasta", Counter::down(Some(0), Some(540)));
+    set.insert("zen ☕", Counter::up(Some(-5), None));
+    set.save_dir(&dir).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(dir.join("Tea%2Ftime.counter")).unwrap(),
+        "#tag=kitchen\n0\n180\nDown"
+    );
+    assert!(dir.join("zen%20%E2%98%95.counter").is_file());
+    let (loaded, errors) = CounterSet::load_dir(&dir).unwrap();
+    assert!(errors.is_empty());
+    assert_eq!(loaded, set);
+
+    set.remove("pasta");
+    std::fs::write(dir.join("notes.txt"), "not a counter").unwrap();
+    set.save_dir(&dir).unwrap();
+    assert!(!dir.join("pasta.counter").exists());
+    assert!(dir.join("notes.txt").exists());
+    assert_eq!(CounterSet::load_dir(&dir).unwrap().0, set);
+
+    std::fs::write(dir.join("broken.counter"), "0\n180\nSideways").unwrap();
+    std::fs::write(dir.join("bad%zz.counter"), "0\n180\nDown").unwrap();
+    let (loaded, errors) = CounterSet::<i64>::load_dir(&dir).unwrap();
+    assert_eq!(loaded, set);
+    let errors: Vec<_> = errors
+        .iter()
+        .map(|(path, error)| (path.file_name().unwrap().to_str().unwrap(), error))
+        .collect();
+    assert!(matches!(
+        errors[..],
+        [
+            ("bad%zz.counter", PersistError::InvalidName),
+            ("broken.counter", PersistError::Invalid(_)),
+        ]
+    ));
+
+    assert!(CounterSet::<i64>::load_dir(dir.join("missing")).is_err());
+}
+
+#[test]
+fn cooldown() {
+    let mut cooldown = Cooldown::<i64>::new(10);
+    assert!(cooldown.ready_at(0));
+    assert_eq!(cooldown.remaining_at(0), 0);
+    assert_eq!(cooldown.trigger_at(0), Ok(()));
+    assert!(!cooldown.ready_at(9));
+    assert_eq!(cooldown.remaining_at(9), 1);
+    assert_eq!(cooldown.trigger_at(9), Err(NotReady));
+    // A refused trigger doesn't restart the countdown
+    assert_eq!(cooldown.remaining_at(9), 1);
+    assert!(cooldown.ready_at(10));
+    assert_eq!(cooldown.trigger_at(15), Ok(()));
+    assert_eq!(cooldown.remaining_at(15), 10);
+    cooldown.reset();
+    assert!(cooldown.ready());
+    assert_eq!(cooldown.trigger(), Ok(()));
+    assert_eq!(cooldown.remaining(), 10);
+}
+
+#[test]
+fn cooldown_charges() {
+    let mut cooldown = Cooldown::<i64>::with_charges(10, 3);
+    assert_eq!(cooldown.max_charges(), 3);
+    assert_eq!(cooldown.charges_at(0), 3);
+    for _ in 0..3 {
+        assert_eq!(cooldown.trigger_at(2), Ok(()));
+    }
+    assert_eq!(cooldown.trigger_at(2), Err(NotReady));
+    assert_eq!(cooldown.remaining_at(2), 10);
+    // Charges come back one after the other
+    assert_eq!(cooldown.charges_at(12), 1);
+    assert_eq!(cooldown.charges_at(21), 1);
+    assert_eq!(cooldown.charges_at(22), 2);
+    // Using a charge mid-recharge queues it behind the others
+    assert_eq!(cooldown.trigger_at(25), Ok(()));
+    assert_eq!(cooldown.charges_at(32), 2);
+    assert_eq!(cooldown.charges_at(42), 3);
+    assert_eq!(Cooldown::<i64>::with_charges(10, 0).max_charges(), 1);
+}
+
+#[test]
+fn relative_counter() {
+    let mut countdown = RelativeCounter::<i64>::down(90).unwrap();
+    assert_eq!(countdown.to_string(), "00:01:30");
+    for _ in 0..30 {
+        countdown.tick(2).unwrap();
+    }
+    assert_eq!(countdown.elapsed(), 60);
+    assert_eq!(countdown.remaining(), 30);
+    assert_eq!(countdown.counter(), (0, 0, 30));
+    assert!(!countdown.is_finished());
+    countdown.tick(40).unwrap();
+    assert!(countdown.is_finished());
+    assert_eq!(countdown.to_string(), "00:00:00");
+    countdown.tick(-45).unwrap();
+    assert_eq!(countdown.to_string(), "00:00:35");
+    assert!(countdown.tick(i64::MAX).is_err());
+    countdown.rewind();
+    assert_eq!(countdown.elapsed(), 0);
+
+    let mut stopwatch = RelativeCounter::<i64>::up();
+    stopwatch.tick(3725).unwrap();
+    assert_eq!(stopwatch.direction(), Direction::Up);
+    assert_eq!(stopwatch.to_string(), "01:02:05");
+}
+
+#[test]
+fn relative_counter_ignores_now() {
+    // Far away from the test backend's `now` of 0
+    let counter = Counter::down(Some(1_000_000), Some(1_000_600));
+    let mut relative = RelativeCounter::new(counter);
+    assert_eq!(relative.position(), 1_000_000);
+    relative.tick(100).unwrap();
+    assert_eq!(relative.breakdown().total_seconds(), 500);
+    assert_eq!(
+        format!("{:?}", relative),
+        "RelativeCounter { direction: Down, start: 1000000, end: 1000600, \
+         position: 1000100, showing: \"00:08:20\" }"
+    );
+}
+
+#[test]
+fn relative_counter_file_round_trip() {
+    let mut relative = RelativeCounter::<i64>::down(600).unwrap();
+    relative.counter.clamp_to_end(true);
+    relative.tick(250).unwrap();
+    relative
+        .to_file("/tmp/counter_test_file_relative.txt")
+        .unwrap();
+    assert_eq!(
+        std::fs::read_to_string("/tmp/counter_test_file_relative.txt").unwrap(),
+        "250\n0\n600\nDown\nstop_at_end=true"
+    );
+    let loaded = RelativeCounter::from_file("/tmp/counter_test_file_relative.txt").unwrap();
+    assert_eq!(loaded, relative);
+    assert_eq!(loaded.to_string(), "00:05:50");
+
+    std::fs::write(
+        "/tmp/counter_test_file_relative_bad.txt",
+        "later\n0\n1\nDown",
+    )
+    .unwrap();
+    assert!(RelativeCounter::<i64>::from_file("/tmp/counter_test_file_relative_bad.txt").is_err());
+}
+
+#[test]
+fn cooldown_saturates() {
+    let mut cooldown = Cooldown::<i64>::new(10);
+    assert_eq!(cooldown.trigger_at(i64::MAX - 5), Ok(()));
+    assert_eq!(cooldown.remaining_at(i64::MAX - 5), 5);
+}
+
+#[test]
+fn reset() {
+    let mut counter = Counter::down(Some(-10), Some(100));
+    counter.try_move_start(-5).unwrap();
+    counter.try_move_end(30).unwrap();
+    counter.flip();
+    counter.pause();
+    counter.reset();
+    assert_eq!(
+        (counter.start, counter.end, counter.direction),
+        (-10, 100, Direction::Down)
+    );
+    assert!(!counter.is_paused());
+}
+
+#[test]
+fn reset_after_reload() {
+    let mut counter = Counter::up(Some(-10), Some(100));
+    counter.try_move_end(30).unwrap();
+    counter.flip();
+    counter.to_file("/tmp/counter_test_file_reset.txt").unwrap();
+    assert_eq!(
+        std::fs::read_to_string("/tmp/counter_test_file_reset.txt").unwrap(),
+        "-10\n130\nDown\ninitial_end=100\ninitial_direction=Up"
+    );
+
+    let mut loaded = Counter::<i64>::from_file("/tmp/counter_test_file_reset.txt").unwrap();
+    assert_eq!(loaded, counter);
+    loaded.reset();
+    assert_eq!(loaded, Counter::up(Some(-10), Some(100)));
+
+    std::fs::write(
+        "/tmp/counter_test_file_reset.txt",
+        "-10\n130\nDown\ninitial_direction=Sideways",
+    )
+    .unwrap();
+    let error = Counter::<i64>::from_file("/tmp/counter_test_file_reset.txt").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "File does not contain valid initial_direction data"
+    );
+}
+
+#[test]
+fn restart() {
+    let mut counter = Counter::down(Some(-400), Some(-100));
+    counter.pause();
+    counter.restart().unwrap();
+    assert_eq!((counter.start, counter.end), (0, 300));
+    assert_eq!(counter.to_string(), "00:05:00");
+    assert!(!counter.is_paused());
+
+    let mut counter = Counter::up(Some(-50), Some(10));
+    counter.restart().unwrap();
+    assert_eq!((counter.start, counter.end), (0, 60));
+    assert_eq!(counter.to_string(), "00:00:00");
+}
+
+#[test]
+fn restart_overflow() {
+    use crate::unix::UnixSeconds;
+
+    let mut counter = Counter::down(Some(UnixSeconds(i64::MIN)), Some(UnixSeconds(0)));
+    assert!(counter.restart().is_err());
+    assert_eq!(
+        (counter.start, counter.end),
+        (UnixSeconds(i64::MIN), UnixSeconds(0))
+    );
+}
+
+#[test]
+fn builder() {
+    let counter = CounterBuilder::new().start(-10).end(20).build();
+    assert_eq!(counter, Ok(Counter::down(Some(-10), Some(20))));
+
+    let counter = CounterBuilder::<i64>::new().lasting(300).build();
+    assert_eq!(counter, Ok(Counter::down(Some(0), Some(300))));
+
+    let counter = CounterBuilder::new()
+        .start(-50)
+        .lasting(20)
+        .direction(Direction::Up)
+        .build();
+    assert_eq!(counter, Ok(Counter::up(Some(-50), Some(-30))));
+
+    let counter = CounterBuilder::new()
+        .start(-50)
+        .direction(Direction::Up)
+        .build();
+    assert_eq!(counter, Ok(Counter::up(Some(-50), Some(-50))));
+
+    let counter = CounterBuilder::new().end(60).build();
+    assert_eq!(counter, Ok(Counter::down(Some(0), Some(60))));
+}
+
+#[test]
+fn builder_rejects_nonsense() {
+    let build = |builder: CounterBuilder<i64>| builder.build().unwrap_err();
+    assert_eq!(build(CounterBuilder::new()), BuildError::MissingEndpoints);
+    assert_eq!(
+        build(CounterBuilder::new().direction(Direction::Up)),
+        BuildError::MissingEndpoints
+    );
+    assert_eq!(
+        build(CounterBuilder::new().start(0)),
+        BuildError::MissingEnd
+    );
+    assert_eq!(
+        build(CounterBuilder::new().end(10).lasting(10)),
+        BuildError::ConflictingEnd
+    );
+    assert_eq!(
+        build(CounterBuilder::new().start(10).end(0)),
+        BuildError::EndBeforeStart
+    );
+    assert_eq!(
+        build(CounterBuilder::new().lasting(-1)),
+        BuildError::EndBeforeStart
+    );
+    assert_eq!(
+        build(CounterBuilder::new().start(1).lasting(i64::MAX)),
+        BuildError::Overflow
+    );
+}
+
+#[test]
+fn progress() {
+    assert_eq!(Counter::down(Some(0), Some(100)).progress(), 0.0);
+    assert_eq!(Counter::down(Some(-50), Some(50)).progress(), 0.5);
+    assert_eq!(Counter::up(Some(-50), Some(50)).progress(), 0.5);
+    assert_eq!(Counter::down(Some(-100), Some(0)).progress(), 1.0);
+    assert_eq!(Counter::down(Some(-100), Some(-10)).progress(), 1.0);
+    assert_eq!(Counter::down(Some(10), Some(110)).progress(), 0.0);
+    assert_eq!(Counter::down(Some(-1), Some(2)).progress(), 1.0 / 3.0);
+}
+
+#[test]
+fn progress_of_empty_window() {
+    assert_eq!(Counter::<i64>::zero().progress(), 1.0);
+    assert_eq!(Counter::down(Some(5), Some(5)).progress(), 0.0);
+    assert_eq!(Counter::down(Some(-5), Some(-5)).progress(), 1.0);
+    assert_eq!(Counter::down(Some(10), Some(5)).progress(), 0.0);
+    assert_eq!(Counter::down(Some(10), Some(-5)).progress(), 1.0);
+}
+
+#[test]
+fn is_finished() {
+    assert!(!Counter::down(Some(-10), Some(1)).is_finished());
+    assert!(Counter::down(Some(-10), Some(0)).is_finished());
+    assert!(Counter::down(Some(-10), Some(-1)).is_finished());
+
+    assert!(!Counter::up(Some(-10), Some(1)).is_finished());
+    assert!(Counter::up(Some(-10), Some(0)).is_finished());
+    // Open-ended
+    assert!(!Counter::up(Some(-10), Some(-10)).is_finished());
+    assert!(!Counter::up(Some(-10), Some(-20)).is_finished());
+
+    let mut counter = Counter::down(Some(-10), Some(5));
+    counter.paused_at = Some(4);
+    assert!(!counter.is_finished());
+    counter.paused_at = Some(5);
+    assert!(counter.is_finished());
+}
+
+#[test]
+fn overtime() {
+    assert_eq!(Counter::down(Some(-100), Some(-90)).overtime(), Some(90));
+    assert_eq!(Counter::down(Some(-100), Some(0)).overtime(), Some(0));
+    assert_eq!(Counter::down(Some(-100), Some(1)).overtime(), None);
+    assert_eq!(Counter::up(Some(-100), Some(-30)).overtime(), Some(30));
+    assert_eq!(Counter::up(Some(-100), Some(-100)).overtime(), None);
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn is_finished_subsecond_boundary() {
+    use crate::types::TimeStamp;
+
+    let now = TimeStamp::now();
+    let soon = now + crate::types::Duration::from(::chrono::Duration::milliseconds(500));
+    assert!(!Counter::down(Some(now), Some(soon)).is_finished());
+}
+
+#[test]
+fn zero_counter() {
+    let mut counter = Counter::<i64>::zero();
+    assert!(counter.is_zero_length());
+    assert_eq!(counter.direction, Direction::Down);
+    assert_eq!(counter.to_string(), "00:00:00");
+    assert_eq!(counter.seconds(), 0);
+    assert!(counter.is_finished());
+    counter.flip();
+    assert_eq!(counter.to_string(), "00:00:00");
+
+    assert!(!Counter::down(Some(0), Some(1)).is_zero_length());
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn zero_counter_timestamp() {
+    let counter = Counter::<crate::types::TimeStamp>::zero();
+    assert!(counter.is_zero_length());
+    assert_eq!(counter.to_string(), "00:00:00");
+}
+
+#[test]
+fn hms_constructors() {
+    let counter = Counter::<i64>::down_for_hms(0, 25, 0).unwrap();
+    assert_eq!(counter, Counter::down(Some(0), Some(1500)));
+    assert_eq!(counter.to_string(), "00:25:00");
+
+    let counter = Counter::<i64>::down_for_dhms(1, 2, 3, 4).unwrap();
+    assert_eq!(counter.to_string(), "26:03:04");
+
+    let counter = Counter::<i64>::up_since_hms(100, 0, 59).unwrap();
+    assert_eq!(counter.start, -360059);
+    assert_eq!(counter.direction, Direction::Up);
+    assert_eq!(counter.to_string(), "100:00:59");
+}
+
+#[test]
+fn hms_constructors_validate() {
+    assert_eq!(
+        Counter::<i64>::down_for_hms(0, 60, 0),
+        Err(InvalidHms::OutOfRange)
+    );
+    assert_eq!(
+        Counter::<i64>::up_since_hms(0, 0, 60),
+        Err(InvalidHms::OutOfRange)
+    );
+    assert_eq!(
+        Counter::<i64>::down_for_hms(-1, 0, 0),
+        Err(InvalidHms::OutOfRange)
+    );
+    assert_eq!(
+        Counter::<i64>::down_for_dhms(1, 24, 0, 0),
+        Err(InvalidHms::OutOfRange)
+    );
+    assert!(Counter::<i64>::down_for_hms(24, 0, 0).is_ok());
+    assert_eq!(
+        Counter::<i64>::down_for_dhms(i64::MAX, 0, 0, 0),
+        Err(InvalidHms::Overflow)
+    );
+    assert_eq!(
+        Counter::<i64>::down_for_hms(i64::MAX / 3600, 59, 59),
+        Err(InvalidHms::Overflow)
+    );
+}
+
+#[test]
+fn debug_shows_live_value() {
+    let counter = Counter::down(Some(-1), Some(599));
+    assert_eq!(
+        format!("{:?}", counter),
+        r#"Counter { direction: Down, start: -1, end: 599, paused_at: None, showing: "00:09:59" }"#
+    );
+
+    let counter = Counter::up(Some(-61), Some(0));
+    assert_eq!(
+        format!("{:?}", counter),
+        r#"Counter { direction: Up, start: -61, end: 0, paused_at: None, showing: "00:01:01" }"#
+    );
+
+    // Inconsistent states still format, clamped like `Display`
+    let counter = Counter::down(Some(i64::MAX), Some(i64::MIN));
+    assert_eq!(
+        format!("{:?}", counter),
+        format!(
+            r#"Counter {{ direction: Down, start: {}, end: {}, paused_at: None, showing: "00:00:00" }}"#,
+            i64::MAX,
+            i64::MIN
+        )
+    );
+}
+
+#[cfg(feature = "types")]
+#[test]
+fn duration_debug_shows_seconds() {
+    use crate::types::Duration;
+
+    assert_eq!(
+        format!("{:?}", Duration::seconds(90)),
+        "Duration { seconds: 90 }"
+    );
+    assert_eq!(
+        format!(
+            "{:?}",
+            Duration::from(::chrono::Duration::milliseconds(-1500))
+        ),
+        "Duration { seconds: -1, nanos: -500000000 }"
+    );
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn tracing_events() {
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Buffer {
+        fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(bytes);
+            Ok(bytes.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let buffer = Buffer::default();
+    let writer = buffer.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing_subscriber::filter::LevelFilter::DEBUG)
+        .with_ansi(false)
+        .with_writer(move || writer.clone())
+        .finish();
+    tracing::subscriber::with_default(subscriber, || {
+        let mut counter = Counter::down(Some(0), Some(10));
+        counter.try_move_end(5).unwrap();
+        counter.try_move_start(-3).unwrap();
+        counter.flip();
+        counter
+            .to_file("/tmp/counter_test_file_tracing.txt")
+            .unwrap();
+        Counter::<i64>::from_file("/tmp/counter_test_file_tracing.txt").unwrap();
+    });
+
+    let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 5, "{output}");
+    assert!(lines[0].contains("moved end delta=5 length=15"));
+    assert!(lines[1].contains("moved start delta=-3 length=18"));
+    assert!(lines[2].contains("flipped counter direction=Up"));
+    assert!(lines[3].contains("saved counter"));
+    assert!(lines[4].contains("loaded counter"));
+    assert!(lines[4].contains("start=-3 end=15 direction=Up"));
+}
+
 proptest! {
     #[test]
     fn always_correct_format(s in -5_000_000i64..5_000_000_i64,
@@ -182,8 +4240,8 @@ proptest! {
         let hours: i64 = counter.next().unwrap().parse().unwrap();
         let minutes: i64 = counter.next().unwrap().parse().unwrap();
         let seconds: i64 = counter.next().unwrap().parse().unwrap();
-        assert!(0 <= seconds && seconds < 60);
-        assert!(0 <= minutes && minutes < 60);
+        assert!((0..60).contains(&seconds));
+        assert!((0..60).contains(&minutes));
         assert!(0 <= hours);
 
     }
@@ -0,0 +1,143 @@
+//! This module provides `WrappingTicks` and `TickDuration` types which
+//! implement the `Time` and `TimeUnits` traits respectively, for targets whose
+//! only time source is a free-running `u32` tick counter that wraps around.
+//! Tick arithmetic itself does not allocate, but the crate needs `std`, and
+//! the provided `TimeUnits::humanize` and `parse_any` allocate.
+//!
+//! Differences between two tick values are interpreted as going forward if
+//! they are at most half of the `u32` range, and backward otherwise. The
+//! longest measurable span is therefore `i32::MAX` ticks, which at 1000
+//! ticks per second is a little under 24 days and 21 hours. The tick rate
+//! must be at least 1, and using a rate of zero fails to compile:
+//! ```rust,compile_fail
+//! # use countrs::ticks::TickDuration;
+//! # use countrs::TimeUnits;
+//! let broken = TickDuration::<0>(1000).num_seconds();
+//! ```
+use crate::errors::{TimeOverflow, TimeParserError};
+use crate::times::{Time, TimeUnits};
+use core::fmt::{self, Display, Formatter};
+use core::ops::{Neg, Sub};
+use core::str::FromStr;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+static CURRENT: AtomicU32 = AtomicU32::new(0);
+
+/// A tick count read from a wrapping `u32` counter running at
+/// `TICKS_PER_SECOND`. `now()` returns the last value passed to
+/// `WrappingTicks::set_current`, which is shared between all tick rates.
+#[derive(Debug, PartialEq, Eq, Default, Clone, Copy, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct WrappingTicks<const TICKS_PER_SECOND: u32>(pub u32);
+
+/// A signed number of ticks at `TICKS_PER_SECOND`
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Default, Clone, Copy, Hash)]
+pub struct TickDuration<const TICKS_PER_SECOND: u32>(pub i64);
+
+impl<const TPS: u32> WrappingTicks<TPS> {
+    /// Updates the value returned by `now()`, e.g. from a timer interrupt.
+    pub fn set_current(ticks: u32) {
+        CURRENT.store(ticks, Ordering::Relaxed);
+    }
+}
+
+impl<const TPS: u32> TickDuration<TPS> {
+    /// `TPS` as a divisor, checked when the rate is used rather than at
+    /// runtime
+    const RATE: i64 = {
+        assert!(TPS > 0, "TICKS_PER_SECOND must be at least 1");
+        TPS as i64
+    };
+}
+
+impl<const TPS: u32> Display for WrappingTicks<TPS> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const TPS: u32> FromStr for WrappingTicks<TPS> {
+    type Err = TimeParserError;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        string
+            .parse()
+            .map(WrappingTicks)
+            .map_err(|_| TimeParserError::new(&["u32 ticks"]))
+    }
+}
+
+/// Wrap-aware: differences up to half the `u32` range count as forward.
+impl<const TPS: u32> Sub<Self> for WrappingTicks<TPS> {
+    type Output = TickDuration<TPS>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        TickDuration(i64::from(self.0.wrapping_sub(other.0) as i32))
+    }
+}
+
+/// Saturates at the limits of `i64` ticks.
+impl<const TPS: u32> Neg for TickDuration<TPS> {
+    type Output = TickDuration<TPS>;
+
+    fn neg(self) -> Self::Output {
+        TickDuration(self.0.saturating_neg())
+    }
+}
+
+impl<const TPS: u32> Time for WrappingTicks<TPS> {
+    type Duration = TickDuration<TPS>;
+
+    fn now() -> Self {
+        WrappingTicks(CURRENT.load(Ordering::Relaxed))
+    }
+
+    /// Fails for durations longer than the measurable span.
+    fn add_seconds(self, duration: TickDuration<TPS>) -> Result<Self, TimeOverflow> {
+        let ticks = i32::try_from(duration.0).map_err(|_| TimeOverflow)?;
+        Ok(WrappingTicks(self.0.wrapping_add(ticks as u32)))
+    }
+}
+
+impl<const TPS: u32> TimeUnits for TickDuration<TPS> {
+    /// Saturates at the limits of `i64` ticks.
+    fn seconds(seconds: i64) -> Self {
+        TickDuration(seconds.saturating_mul(Self::RATE))
+    }
+
+    fn try_seconds(seconds: i64) -> Result<Self, TimeOverflow> {
+        Ok(TickDuration(
+            seconds.checked_mul(Self::RATE).ok_or(TimeOverflow)?,
+        ))
+    }
+
+    fn num_seconds(&self) -> i64 {
+        self.0 / Self::RATE
+    }
+
+    fn subsec_nanos(&self) -> i64 {
+        let rest = i128::from(self.0 % Self::RATE);
+        (rest * 1_000_000_000 / i128::from(Self::RATE)) as i64
+    }
+}
+
+/// Ticks alongside whole seconds, e.g. "1500 ticks (1s)"
+#[cfg(feature = "defmt")]
+impl<const TPS: u32> defmt::Format for TickDuration<TPS> {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(f, "{=i64} ticks ({=i64}s)", self.0, self.num_seconds())
+    }
+}
+
+impl<const TPS: u32> From<i64> for TickDuration<TPS> {
+    /// Interprets `num` as seconds, like the other backends.
+    fn from(num: i64) -> TickDuration<TPS> {
+        TickDuration::seconds(num)
+    }
+}
+
+impl_duration_cmp!([const TPS: u32] WrappingTicks<TPS> => TickDuration<TPS>);
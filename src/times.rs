@@ -1,17 +1,224 @@
-use crate::errors::TimeOverflow;
+use crate::errors::{DurationParserError, TimeOverflow};
+use crate::parse::{parse_duration, parse_hms};
+use std::ops::Sub;
+
+/// The units used when rendering durations for humans, largest first.
+pub(crate) const UNITS: [(i64, &str); 4] = [(86400, "d"), (3600, "h"), (60, "m"), (1, "s")];
+
+/// Calendar-free units that times can be rounded to.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum Unit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+}
+
+impl Unit {
+    /// The length of the unit in seconds
+    pub fn seconds(self) -> i64 {
+        match self {
+            Unit::Second => 1,
+            Unit::Minute => 60,
+            Unit::Hour => 3600,
+            Unit::Day => 86400,
+        }
+    }
+}
+
+/// An adjustment in whole units, for `Counter::move_start_by`/`move_end_by`.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum Adjust {
+    Seconds(i64),
+    Minutes(i64),
+    Hours(i64),
+    Days(i64),
+}
+
+impl Adjust {
+    /// The adjustment in seconds, or `None` if that does not fit into `i64`
+    pub fn seconds(self) -> Option<i64> {
+        let (amount, unit) = match self {
+            Adjust::Seconds(amount) => (amount, Unit::Second),
+            Adjust::Minutes(amount) => (amount, Unit::Minute),
+            Adjust::Hours(amount) => (amount, Unit::Hour),
+            Adjust::Days(amount) => (amount, Unit::Day),
+        };
+        amount.checked_mul(unit.seconds())
+    }
+}
+
+/// How to round values that do not fall on a boundary. `Nearest` breaks
+/// ties (values exactly halfway between two boundaries) towards the later one.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Rounding {
+    Floor,
+    Ceil,
+    Nearest,
+}
 
 pub trait Time {
     type Duration;
 
     fn now() -> Self;
 
+    /// Adds a possibly negative duration. Implementations must return
+    /// `TimeOverflow` rather than panic or wrap when the result is out of range
+    /// in either direction, since `Counter::try_move_start`/`try_move_end`
+    /// rely on this for negative adjustments.
     fn add_seconds(self, duration: Self::Duration) -> Result<Self, TimeOverflow>
     where
         Self: Sized;
+
+    /// The time from `self` until `end`, in a window that starts at `start`,
+    /// negative once `self` is past `end`. This is `end - self`, unless
+    /// the backend's times wrap around, like times of day. There, `end - self`
+    /// is never negative, and this must be overridden to tell times before
+    /// `end` from those after it.
+    fn until_end(self, start: Self, end: Self) -> Self::Duration
+    where
+        Self: Sized + Sub<Self, Output = Self::Duration>,
+    {
+        let _ = start;
+        end - self
+    }
+
+    /// The latest representable time, if the backend has such a limit.
+    fn max_value() -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
+
+    /// The earliest representable time, if the backend has such a limit.
+    fn min_value() -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
 }
 
 pub trait TimeUnits {
     fn seconds(seconds: i64) -> Self;
 
     fn num_seconds(&self) -> i64;
+
+    /// Like `seconds`, but fails instead of panicking or wrapping when the
+    /// backend cannot represent the duration. Backends with a narrower range
+    /// than `i64` seconds should override this.
+    fn try_seconds(seconds: i64) -> Result<Self, TimeOverflow>
+    where
+        Self: Sized,
+    {
+        Ok(Self::seconds(seconds))
+    }
+
+    /// Makes a duration of whole weeks, failing if it does not fit.
+    fn weeks(weeks: i64) -> Result<Self, TimeOverflow>
+    where
+        Self: Sized,
+    {
+        Self::try_seconds(weeks.checked_mul(604800).ok_or(TimeOverflow)?)
+    }
+
+    /// The part below a whole second, in nanoseconds, with the sign of the
+    /// duration. Backends with only whole seconds keep the default of 0.
+    fn subsec_nanos(&self) -> i64 {
+        0
+    }
+
+    /// Returns the total number of full weeks
+    fn num_weeks(&self) -> i64 {
+        self.num_seconds() / 604800
+    }
+
+    /// Parses bare seconds (`"5400"`), compact units (`"1h30m"`, `"2w 3d"`)
+    /// or ISO 8601 durations (`"PT1H30M"`), each optionally signed.
+    fn parse_any(string: &str) -> Result<Self, DurationParserError>
+    where
+        Self: Sized,
+    {
+        Self::try_seconds(parse_duration(string)?)
+            .map_err(|_| DurationParserError::new(0, "duration is too large"))
+    }
+
+    /// Parses the `Counter` display format, `"HH(+):MM:SS"`.
+    fn from_hms_str(string: &str) -> Result<Self, DurationParserError>
+    where
+        Self: Sized,
+    {
+        Self::try_seconds(parse_hms(string)?)
+            .map_err(|_| DurationParserError::new(0, "duration is too large"))
+    }
+
+    /// Returns the total number of full minutes
+    fn num_minutes(&self) -> i64 {
+        self.num_seconds() / 60
+    }
+
+    /// Returns the total number of full hours
+    fn num_hours(&self) -> i64 {
+        self.num_seconds() / 3600
+    }
+
+    /// Returns the total number of full days
+    fn num_days(&self) -> i64 {
+        self.num_seconds() / 86400
+    }
+
+    /// Splits into (days, hours, minutes, seconds), each carrying the sign
+    /// of the duration.
+    fn dhms(&self) -> (i64, i64, i64, i64) {
+        let seconds = self.num_seconds();
+        (
+            seconds / 86400,
+            seconds / 3600 % 24,
+            seconds / 60 % 60,
+            seconds % 60,
+        )
+    }
+
+    /// Splits into (weeks, days, hours, minutes, seconds), each carrying the
+    /// sign of the duration.
+    fn wdhms(&self) -> (i64, i64, i64, i64, i64) {
+        let (days, hours, minutes, seconds) = self.dhms();
+        (days / 7, days % 7, hours, minutes, seconds)
+    }
+
+    /// Renders the largest two non-zero units, e.g. "1d 4h" or "-2m 5s".
+    /// Durations backed only by whole seconds never show "<1s".
+    fn humanize(&self) -> String {
+        humanize(self.num_seconds(), 0)
+    }
+}
+
+/// Shared implementation of `TimeUnits::humanize`, taking the whole seconds
+/// and the sub-second remainder (with the same sign) of a duration.
+pub(crate) fn humanize(seconds: i64, subsec_nanos: i64) -> String {
+    let sign = if seconds < 0 || subsec_nanos < 0 {
+        "-"
+    } else {
+        ""
+    };
+    let mut rest = seconds.unsigned_abs();
+    if rest == 0 {
+        return match subsec_nanos {
+            0 => "0s".to_string(),
+            _ => format!("{sign}<1s"),
+        };
+    }
+
+    let mut parts = Vec::with_capacity(2);
+    for (size, suffix) in UNITS {
+        let size = size as u64;
+        let amount = rest / size;
+        rest %= size;
+        if amount > 0 && parts.len() < 2 {
+            parts.push(format!("{amount}{suffix}"));
+        }
+    }
+    format!("{sign}{}", parts.join(" "))
 }
@@ -0,0 +1,162 @@
+//! This module provides `TrackedCounter`, which keeps a bounded history of
+//! adjustments to a `Counter` that can be undone and redone.
+use crate::errors::{EmptyHistory, TimeOverflow};
+use crate::times::{Time, TimeUnits};
+use crate::{Counter, Direction};
+use std::ops::Sub;
+
+/// What an `Adjustment` did, with moves given in seconds.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum AdjustmentKind {
+    MoveStart(i64),
+    MoveEnd(i64),
+    Shift(i64),
+    Flip,
+}
+
+/// One change recorded by a `TrackedCounter`, along with the state of the
+/// counter before and after it.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Adjustment<T> {
+    pub kind: AdjustmentKind,
+    /// When the change was made
+    pub at: T,
+    before: (T, T, Direction),
+    after: (T, T, Direction),
+}
+
+/// A `Counter` whose `try_move_start`, `try_move_end`, `shift` and `flip`
+/// are recorded, keeping at most `max_entries` of them.
+/// ```rust
+/// # use countrs::{Counter, TrackedCounter};
+/// # use countrs::types::TimeStamp;
+/// let mut counter = TrackedCounter::new(Counter::<TimeStamp>::down_showing("00:05:00").unwrap(), 10);
+/// counter.try_move_end(600).unwrap();
+/// assert_eq!(counter.counter().to_string(), "00:14:59");
+/// counter.undo().unwrap();
+/// assert_eq!(counter.counter().to_string(), "00:04:59");
+/// counter.redo().unwrap();
+/// assert_eq!(counter.counter().to_string(), "00:14:59");
+/// ```
+#[derive(Clone)]
+pub struct TrackedCounter<T> {
+    counter: Counter<T>,
+    history: Vec<Adjustment<T>>,
+    undone: Vec<Adjustment<T>>,
+    max_entries: usize,
+}
+
+impl<T, D> TrackedCounter<T>
+where
+    T: Copy + Time<Duration = D> + Sub<T, Output = D>,
+    D: TimeUnits,
+{
+    pub fn new(counter: Counter<T>, max_entries: usize) -> TrackedCounter<T> {
+        TrackedCounter {
+            counter,
+            history: Vec::new(),
+            undone: Vec::new(),
+            max_entries,
+        }
+    }
+
+    pub fn counter(&self) -> &Counter<T> {
+        &self.counter
+    }
+
+    pub fn into_inner(self) -> Counter<T> {
+        self.counter
+    }
+
+    /// The adjustments that `undo` would revert, oldest first
+    pub fn history(&self) -> &[Adjustment<T>] {
+        &self.history
+    }
+
+    pub fn try_move_start(&mut self, seconds: impl Into<D>) -> Result<(), TimeOverflow> {
+        let seconds = seconds.into();
+        let kind = AdjustmentKind::MoveStart(seconds.num_seconds());
+        self.track(kind, |counter| counter.try_move_start(seconds))
+    }
+
+    pub fn try_move_end(&mut self, seconds: impl Into<D>) -> Result<(), TimeOverflow> {
+        let seconds = seconds.into();
+        let kind = AdjustmentKind::MoveEnd(seconds.num_seconds());
+        self.track(kind, |counter| counter.try_move_end(seconds))
+    }
+
+    pub fn shift(&mut self, amount: impl Into<D>) -> Result<(), TimeOverflow>
+    where
+        D: Copy,
+    {
+        let amount = amount.into();
+        let kind = AdjustmentKind::Shift(amount.num_seconds());
+        self.track(kind, |counter| counter.shift(amount))
+    }
+
+    pub fn flip(&mut self) {
+        let before = self.state();
+        self.counter.flip();
+        self.push(AdjustmentKind::Flip, before);
+    }
+
+    /// Reverts the latest adjustment in `history`.
+    pub fn undo(&mut self) -> Result<(), EmptyHistory> {
+        let adjustment = self.history.pop().ok_or(EmptyHistory)?;
+        self.restore(adjustment.before);
+        self.undone.push(adjustment);
+        Ok(())
+    }
+
+    /// Reapplies the latest adjustment reverted by `undo`. Any new adjustment
+    /// in between discards what could be redone.
+    pub fn redo(&mut self) -> Result<(), EmptyHistory> {
+        let adjustment = self.undone.pop().ok_or(EmptyHistory)?;
+        self.restore(adjustment.after);
+        self.record(adjustment);
+        Ok(())
+    }
+
+    fn track<F>(&mut self, kind: AdjustmentKind, change: F) -> Result<(), TimeOverflow>
+    where
+        F: FnOnce(&mut Counter<T>) -> Result<(), TimeOverflow>,
+    {
+        let before = self.state();
+        change(&mut self.counter)?;
+        self.push(kind, before);
+        Ok(())
+    }
+
+    /// Records a new adjustment, which discards what could be redone.
+    fn push(&mut self, kind: AdjustmentKind, before: (T, T, Direction)) {
+        let adjustment = Adjustment {
+            kind,
+            at: T::now(),
+            before,
+            after: self.state(),
+        };
+        self.undone.clear();
+        self.record(adjustment);
+    }
+
+    fn record(&mut self, adjustment: Adjustment<T>) {
+        if self.max_entries == 0 {
+            return;
+        }
+        if self.history.len() == self.max_entries {
+            self.history.remove(0);
+        }
+        self.history.push(adjustment);
+    }
+
+    fn state(&self) -> (T, T, Direction) {
+        (self.counter.start, self.counter.end, self.counter.direction)
+    }
+
+    fn restore(&mut self, (start, end, direction): (T, T, Direction)) {
+        self.counter.start = start;
+        self.counter.end = end;
+        self.counter.direction = direction;
+        self.counter.touch();
+    }
+}
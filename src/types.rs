@@ -1,39 +1,506 @@
 //! This module provides `TimeStamp` and `Duration` types which implement
 //! the `Time` and `TimeUnits` traits respectively, for use with `Counter`
-//! and its methods.
-use crate::errors::{TimeOverflow, TimeParserError};
+//! and its methods, as well as `TimeOfDay` for daily timers.
+use crate::chrono::{checked_seconds, deadline_string};
+use crate::errors::{
+    DurationParserError, InvalidFormat, InvalidSpec, TimeOverflow, TimeParserError,
+};
+use crate::times::humanize;
 use crate::times::Time;
 use crate::TimeUnits;
-use chrono::{self, DateTime, Utc};
+use crate::{Counter, Direction, Rounding, Unit};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{
+    self, DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone, Utc,
+};
 use std::fmt::{self, Display, Formatter};
-use std::ops::{Add, Sub};
+use std::io;
+use std::ops::{Add, Neg, Sub};
+use std::path::Path;
 use std::str::FromStr;
 
+/// Formats carrying their own offset, tried by `TimeStamp::parse_flexible` after RFC 3339/2822.
+const OFFSET_FORMATS: [&str; 3] = [
+    "%Y-%m-%d %H:%M:%S%.f%:z",
+    "%Y-%m-%dT%H:%M%:z",
+    "%Y-%m-%d %H:%M%:z",
+];
+
+/// Offset-less formats, tried by `TimeStamp::parse_flexible` last.
+const NAIVE_FORMATS: [&str; 4] = [
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M",
+];
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+const TIME_OF_DAY_FORMAT: &str = "%H:%M:%S";
+
+const RELATIVE_FORMAT: &str = "now[±duration]";
+
+const FLEXIBLE_TRIED: [&str; 11] = [
+    "RFC 3339",
+    "RFC 2822",
+    OFFSET_FORMATS[0],
+    OFFSET_FORMATS[1],
+    OFFSET_FORMATS[2],
+    NAIVE_FORMATS[0],
+    NAIVE_FORMATS[1],
+    NAIVE_FORMATS[2],
+    NAIVE_FORMATS[3],
+    DATE_FORMAT,
+    RELATIVE_FORMAT,
+];
+
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Default, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct TimeStamp {
     time: DateTime<Utc>,
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 pub struct Duration {
     duration: chrono::Duration,
 }
 
+/// A UTC time of day that wraps around at midnight. The difference between two
+/// times of day is always in `0..24h`, measured forward from the right-hand side,
+/// so a `Counter` counting down to one targets its next occurrence.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Default, Clone, Copy, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct TimeOfDay(pub NaiveTime);
+
+/// Displays a `TimeStamp` with a pre-validated `strftime`-style format,
+/// as returned by `TimeStamp::display_as`.
+#[derive(Debug, Clone)]
+pub struct DisplayAs<'a> {
+    time: DateTime<Utc>,
+    items: StrftimeItems<'a>,
+}
+
+/// chrono only reports invalid specifiers while formatting, by failing
+/// `Display` (which makes `to_string` panic), so they are checked up front.
+fn strftime_items(fmt: &str) -> Result<StrftimeItems<'_>, InvalidFormat> {
+    let items = StrftimeItems::new(fmt);
+    match items.clone().any(|item| item == Item::Error) {
+        true => Err(InvalidFormat),
+        false => Ok(items),
+    }
+}
+
+impl TimeStamp {
+    /// Parses the strict `FromStr` formats, followed by a few common shapes like
+    /// `2025-06-01`, `2025-06-01 14:30` and `2025-06-01T14:30:00`, each with or
+    /// without a trailing `+HH:MM` offset. Inputs without an offset are
+    /// interpreted as UTC; use `parse_flexible_in` to supply another offset.
+    /// Finally, `now±duration` expressions are evaluated with `parse_relative`.
+    pub fn parse_flexible(string: &str) -> Result<TimeStamp, TimeParserError> {
+        Self::parse_flexible_in(string, Utc.fix())
+    }
+
+    /// Like `parse_flexible`, but interprets offset-less inputs in `offset`.
+    pub fn parse_flexible_in(
+        string: &str,
+        offset: FixedOffset,
+    ) -> Result<TimeStamp, TimeParserError> {
+        if let Ok(time) = string.parse() {
+            return Ok(time);
+        }
+        let with_offset = OFFSET_FORMATS
+            .iter()
+            .find_map(|format| DateTime::parse_from_str(string, format).ok());
+        let naive = || {
+            NAIVE_FORMATS
+                .iter()
+                .find_map(|format| NaiveDateTime::parse_from_str(string, format).ok())
+                .or_else(|| {
+                    NaiveDate::parse_from_str(string, DATE_FORMAT)
+                        .ok()
+                        .and_then(|date| date.and_hms_opt(0, 0, 0))
+                })
+                .and_then(|naive| offset.from_local_datetime(&naive).single())
+        };
+        with_offset
+            .or_else(naive)
+            .map(|time| TimeStamp { time: time.into() })
+            .or_else(|| Self::parse_relative(string, Self::now()).ok())
+            .ok_or(TimeParserError::new(&FLEXIBLE_TRIED))
+    }
+
+    /// Evaluates `now`, optionally followed by `+` or `-` and a duration in any
+    /// format `TimeUnits::parse_any` accepts, relative to the given `now`.
+    /// Whitespace around the operator is ignored, e.g. `"now - 15m"`.
+    pub fn parse_relative(string: &str, now: TimeStamp) -> Result<TimeStamp, TimeParserError> {
+        let error = TimeParserError::new(&[RELATIVE_FORMAT]);
+        let rest = string.trim().strip_prefix("now").ok_or(error)?.trim_start();
+        let (negative, duration) = match rest.chars().next() {
+            None => return Ok(now),
+            Some('+') => (false, &rest[1..]),
+            Some('-') => (true, &rest[1..]),
+            Some(_) => return Err(error),
+        };
+        let duration = Duration::parse_any(duration.trim_start())
+            .map_err(|_| error)?
+            .duration;
+        let time = match negative {
+            false => now.time.checked_add_signed(duration),
+            true => now.time.checked_sub_signed(duration),
+        };
+        Ok(TimeStamp {
+            time: time.ok_or(error)?,
+        })
+    }
+}
+
+impl TimeStamp {
+    /// Formats the time in UTC using chrono's `strftime`-style specifiers.
+    pub fn format(&self, fmt: &str) -> Result<String, InvalidFormat> {
+        Ok(self.display_as(fmt)?.to_string())
+    }
+
+    /// Like `format`, but shows the time as seen in `tz`.
+    pub fn format_in<Tz: TimeZone>(&self, fmt: &str, tz: &Tz) -> Result<String, InvalidFormat>
+    where
+        Tz::Offset: Display,
+    {
+        let items = strftime_items(fmt)?;
+        Ok(self
+            .time
+            .with_timezone(tz)
+            .format_with_items(items)
+            .to_string())
+    }
+
+    /// Returns an adapter implementing `Display` with the given format, for
+    /// use in `format!` and friends without an intermediate `String`.
+    /// ```rust
+    /// # use countrs::types::TimeStamp;
+    /// let time: TimeStamp = "2025-06-01T14:30:00Z".parse().unwrap();
+    /// assert_eq!(format!("due {}", time.display_as("%H:%M").unwrap()), "due 14:30");
+    /// ```
+    pub fn display_as<'a>(&self, fmt: &'a str) -> Result<DisplayAs<'a>, InvalidFormat> {
+        Ok(DisplayAs {
+            time: self.time,
+            items: strftime_items(fmt)?,
+        })
+    }
+}
+
+impl Display for DisplayAs<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.time.format_with_items(self.items.clone()))
+    }
+}
+
+impl TimeStamp {
+    /// Midnight (UTC) at the start of this timestamp's day.
+    pub fn start_of_day(&self) -> TimeStamp {
+        self.start_of_day_in(&Utc)
+    }
+
+    /// The start of the following day (UTC), i.e. the exclusive end of this one.
+    pub fn end_of_day(&self) -> TimeStamp {
+        self.end_of_day_in(&Utc)
+    }
+
+    /// Like `start_of_day`, but for the day as seen in `tz`. If the local
+    /// midnight is ambiguous because of a DST change, the earlier instant is
+    /// used. If it falls into a gap because the clocks jump forward, the
+    /// result is the earliest valid instant after the gap, i.e. the moment of
+    /// the jump, which local clocks show as the time they jumped to (e.g.
+    /// "01:00"). The instant one second before it is still on the day
+    /// before. Gaps that do not end on a whole local minute resolve to the
+    /// first whole minute after them.
+    pub fn start_of_day_in<Tz: TimeZone>(&self, tz: &Tz) -> TimeStamp {
+        let date = self.time.with_timezone(tz).date_naive();
+        day_start(tz, date).unwrap_or(*self)
+    }
+
+    /// Like `end_of_day`, but for the day as seen in `tz`, following the same
+    /// DST rules as `start_of_day_in`.
+    pub fn end_of_day_in<Tz: TimeZone>(&self, tz: &Tz) -> TimeStamp {
+        let date = self.time.with_timezone(tz).date_naive();
+        date.succ_opt()
+            .and_then(|date| day_start(tz, date))
+            .unwrap_or(*self)
+    }
+}
+
+impl TimeStamp {
+    /// Rounds to a multiple of `unit` since the unix epoch (so days are UTC
+    /// days). Under `Rounding::Nearest`, times exactly halfway round up.
+    /// Results that would not be representable leave the time unchanged.
+    pub fn round_to(&self, unit: Unit, mode: Rounding) -> TimeStamp {
+        const NANOS: i128 = 1_000_000_000;
+        let nanos = i128::from(self.time.timestamp()) * NANOS
+            + i128::from(self.time.timestamp_subsec_nanos());
+        let size = i128::from(unit.seconds()) * NANOS;
+        let floor = nanos - nanos.rem_euclid(size);
+        let ceil = if floor == nanos { floor } else { floor + size };
+        let rounded = match mode {
+            Rounding::Floor => floor,
+            Rounding::Ceil => ceil,
+            Rounding::Nearest if (nanos - floor) * 2 >= size => ceil,
+            Rounding::Nearest => floor,
+        };
+        i64::try_from(rounded.div_euclid(NANOS))
+            .ok()
+            .and_then(|seconds| Utc.timestamp_opt(seconds, 0).single())
+            .map_or(*self, |time| TimeStamp { time })
+    }
+}
+
+/// The first instant of `date` in `tz`, see `TimeStamp::start_of_day_in`.
+fn day_start<Tz: TimeZone>(tz: &Tz, date: NaiveDate) -> Option<TimeStamp> {
+    let midnight = date.and_hms_opt(0, 0, 0)?;
+    (0..24 * 60)
+        .find_map(|minute| {
+            tz.from_local_datetime(&(midnight + chrono::Duration::minutes(minute)))
+                .earliest()
+        })
+        .map(|time| TimeStamp {
+            time: time.with_timezone(&Utc),
+        })
+}
+
+impl From<DateTime<Utc>> for TimeStamp {
+    fn from(time: DateTime<Utc>) -> TimeStamp {
+        TimeStamp { time }
+    }
+}
+
+impl From<TimeStamp> for DateTime<Utc> {
+    fn from(timestamp: TimeStamp) -> DateTime<Utc> {
+        timestamp.time
+    }
+}
+
+impl Counter<TimeStamp> {
+    /// Rounds `start` with `TimeStamp::round_to`, e.g. to the nearest minute.
+    pub fn snap_start(&mut self, unit: Unit, mode: Rounding) {
+        self.start = self.start.round_to(unit, mode);
+        self.touch();
+    }
+
+    /// Counts down from now until the next midnight (UTC).
+    pub fn down_to_midnight() -> Counter<TimeStamp> {
+        let now = TimeStamp::now();
+        Counter::down(Some(now), Some(now.end_of_day())).stamped(now)
+    }
+
+    /// Converts both endpoints into `tz`, e.g. for display in local time.
+    pub fn into_datetime<Tz: TimeZone>(self, tz: &Tz) -> Counter<DateTime<Tz>> {
+        self.map_times(|time| time.time.with_timezone(tz))
+    }
+
+    /// Like `Counter::from_file`, but reads `start` and `end` with
+    /// `TimeStamp::parse_flexible`, for hand-edited files.
+    pub fn from_file_lenient<P: AsRef<Path>>(path: P) -> io::Result<Counter<TimeStamp>> {
+        Self::from_file_with(path, |s| TimeStamp::parse_flexible(s).ok())
+    }
+}
+
+impl Counter<TimeStamp> {
+    /// Builds a counter from a short spec anchored at now, e.g. as typed into
+    /// a launcher. The grammar, with keywords in any case, is
+    ///
+    /// ```text
+    /// spec := [down | up] <duration>
+    ///       | [down | up] until <time>
+    ///       | [up] from <time>
+    /// ```
+    ///
+    /// where `<duration>` is anything `TimeUnits::parse_any` accepts (`25m`,
+    /// `1h30m`, `90s`), and `<time>` is anything `TimeStamp::parse_flexible`
+    /// accepts, or `HH:MM(:SS)` in UTC. Counting down is the default.
+    /// `until` targets the next occurrence of a time of day, and `from` the
+    /// latest one. A duration counting up becomes the counter's `end`.
+    /// ```rust
+    /// # use countrs::{Counter, Direction};
+    /// # use countrs::types::TimeStamp;
+    /// let pomodoro = Counter::parse_spec("down 25m").unwrap();
+    /// assert_eq!(pomodoro.to_string(), "00:24:59");
+    ///
+    /// let shift = Counter::parse_spec("UP from 09:00").unwrap();
+    /// assert_eq!(shift.direction, Direction::Up);
+    ///
+    /// let error = Counter::parse_spec("down 25x").unwrap_err();
+    /// assert_eq!(error.token(), "25x");
+    /// ```
+    pub fn parse_spec(spec: &str) -> Result<Counter<TimeStamp>, InvalidSpec> {
+        Self::parse_spec_at(spec, TimeStamp::now())
+    }
+
+    pub(crate) fn parse_spec_at(
+        spec: &str,
+        now: TimeStamp,
+    ) -> Result<Counter<TimeStamp>, InvalidSpec> {
+        let mut words = spec.split_whitespace().peekable();
+        let direction = match words.peek() {
+            Some(word) if word.eq_ignore_ascii_case("down") => Some(Direction::Down),
+            Some(word) if word.eq_ignore_ascii_case("up") => Some(Direction::Up),
+            _ => None,
+        };
+        if direction.is_some() {
+            words.next();
+        }
+        let Some(word) = words.next() else {
+            return Err(InvalidSpec::new(
+                "",
+                "expected a duration, `until` or `from`",
+            ));
+        };
+        let rest = words.collect::<Vec<_>>().join(" ");
+
+        if word.eq_ignore_ascii_case("until") || word.eq_ignore_ascii_case("from") {
+            let forward = word.eq_ignore_ascii_case("until");
+            if rest.is_empty() {
+                return Err(InvalidSpec::new(word, "expected a time"));
+            }
+            let time = spec_time(&rest, now, forward)
+                .ok_or(InvalidSpec::new(&rest, "expected a time or HH:MM"))?;
+            return match (forward, direction) {
+                (true, Some(Direction::Up)) => Ok(Counter::up(Some(now), Some(time))),
+                (true, _) => Ok(Counter::down(Some(now), Some(time))),
+                (false, Some(Direction::Down)) => Err(InvalidSpec::new(
+                    word,
+                    "only counting up can start at a time",
+                )),
+                (false, _) => Ok(Counter::up(Some(time), Some(time))),
+            };
+        }
+
+        let duration = match rest.is_empty() {
+            true => word.to_string(),
+            false => format!("{word} {rest}"),
+        };
+        let parsed = Duration::parse_any(&duration)
+            .or_else(|error| Duration::parse_any(&duration.to_ascii_lowercase()).map_err(|_| error))
+            .map_err(|error| InvalidSpec::new(&duration, error.reason()))?;
+        if parsed.num_seconds() < 0 {
+            return Err(InvalidSpec::new(&duration, "duration must not be negative"));
+        }
+        let end = now
+            .add_seconds(parsed)
+            .map_err(|_| InvalidSpec::new(&duration, "duration is too large"))?;
+        Ok(match direction {
+            Some(Direction::Up) => Counter::up(Some(now), Some(end)),
+            _ => Counter::down(Some(now), Some(end)),
+        })
+    }
+}
+
+/// Reads a `<time>` of `Counter::parse_spec`. Times of day are taken on the
+/// day of `now`, then moved to the next (`forward`) or previous day so they
+/// fall after or before `now` respectively.
+fn spec_time(string: &str, now: TimeStamp, forward: bool) -> Option<TimeStamp> {
+    if let Ok(time) = TimeStamp::parse_flexible(string) {
+        return Some(time);
+    }
+    let time = NaiveTime::parse_from_str(string, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(string, TIME_OF_DAY_FORMAT))
+        .ok()?;
+    let today = TimeStamp {
+        time: Utc.from_utc_datetime(&now.time.date_naive().and_time(time)),
+    };
+    let day = chrono::Duration::days(1);
+    let time = match (forward, today.time > now.time) {
+        (true, false) => today.time.checked_add_signed(day)?,
+        (false, true) => today.time.checked_sub_signed(day)?,
+        _ => today.time,
+    };
+    Some(TimeStamp { time })
+}
+
+impl Counter<TimeStamp> {
+    /// Like `deadline_string` for counters of `DateTime`s
+    pub fn deadline_string(&self) -> String {
+        self.deadline_string_at(&Local::now())
+    }
+
+    /// Like `deadline_string_at` for counters of `DateTime`s
+    pub fn deadline_string_at<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> String
+    where
+        Tz::Offset: Display,
+    {
+        deadline_string(&self.end.time, now)
+    }
+}
+
+impl<Tz: TimeZone> Counter<DateTime<Tz>> {
+    /// Converts both endpoints back into `TimeStamp`s.
+    pub fn into_timestamps(self) -> Counter<TimeStamp> {
+        self.map_times(|time| TimeStamp::from(time.with_timezone(&Utc)))
+    }
+}
+
+impl Counter<TimeOfDay> {
+    /// Counts down from now to the next occurrence of `target`, and is finished
+    /// from then on, like any countdown. A `target` less than a second ago
+    /// counts as now, so the counter starts out finished instead of waiting
+    /// for the following day.
+    pub fn down_to(target: TimeOfDay) -> Counter<TimeOfDay> {
+        let now = TimeOfDay::now();
+        let start = match now - target < Duration::seconds(1) {
+            true => target,
+            false => now,
+        };
+        Counter::down(Some(start), Some(target)).stamped(now)
+    }
+}
+
 impl Display for TimeStamp {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.time.to_rfc3339())
     }
 }
 
+/// Parses RFC 3339, falling back to RFC 2822 (`"Tue, 1 Jul 2003 10:52:37 +0200"`).
+/// Either way the result is normalized into UTC, and `Display` emits RFC 3339.
 impl FromStr for TimeStamp {
     type Err = TimeParserError;
 
     fn from_str(string: &str) -> Result<Self, Self::Err> {
-        Ok(TimeStamp {
-            time: DateTime::parse_from_rfc3339(string)
-                .map_err(|_| TimeParserError)?
-                .into(),
-        })
+        let time = DateTime::parse_from_rfc3339(string)
+            .or_else(|_| DateTime::parse_from_rfc2822(string))
+            .map_err(|_| TimeParserError::new(&["RFC 3339", "RFC 2822"]))?;
+        Ok(TimeStamp { time: time.into() })
+    }
+}
+
+/// Displayed as "HH:MM:SS", dropping fractional seconds
+impl Display for TimeOfDay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.format(TIME_OF_DAY_FORMAT))
+    }
+}
+
+impl FromStr for TimeOfDay {
+    type Err = TimeParserError;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        NaiveTime::parse_from_str(string, TIME_OF_DAY_FORMAT)
+            .map(TimeOfDay)
+            .map_err(|_| TimeParserError::new(&[TIME_OF_DAY_FORMAT]))
+    }
+}
+
+/// See `TimeUnits::parse_any` for the accepted formats.
+impl FromStr for Duration {
+    type Err = DurationParserError;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        Duration::parse_any(string)
     }
 }
 
@@ -57,6 +524,16 @@ impl<T: Into<Duration>> Sub<T> for TimeStamp {
     }
 }
 
+impl Neg for Duration {
+    type Output = Duration;
+
+    fn neg(self) -> Self::Output {
+        Duration {
+            duration: -self.duration,
+        }
+    }
+}
+
 impl Sub<Self> for TimeStamp {
     type Output = Duration;
 
@@ -67,6 +544,46 @@ impl Sub<Self> for TimeStamp {
     }
 }
 
+/// Wraps into `0..24h`
+impl Sub<Self> for TimeOfDay {
+    type Output = Duration;
+
+    fn sub(self, other: Self) -> Self::Output {
+        let difference = self.0 - other.0;
+        let duration = if difference < chrono::Duration::zero() {
+            difference + chrono::Duration::days(1)
+        } else {
+            difference
+        };
+        Duration { duration }
+    }
+}
+
+impl Time for TimeOfDay {
+    type Duration = Duration;
+
+    /// The current time of day in UTC
+    fn now() -> Self {
+        TimeOfDay(Utc::now().time())
+    }
+
+    /// Wraps around midnight, so this never overflows.
+    fn add_seconds(self, duration: Duration) -> Result<TimeOfDay, TimeOverflow> {
+        Ok(TimeOfDay(
+            self.0.overflowing_add_signed(duration.duration).0,
+        ))
+    }
+
+    /// `self` is past `end` once the time since `start` reaches the length of
+    /// the window, both measured forward around midnight.
+    fn until_end(self, start: Self, end: Self) -> Duration {
+        match self - start < end - start {
+            true => end - self,
+            false => -(self - end),
+        }
+    }
+}
+
 impl Time for TimeStamp {
     type Duration = Duration;
 
@@ -82,6 +599,18 @@ impl Time for TimeStamp {
                 .ok_or(TimeOverflow)?,
         })
     }
+
+    fn max_value() -> Option<TimeStamp> {
+        Some(TimeStamp {
+            time: DateTime::<Utc>::MAX_UTC,
+        })
+    }
+
+    fn min_value() -> Option<TimeStamp> {
+        Some(TimeStamp {
+            time: DateTime::<Utc>::MIN_UTC,
+        })
+    }
 }
 
 impl TimeUnits for Duration {
@@ -94,6 +623,52 @@ impl TimeUnits for Duration {
     fn num_seconds(&self) -> i64 {
         self.duration.num_seconds()
     }
+
+    fn try_seconds(seconds: i64) -> Result<Self, TimeOverflow> {
+        Ok(Duration {
+            duration: checked_seconds(seconds).ok_or(TimeOverflow)?,
+        })
+    }
+
+    fn subsec_nanos(&self) -> i64 {
+        TimeUnits::subsec_nanos(&self.duration)
+    }
+
+    /// Like the provided method, but shows "<1s" for sub-second durations.
+    fn humanize(&self) -> String {
+        humanize(self.num_seconds(), self.subsec_nanos())
+    }
+}
+
+/// Shows whole seconds, plus the sub-second part if there is one,
+/// e.g. `Duration { seconds: 90 }` or `Duration { seconds: 1, nanos: 500000000 }`
+impl fmt::Debug for Duration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let seconds = self.duration.num_seconds();
+        let nanos = (self.duration - chrono::Duration::seconds(seconds))
+            .num_nanoseconds()
+            .unwrap_or_default();
+        let mut debug = f.debug_struct("Duration");
+        debug.field("seconds", &seconds);
+        if nanos != 0 {
+            debug.field("nanos", &nanos);
+        }
+        debug.finish()
+    }
+}
+
+/// Whole seconds, e.g. "90s"
+#[cfg(feature = "defmt")]
+impl defmt::Format for Duration {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(f, "{=i64}s", self.duration.num_seconds())
+    }
+}
+
+impl From<chrono::Duration> for Duration {
+    fn from(duration: chrono::Duration) -> Duration {
+        Duration { duration }
+    }
 }
 
 impl From<i64> for Duration {
@@ -101,3 +676,5 @@ impl From<i64> for Duration {
         Duration::seconds(num)
     }
 }
+
+impl_duration_cmp!(TimeStamp => Duration);
@@ -0,0 +1,140 @@
+//! This module provides `UnixSeconds` and `Seconds` types which implement
+//! the `Time` and `TimeUnits` traits respectively, without depending on
+//! `chrono`. Both are plain whole seconds, displayed and parsed as integers.
+use crate::errors::{DurationParserError, TimeOverflow, TimeParserError};
+use crate::times::{Time, TimeUnits};
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Add, Neg, Sub};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Seconds since the unix epoch
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Default, Clone, Copy, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct UnixSeconds(pub i64);
+
+/// A signed number of seconds
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Default, Clone, Copy, Hash)]
+pub struct Seconds(pub i64);
+
+impl Display for UnixSeconds {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for UnixSeconds {
+    type Err = TimeParserError;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        string
+            .parse()
+            .map(UnixSeconds)
+            .map_err(|_| TimeParserError::new(&["unix seconds"]))
+    }
+}
+
+/// See `TimeUnits::parse_any` for the accepted formats.
+impl FromStr for Seconds {
+    type Err = DurationParserError;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        Seconds::parse_any(string)
+    }
+}
+
+/// Saturates at the limits of `i64`.
+impl<T: Into<Seconds>> Add<T> for UnixSeconds {
+    type Output = UnixSeconds;
+
+    fn add(self, seconds: T) -> Self::Output {
+        UnixSeconds(self.0.saturating_add(seconds.into().0))
+    }
+}
+
+/// Saturates at the limits of `i64`.
+impl<T: Into<Seconds>> Sub<T> for UnixSeconds {
+    type Output = UnixSeconds;
+
+    fn sub(self, seconds: T) -> Self::Output {
+        UnixSeconds(self.0.saturating_sub(seconds.into().0))
+    }
+}
+
+/// Saturates at the limits of `i64`.
+impl Sub<Self> for UnixSeconds {
+    type Output = Seconds;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Seconds(self.0.saturating_sub(other.0))
+    }
+}
+
+/// Saturates at the limits of `i64`.
+impl Neg for Seconds {
+    type Output = Seconds;
+
+    fn neg(self) -> Self::Output {
+        Seconds(self.0.saturating_neg())
+    }
+}
+
+impl Time for UnixSeconds {
+    type Duration = Seconds;
+
+    /// Whole seconds are floored, also for system clocks set before 1970.
+    fn now() -> Self {
+        match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(since) => UnixSeconds(since.as_secs() as i64),
+            Err(before) => {
+                let before = before.duration();
+                let partial = u64::from(before.subsec_nanos() > 0);
+                UnixSeconds(-((before.as_secs() + partial) as i64))
+            }
+        }
+    }
+
+    fn add_seconds(self, duration: Seconds) -> Result<Self, TimeOverflow> {
+        Ok(UnixSeconds(
+            self.0.checked_add(duration.0).ok_or(TimeOverflow)?,
+        ))
+    }
+
+    fn max_value() -> Option<Self> {
+        Some(UnixSeconds(i64::MAX))
+    }
+
+    fn min_value() -> Option<Self> {
+        Some(UnixSeconds(i64::MIN))
+    }
+}
+
+impl TimeUnits for Seconds {
+    fn seconds(seconds: i64) -> Self {
+        Seconds(seconds)
+    }
+
+    fn num_seconds(&self) -> i64 {
+        self.0
+    }
+}
+
+/// e.g. "90s"
+#[cfg(feature = "defmt")]
+impl defmt::Format for Seconds {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(f, "{=i64}s", self.0)
+    }
+}
+
+impl From<i64> for Seconds {
+    fn from(num: i64) -> Seconds {
+        Seconds(num)
+    }
+}
+
+impl_duration_cmp!(UnixSeconds => Seconds);